@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+use futures::future::join_all;
+
+use crate::alerts::types::Alert;
+use crate::alerts::Alerts;
+
+/// FanOutAlerts sends every alert to several alert services at once, e.g. SNS for the paging
+/// and a Slack channel for the team.
+///
+/// An alert is sent to all the services even if some of them fail, it only fails if one of them
+/// did.
+pub struct FanOutAlerts {
+    sinks: Vec<Box<dyn Alerts>>,
+}
+
+impl FanOutAlerts {
+    pub fn new(sinks: Vec<Box<dyn Alerts>>) -> Self {
+        Self { sinks }
+    }
+}
+
+#[async_trait]
+impl Alerts for FanOutAlerts {
+    async fn send_alert_message(&self, message_body: String) -> color_eyre::Result<()> {
+        self.send_alert(&Alert::from(message_body)).await
+    }
+
+    async fn send_alert(&self, alert: &Alert) -> color_eyre::Result<()> {
+        let results = join_all(self.sinks.iter().map(|sink| sink.send_alert(alert))).await;
+        let failures: Vec<String> =
+            results.into_iter().filter_map(|result| result.err()).map(|e| e.to_string()).collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(eyre!(
+                "Failed to send the alert to {} of {} alert services: {}",
+                failures.len(),
+                self.sinks.len(),
+                failures.join("; ")
+            ))
+        }
+    }
+
+    async fn get_topic_name(&self) -> String {
+        let mut topic_names = Vec::with_capacity(self.sinks.len());
+        for sink in &self.sinks {
+            topic_names.push(sink.get_topic_name().await);
+        }
+        topic_names.join(",")
+    }
+
+    /// Sets up every alert service with its own topic, `topic_name` is the list of all of them.
+    async fn create_alert(&self, _topic_name: &str) -> color_eyre::Result<()> {
+        for sink in &self.sinks {
+            sink.setup().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod fan_out_test {
+    use std::sync::Arc;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::alerts::in_memory::InMemoryAlerts;
+    use crate::alerts::MockAlerts;
+
+    /// Lets the test read the alerts of a sink owned by the fan out.
+    struct SharedAlerts(Arc<InMemoryAlerts>);
+
+    #[async_trait]
+    impl Alerts for SharedAlerts {
+        async fn send_alert_message(&self, message_body: String) -> color_eyre::Result<()> {
+            self.0.send_alert_message(message_body).await
+        }
+
+        async fn get_topic_name(&self) -> String {
+            self.0.get_topic_name().await
+        }
+
+        async fn create_alert(&self, topic_name: &str) -> color_eyre::Result<()> {
+            self.0.create_alert(topic_name).await
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_fan_out_sends_to_every_sink() {
+        let first = Arc::new(InMemoryAlerts::new());
+        let second = Arc::new(InMemoryAlerts::new());
+        let mut failing = MockAlerts::new();
+        failing.expect_send_alert().times(1).returning(|_| Err(eyre!("unreachable")));
+
+        let alerts = FanOutAlerts::new(vec![
+            Box::new(SharedAlerts(first.clone())),
+            Box::new(failing),
+            Box::new(SharedAlerts(second.clone())),
+        ]);
+        let result = alerts.send_alert_message("Failed to run worker".to_string()).await;
+
+        assert!(result.unwrap_err().to_string().contains("1 of 3 alert services"));
+        assert_eq!(first.messages(), vec!["[ERROR] Failed to run worker".to_string()]);
+        assert_eq!(second.messages(), first.messages());
+    }
+}
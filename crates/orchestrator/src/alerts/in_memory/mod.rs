@@ -0,0 +1,44 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+
+use crate::alerts::Alerts;
+
+const IN_MEMORY_TOPIC_NAME: &str = "in_memory_alerts";
+
+/// InMemoryAlerts logs the alerts and keeps them in the process memory instead of
+/// publishing them to an alert service. It's meant for local runs and tests.
+#[derive(Default)]
+pub struct InMemoryAlerts {
+    messages: Mutex<Vec<String>>,
+}
+
+impl InMemoryAlerts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the alerts sent so far, oldest first.
+    pub fn messages(&self) -> Vec<String> {
+        self.messages.lock().map(|messages| messages.clone()).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl Alerts for InMemoryAlerts {
+    async fn send_alert_message(&self, message_body: String) -> color_eyre::Result<()> {
+        tracing::warn!(category = "alerts", "Alert: {}", message_body);
+        self.messages.lock().map_err(|e| eyre!("In memory alerts lock is poisoned: {}", e))?.push(message_body);
+        Ok(())
+    }
+
+    async fn get_topic_name(&self) -> String {
+        IN_MEMORY_TOPIC_NAME.to_string()
+    }
+
+    /// Nothing to create, the alerts are kept in memory.
+    async fn create_alert(&self, _topic_name: &str) -> color_eyre::Result<()> {
+        Ok(())
+    }
+}
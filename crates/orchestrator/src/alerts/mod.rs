@@ -2,12 +2,22 @@ use async_trait::async_trait;
 use mockall::automock;
 
 pub mod aws_sns;
+pub mod fan_out;
+pub mod in_memory;
+pub mod types;
+pub mod webhook;
+
+use types::Alert;
 
 #[automock]
 #[async_trait]
 pub trait Alerts: Send + Sync {
     /// To send an alert message to our alert service
     async fn send_alert_message(&self, message_body: String) -> color_eyre::Result<()>;
+    /// To send a structured alert, the services without structured payloads get it as a message
+    async fn send_alert(&self, alert: &Alert) -> color_eyre::Result<()> {
+        self.send_alert_message(alert.to_string()).await
+    }
     async fn get_topic_name(&self) -> String;
     async fn create_alert(&self, topic_name: &str) -> color_eyre::Result<()>;
     async fn setup(&self) -> color_eyre::Result<()> {
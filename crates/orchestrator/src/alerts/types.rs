@@ -0,0 +1,120 @@
+use std::fmt;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::jobs::types::{JobItem, JobType};
+
+/// How urgently an alert needs someone's attention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, strum_macros::Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    /// A failure which the orchestrator may recover from on its own
+    Error,
+    /// A failure which needs a manual intervention, e.g. a failed job
+    Critical,
+}
+
+/// An alert along with the context needed to act on it. The sinks with structured payloads send
+/// the fields as they are, the other ones send the alert as a single line of text.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    pub severity: AlertSeverity,
+    pub summary: String,
+    pub job_id: Option<Uuid>,
+    pub job_type: Option<JobType>,
+    /// Internal id of the job, i.e. the block it handles for most job types
+    pub block: Option<String>,
+    pub failure_reason: Option<String>,
+}
+
+impl Alert {
+    pub fn new(severity: AlertSeverity, summary: impl Into<String>) -> Self {
+        Self { severity, summary: summary.into(), job_id: None, job_type: None, block: None, failure_reason: None }
+    }
+
+    /// An alert about `job`.
+    pub fn for_job(severity: AlertSeverity, summary: impl Into<String>, job: &JobItem) -> Self {
+        Self {
+            job_id: Some(job.id),
+            job_type: Some(job.job_type.clone()),
+            block: Some(job.internal_id.clone()),
+            ..Self::new(severity, summary)
+        }
+    }
+
+    /// The alert sent when `job` is moved to `Failed` because of `reason`.
+    pub fn job_failed(job: &JobItem, reason: impl Into<String>) -> Self {
+        Self::for_job(AlertSeverity::Critical, format!("{:?} job failed", job.job_type), job)
+            .with_failure_reason(reason)
+    }
+
+    pub fn with_failure_reason(mut self, reason: impl Into<String>) -> Self {
+        self.failure_reason = Some(reason.into());
+        self
+    }
+
+    /// The context of the alert as display names and values, leaving out the missing ones.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        [
+            ("Job ID", self.job_id.map(|job_id| job_id.to_string())),
+            ("Job type", self.job_type.as_ref().map(|job_type| format!("{:?}", job_type))),
+            ("Block", self.block.clone()),
+            ("Failure reason", self.failure_reason.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect()
+    }
+}
+
+/// Plain messages are alerts without context, sent by the places which only have an error at hand.
+impl From<String> for Alert {
+    fn from(message: String) -> Self {
+        Self::new(AlertSeverity::Error, message)
+    }
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.severity.to_string().to_uppercase(), self.summary)?;
+        for (name, value) in self.fields() {
+            write!(f, " | {}: {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod alert_types_test {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::jobs::types::JobStatus;
+    use crate::tests::utils::build_job_item;
+
+    #[rstest]
+    fn test_job_failed_alert() {
+        let mut job = build_job_item(JobType::SnosRun, JobStatus::LockedForProcessing, 42);
+        job.id = Uuid::nil();
+
+        let alert = Alert::job_failed(&job, "Processing failed: boom");
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert_eq!(alert.block.as_deref(), Some("42"));
+        assert_eq!(
+            alert.to_string(),
+            "[CRITICAL] SnosRun job failed | Job ID: 00000000-0000-0000-0000-000000000000 | Job type: SnosRun | \
+             Block: 42 | Failure reason: Processing failed: boom"
+        );
+    }
+
+    #[rstest]
+    fn test_plain_message_alert() {
+        let alert = Alert::from("Failed to run worker".to_string());
+        assert!(alert.fields().is_empty());
+        assert_eq!(alert.to_string(), "[ERROR] Failed to run worker");
+    }
+}
@@ -0,0 +1,304 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use color_eyre::eyre::{eyre, WrapErr};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::alerts::types::{Alert, AlertSeverity};
+use crate::alerts::Alerts;
+
+/// How long a webhook is given to accept an alert.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The payload posted to the webhook.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookFormat {
+    /// The alert as a JSON object, or rendered through the JSON template if any
+    Generic { template: Option<String> },
+    /// A message with a colored attachment holding the alert fields, as accepted by the Slack and
+    /// Mattermost incoming webhooks
+    Slack,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookValidatedArgs {
+    pub url: Url,
+    /// Headers sent along every alert, e.g. the authorization of the webhook
+    pub headers: Vec<(String, String)>,
+    pub format: WebhookFormat,
+}
+
+/// WebhookAlerts posts the alerts as JSON to an HTTP endpoint, e.g. a Slack or Mattermost
+/// incoming webhook, or an incident management service.
+pub struct WebhookAlerts {
+    client: reqwest::Client,
+    url: Url,
+    format: WebhookFormat,
+}
+
+impl WebhookAlerts {
+    pub fn new_with_args(webhook_params: &WebhookValidatedArgs) -> Self {
+        let headers: HeaderMap = webhook_params
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_bytes(name.as_bytes()).expect("Invalid alert webhook header name"),
+                    HeaderValue::from_str(value).expect("Invalid alert webhook header value"),
+                )
+            })
+            .collect();
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .expect("Failed to build the alert webhook client");
+        Self { client, url: webhook_params.url.clone(), format: webhook_params.format.clone() }
+    }
+}
+
+#[async_trait]
+impl Alerts for WebhookAlerts {
+    async fn send_alert_message(&self, message_body: String) -> color_eyre::Result<()> {
+        self.send_alert(&Alert::from(message_body)).await
+    }
+
+    async fn send_alert(&self, alert: &Alert) -> color_eyre::Result<()> {
+        let payload = self.format.payload(alert)?;
+        // the errors leave the url out, the ones of Slack webhooks hold their secret
+        self.client
+            .post(self.url.clone())
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| eyre!("Failed to post the alert to the webhook: {}", e.without_url()))?;
+        Ok(())
+    }
+
+    async fn get_topic_name(&self) -> String {
+        self.url.host_str().unwrap_or_default().to_string()
+    }
+
+    /// Nothing to create, the webhook receives the alerts as they are.
+    async fn create_alert(&self, _topic_name: &str) -> color_eyre::Result<()> {
+        Ok(())
+    }
+}
+
+impl WebhookFormat {
+    /// The body posted to the webhook for `alert`.
+    pub fn payload(&self, alert: &Alert) -> color_eyre::Result<Value> {
+        match self {
+            WebhookFormat::Generic { template: None } => Ok(serde_json::to_value(alert)?),
+            WebhookFormat::Generic { template: Some(template) } => render_template(template, alert),
+            WebhookFormat::Slack => Ok(slack_payload(alert)),
+        }
+    }
+}
+
+/// Replaces the `{{placeholder}}`s of `template` with the fields of `alert`, escaped so that they
+/// can be used within JSON strings, e.g. `{"title": "{{summary}}", "details": "{{text}}"}`. The
+/// missing fields are replaced with empty strings.
+pub fn render_template(template: &str, alert: &Alert) -> color_eyre::Result<Value> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let placeholder = &rest[start + 2..];
+        let end = placeholder.find("}}").ok_or_else(|| eyre!("Unclosed placeholder in the alert webhook template"))?;
+        let name = placeholder[..end].trim();
+        let value = placeholder_value(alert, name)
+            .ok_or_else(|| eyre!("Unknown placeholder {:?} in the alert webhook template", name))?;
+        let escaped = serde_json::to_string(&value)?;
+        rendered.push_str(&escaped[1..escaped.len() - 1]);
+        rest = &placeholder[end + 2..];
+    }
+    rendered.push_str(rest);
+    serde_json::from_str(&rendered).wrap_err("The rendered alert webhook template isn't valid JSON")
+}
+
+/// Parses the headers sent along the alerts, e.g. `Authorization=Bearer token,X-Team=sequencer`.
+pub fn parse_webhook_headers(headers: &str) -> Result<Vec<(String, String)>, String> {
+    headers
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, value) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid alert webhook header {:?}, expected Name=value", entry))?;
+            let (name, value) = (name.trim(), value.trim());
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("Invalid alert webhook header name {:?}", name))?;
+            HeaderValue::from_str(value)
+                .map_err(|_| format!("Invalid value for the alert webhook header {:?}", name))?;
+            Ok((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Checks that `template` only uses known placeholders and renders to valid JSON.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    render_template(template, &Alert::new(AlertSeverity::Info, "Template check")).map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn placeholder_value(alert: &Alert, name: &str) -> Option<String> {
+    let value = match name {
+        "severity" => alert.severity.to_string(),
+        "summary" => alert.summary.clone(),
+        "text" => alert.to_string(),
+        "job_id" => alert.job_id.map(|job_id| job_id.to_string()).unwrap_or_default(),
+        "job_type" => alert.job_type.as_ref().map(|job_type| format!("{:?}", job_type)).unwrap_or_default(),
+        "block" => alert.block.clone().unwrap_or_default(),
+        "failure_reason" => alert.failure_reason.clone().unwrap_or_default(),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn slack_payload(alert: &Alert) -> Value {
+    let fields: Vec<Value> = alert
+        .fields()
+        .into_iter()
+        // the long values, e.g. failure reasons, get a line of their own
+        .map(|(name, value)| json!({ "title": name, "short": value.len() <= 40, "value": value }))
+        .collect();
+    json!({
+        "text": format!("*[{}]* {}", alert.severity.to_string().to_uppercase(), alert.summary),
+        "attachments": [{
+            "fallback": alert.to_string(),
+            "color": severity_color(alert.severity),
+            "fields": fields,
+        }],
+    })
+}
+
+fn severity_color(severity: AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Info => "#439fe0",
+        AlertSeverity::Warning => "#daa038",
+        AlertSeverity::Error => "#e01e5a",
+        AlertSeverity::Critical => "#8b0000",
+    }
+}
+
+#[cfg(test)]
+mod webhook_test {
+    use httpmock::prelude::*;
+    use rstest::rstest;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::jobs::types::JobType;
+
+    fn failed_job_alert() -> Alert {
+        Alert {
+            job_id: Some(Uuid::nil()),
+            job_type: Some(JobType::DataSubmission),
+            block: Some("7".to_string()),
+            ..Alert::new(AlertSeverity::Critical, "DataSubmission job failed")
+        }
+        .with_failure_reason("Blob \"rejected\"")
+    }
+
+    #[rstest]
+    fn test_generic_payload() {
+        let payload = WebhookFormat::Generic { template: None }.payload(&failed_job_alert()).unwrap();
+        assert_eq!(
+            payload,
+            json!({
+                "severity": "critical",
+                "summary": "DataSubmission job failed",
+                "job_id": "00000000-0000-0000-0000-000000000000",
+                "job_type": "DataSubmission",
+                "block": "7",
+                "failure_reason": "Blob \"rejected\"",
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_template_payload() {
+        let template =
+            r#"{"title": "{{ summary }} ({{severity}})", "block": "{{block}}", "reason": "{{failure_reason}}"}"#;
+        let payload = render_template(template, &failed_job_alert()).unwrap();
+        assert_eq!(
+            payload,
+            json!({ "title": "DataSubmission job failed (critical)", "block": "7", "reason": "Blob \"rejected\"" })
+        );
+    }
+
+    #[rstest]
+    #[case(r#"{"text": "{{message}}"}"#)]
+    #[case(r#"{"text": "{{summary"}"#)]
+    #[case(r#"{"text": {{summary}}}"#)]
+    fn test_invalid_template(#[case] template: &str) {
+        assert!(validate_template(template).is_err());
+    }
+
+    #[rstest]
+    fn test_parse_webhook_headers() {
+        let headers = parse_webhook_headers("Authorization=Basic dXNlcjpwYXNz==, X-Team=sequencer").unwrap();
+        assert_eq!(
+            headers,
+            vec![
+                ("Authorization".to_string(), "Basic dXNlcjpwYXNz==".to_string()),
+                ("X-Team".to_string(), "sequencer".to_string())
+            ]
+        );
+        assert!(parse_webhook_headers("Authorization").is_err());
+        assert!(parse_webhook_headers("Bad Name=value").is_err());
+    }
+
+    #[rstest]
+    fn test_slack_payload() {
+        let payload = WebhookFormat::Slack.payload(&failed_job_alert()).unwrap();
+        assert_eq!(payload["text"], "*[CRITICAL]* DataSubmission job failed");
+        let attachment = &payload["attachments"][0];
+        assert_eq!(attachment["color"], "#8b0000");
+        assert_eq!(attachment["fields"][2], json!({ "title": "Block", "value": "7", "short": true }));
+        assert_eq!(attachment["fields"][3]["value"], "Blob \"rejected\"");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_webhook_posts_alert_with_headers() {
+        let server = MockServer::start();
+        let webhook_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/alerts")
+                .header("authorization", "Bearer token")
+                .json_body(json!({ "text": "[ERROR] Failed to run worker" }));
+            then.status(200);
+        });
+
+        let alerts = WebhookAlerts::new_with_args(&WebhookValidatedArgs {
+            url: Url::parse(&server.url("/alerts")).unwrap(),
+            headers: vec![("Authorization".to_string(), "Bearer token".to_string())],
+            format: WebhookFormat::Generic { template: Some(r#"{"text": "{{text}}"}"#.to_string()) },
+        });
+        alerts.send_alert_message("Failed to run worker".to_string()).await.unwrap();
+
+        webhook_mock.assert();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_webhook_error_status_fails() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/alerts");
+            then.status(500);
+        });
+
+        let alerts = WebhookAlerts::new_with_args(&WebhookValidatedArgs {
+            url: Url::parse(&server.url("/alerts")).unwrap(),
+            headers: vec![],
+            format: WebhookFormat::Slack,
+        });
+        assert!(alerts.send_alert(&failed_job_alert()).await.is_err());
+    }
+}
@@ -1,8 +1,15 @@
 use crate::alerts::aws_sns::AWSSNSValidatedArgs;
+use crate::alerts::webhook::WebhookValidatedArgs;
 
 pub mod aws_sns;
+pub mod slack;
+pub mod webhook;
 
 #[derive(Clone, Debug)]
 pub enum AlertValidatedArgs {
     AWSSNS(AWSSNSValidatedArgs),
+    Webhook(WebhookValidatedArgs),
+    InMemory,
+    /// Several alert services, each alert is sent to all of them
+    FanOut(Vec<AlertValidatedArgs>),
 }
@@ -0,0 +1,15 @@
+use clap::Args;
+use url::Url;
+
+/// Parameters used to config the Slack or Mattermost alerts.
+#[derive(Debug, Clone, Default, Args)]
+#[group()]
+pub struct SlackCliArgs {
+    /// Post the alerts to a Slack or Mattermost incoming webhook
+    #[arg(long)]
+    pub slack_alerts: bool,
+
+    /// The URL of the incoming webhook.
+    #[arg(env = "MADARA_ORCHESTRATOR_SLACK_WEBHOOK_URL", long)]
+    pub slack_webhook_url: Option<Url>,
+}
@@ -0,0 +1,24 @@
+use clap::Args;
+use url::Url;
+
+/// Parameters used to config the generic alert webhook.
+#[derive(Debug, Clone, Default, Args)]
+#[group()]
+pub struct WebhookCliArgs {
+    /// Post the alerts to a webhook
+    #[arg(long)]
+    pub webhook_alerts: bool,
+
+    /// The URL the alerts are posted to.
+    #[arg(env = "MADARA_ORCHESTRATOR_ALERT_WEBHOOK_URL", long)]
+    pub alert_webhook_url: Option<Url>,
+
+    /// Headers sent along the alerts, as comma separated `Name=value` pairs.
+    #[arg(env = "MADARA_ORCHESTRATOR_ALERT_WEBHOOK_HEADERS", long)]
+    pub alert_webhook_headers: Option<String>,
+
+    /// JSON template of the payload, with `{{placeholder}}`s replaced by the alert fields. The
+    /// alert is posted as a JSON object if not set.
+    #[arg(env = "MADARA_ORCHESTRATOR_ALERT_WEBHOOK_TEMPLATE", long)]
+    pub alert_webhook_template: Option<String>,
+}
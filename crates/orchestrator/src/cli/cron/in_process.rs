@@ -0,0 +1,36 @@
+use clap::Args;
+
+/// Parameters used to trigger the workers from within the orchestrator.
+///
+/// A schedule is either an interval in seconds (e.g. `60`) or a cron expression with seconds
+/// (e.g. `0 */5 * * * *`). An empty schedule disables the worker.
+#[derive(Debug, Clone, Default, Args)]
+pub struct InProcessCronCliArgs {
+    /// Use the in-process cron scheduler instead of an external trigger
+    #[arg(long)]
+    pub in_process_cron: bool,
+
+    /// The schedule of the SNOS worker.
+    #[arg(env = "MADARA_ORCHESTRATOR_CRON_SNOS_SCHEDULE", long, default_value = Some("60"))]
+    pub cron_snos_schedule: Option<String>,
+
+    /// The schedule of the proving worker.
+    #[arg(env = "MADARA_ORCHESTRATOR_CRON_PROVING_SCHEDULE", long, default_value = Some("60"))]
+    pub cron_proving_schedule: Option<String>,
+
+    /// The schedule of the proof registration worker.
+    #[arg(env = "MADARA_ORCHESTRATOR_CRON_PROOF_REGISTRATION_SCHEDULE", long, default_value = Some("60"))]
+    pub cron_proof_registration_schedule: Option<String>,
+
+    /// The schedule of the data submission worker.
+    #[arg(env = "MADARA_ORCHESTRATOR_CRON_DATA_SUBMISSION_SCHEDULE", long, default_value = Some("60"))]
+    pub cron_data_submission_schedule: Option<String>,
+
+    /// The schedule of the update state worker.
+    #[arg(env = "MADARA_ORCHESTRATOR_CRON_UPDATE_STATE_SCHEDULE", long, default_value = Some("60"))]
+    pub cron_update_state_schedule: Option<String>,
+
+    /// The schedule of the stuck job reaper.
+    #[arg(env = "MADARA_ORCHESTRATOR_CRON_STUCK_JOB_REAPER_SCHEDULE", long, default_value = Some("300"))]
+    pub cron_stuck_job_reaper_schedule: Option<String>,
+}
@@ -1,8 +1,11 @@
 use crate::cron::event_bridge::AWSEventBridgeValidatedArgs;
+use crate::cron::in_process::InProcessCronValidatedArgs;
 
 pub mod event_bridge;
+pub mod in_process;
 
 #[derive(Clone, Debug)]
 pub enum CronValidatedArgs {
     AWSEventBridge(AWSEventBridgeValidatedArgs),
+    InProcess(InProcessCronValidatedArgs),
 }
@@ -1,8 +1,12 @@
 use crate::database::mongodb::MongoDBValidatedArgs;
+use crate::database::postgres::PostgresValidatedArgs;
 
 pub mod mongodb;
+pub mod postgres;
 
 #[derive(Debug, Clone)]
 pub enum DatabaseValidatedArgs {
     MongoDB(MongoDBValidatedArgs),
+    Postgres(PostgresValidatedArgs),
+    InMemory,
 }
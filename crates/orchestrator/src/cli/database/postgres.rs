@@ -0,0 +1,18 @@
+use clap::Args;
+
+/// Parameters used to config Postgres.
+#[derive(Debug, Clone, Args)]
+#[group(requires_all = ["postgres_connection_url"])]
+pub struct PostgresCliArgs {
+    /// Use the Postgres client
+    #[arg(long)]
+    pub postgres: bool,
+
+    /// The connection string to the Postgres server, including the database name.
+    #[arg(env = "MADARA_ORCHESTRATOR_POSTGRES_CONNECTION_URL", long)]
+    pub postgres_connection_url: Option<String>,
+
+    /// The maximum number of connections in the pool.
+    #[arg(env = "MADARA_ORCHESTRATOR_POSTGRES_MAX_CONNECTIONS", long, default_value = Some("10"))]
+    pub postgres_max_connections: Option<u32>,
+}
@@ -0,0 +1,99 @@
+use clap::{Args, Subcommand};
+use color_eyre::eyre::{eyre, WrapErr};
+use serde_json::Value;
+use url::Url;
+use uuid::Uuid;
+
+use crate::routes::types::DeadLetterActionRequest;
+
+/// Parameters used to inspect, replay and discard the dead letters through the API of a running
+/// orchestrator.
+#[derive(Debug, Clone, Args)]
+pub struct DeadLetterCmd {
+    /// The URL of the orchestrator API.
+    #[arg(env = "MADARA_ORCHESTRATOR_API_URL", long, default_value = "http://127.0.0.1:3000")]
+    pub api_url: Url,
+
+    #[command(subcommand)]
+    pub action: DeadLetterSubcommand,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum DeadLetterSubcommand {
+    /// List the jobs whose message is in the dead letter queue
+    List,
+    /// Send dead letters back to the queue they were moved from
+    Replay {
+        #[command(flatten)]
+        selection: DeadLetterSelectionArgs,
+    },
+    /// Drop dead letters, leaving their jobs failed
+    Discard {
+        #[command(flatten)]
+        selection: DeadLetterSelectionArgs,
+    },
+    /// Show the latest replayed and discarded dead letters
+    Audit {
+        /// Maximum number of entries to show.
+        #[arg(long, default_value = "50")]
+        limit: i64,
+    },
+}
+
+/// The dead letters an action applies to, and who takes it.
+#[derive(Debug, Clone, Args)]
+pub struct DeadLetterSelectionArgs {
+    /// The id of a job whose dead letter the action applies to, can be repeated.
+    #[arg(long, required_unless_present = "all")]
+    pub job_id: Vec<Uuid>,
+
+    /// Apply the action to every dead letter.
+    #[arg(long, conflicts_with = "job_id")]
+    pub all: bool,
+
+    /// Who takes the action, written to the audit log.
+    #[arg(env = "USER", long)]
+    pub actor: String,
+}
+
+impl DeadLetterSelectionArgs {
+    fn request(&self) -> DeadLetterActionRequest {
+        DeadLetterActionRequest {
+            job_ids: if self.all { None } else { Some(self.job_id.clone()) },
+            all: self.all,
+            actor: self.actor.clone(),
+        }
+    }
+}
+
+impl DeadLetterCmd {
+    /// Calls the dead letter endpoint of the action and prints its response.
+    pub async fn execute(&self) -> color_eyre::Result<()> {
+        let client = reqwest::Client::new();
+        let request = match &self.action {
+            DeadLetterSubcommand::List => client.get(self.endpoint("dead_letters")?),
+            DeadLetterSubcommand::Replay { selection } => {
+                client.post(self.endpoint("dead_letters/replay")?).json(&selection.request())
+            }
+            DeadLetterSubcommand::Discard { selection } => {
+                client.post(self.endpoint("dead_letters/discard")?).json(&selection.request())
+            }
+            DeadLetterSubcommand::Audit { limit } => {
+                client.get(self.endpoint("dead_letters/audit")?).query(&[("limit", limit)])
+            }
+        };
+
+        let response = request.send().await.wrap_err("Failed to call the orchestrator API")?;
+        let status = response.status();
+        let body: Value = response.json().await.wrap_err("Invalid response from the orchestrator API")?;
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        if !status.is_success() {
+            return Err(eyre!("The orchestrator API responded with {}", status));
+        }
+        Ok(())
+    }
+
+    fn endpoint(&self, path: &str) -> color_eyre::Result<Url> {
+        self.api_url.join(path).wrap_err(format!("Invalid orchestrator API URL: {}", self.api_url))
+    }
+}
@@ -12,4 +12,9 @@ pub struct InstrumentationCliArgs {
     /// The endpoint of the collector.
     #[arg(env = "MADARA_ORCHESTRATOR_OTEL_COLLECTOR_ENDPOINT", long)]
     pub otel_collector_endpoint: Option<Url>,
+
+    /// Expose the metrics in the Prometheus format on the `/metrics` route of the server.
+    /// Works with or without the collector.
+    #[arg(env = "MADARA_ORCHESTRATOR_PROMETHEUS_METRICS", long)]
+    pub prometheus_metrics: bool,
 }
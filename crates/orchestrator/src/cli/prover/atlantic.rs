@@ -36,4 +36,8 @@ pub struct AtlanticCliArgs {
     /// The verifier contract address for Atlantic.
     #[arg(env = "MADARA_ORCHESTRATOR_ATLANTIC_VERIFIER_CONTRACT_ADDRESS", long)]
     pub atlantic_verifier_contract_address: Option<String>,
+
+    /// The URL the proofs are downloaded from, `{}` being replaced by the query id.
+    #[arg(env = "MADARA_ORCHESTRATOR_ATLANTIC_PROOF_URL", long)]
+    pub atlantic_proof_url: Option<String>,
 }
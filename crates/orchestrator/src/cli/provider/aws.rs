@@ -11,13 +11,13 @@ pub struct AWSConfigCliArgs {
 
     /// The access key ID.
     #[arg(env = "AWS_ACCESS_KEY_ID", long)]
-    pub aws_access_key_id: String,
+    pub aws_access_key_id: Option<String>,
 
     /// The secret access key.
     #[arg(env = "AWS_SECRET_ACCESS_KEY", long)]
-    pub aws_secret_access_key: String,
+    pub aws_secret_access_key: Option<String>,
 
     /// The region.
     #[arg(env = "AWS_REGION", long)]
-    pub aws_region: String,
+    pub aws_region: Option<String>,
 }
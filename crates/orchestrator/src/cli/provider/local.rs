@@ -0,0 +1,10 @@
+use clap::Args;
+
+/// Parameters used to run the orchestrator locally.
+#[derive(Debug, Clone, Default, Args)]
+pub struct LocalCliArgs {
+    /// Use this flag to run everything in process. The database, queues, storage and alerts
+    /// default to their in memory implementations unless another backend is selected.
+    #[arg(long)]
+    pub local: bool,
+}
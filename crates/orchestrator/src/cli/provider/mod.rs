@@ -1,8 +1,11 @@
 pub mod aws;
+pub mod local;
 
 #[derive(Debug, Clone)]
 pub enum ProviderValidatedArgs {
     AWS(AWSConfigValidatedArgs),
+    /// Everything runs in process, see `LocalCliArgs`
+    Local,
 }
 
 #[derive(Debug, Clone)]
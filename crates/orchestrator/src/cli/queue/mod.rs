@@ -1,8 +1,12 @@
+use crate::queue::amqp::AMQPValidatedArgs;
 use crate::queue::sqs::AWSSQSValidatedArgs;
 
+pub mod amqp;
 pub mod aws_sqs;
 
 #[derive(Clone, Debug)]
 pub enum QueueValidatedArgs {
     AWSSQS(AWSSQSValidatedArgs),
+    AMQP(AMQPValidatedArgs),
+    InMemory,
 }
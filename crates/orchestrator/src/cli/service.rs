@@ -1,5 +1,7 @@
 use clap::Args;
 
+use crate::workers::circuit_breaker::HaltScope;
+
 #[derive(Debug, Clone, Args)]
 pub struct ServiceCliArgs {
     /// The maximum block to process.
@@ -14,4 +16,98 @@ pub struct ServiceCliArgs {
     /// The maximum number of SNOS jobs to process concurrently.
     #[arg(env = "MADARA_ORCHESTRATOR_MAX_CONCURRENT_SNOS_JOBS", long, default_value = Some("1"))]
     pub max_concurrent_snos_jobs: Option<usize>,
+
+    /// How long, in seconds, a job processing lease lasts without being renewed.
+    /// Leases are renewed while their job is processed, so this is how long the permits of a
+    /// replica which crashed stay taken.
+    #[arg(env = "MADARA_ORCHESTRATOR_JOB_PROCESSING_LEASE_TTL", long, default_value = Some("60"))]
+    pub job_processing_lease_ttl: Option<u64>,
+
+    /// How long, in seconds, the leadership of a singleton worker lasts without being renewed.
+    /// The leadership is renewed while the worker runs, so this is how long the worker stays
+    /// stuck if the replica running it crashed.
+    #[arg(env = "MADARA_ORCHESTRATOR_WORKER_LEADER_LEASE_TTL", long, default_value = Some("30"))]
+    pub worker_leader_lease_ttl: Option<u64>,
+
+    /// Register proofs on the settlement layer from the orchestrator.
+    /// Should be enabled when the prover does not register the facts itself. The state transition
+    /// jobs of a block then wait for its proof to be registered. Not supported with the Atlantic
+    /// prover yet.
+    #[arg(env = "MADARA_ORCHESTRATOR_REGISTER_PROOFS", long)]
+    pub register_proofs: bool,
+
+    /// Which workers a failed job halts.
+    #[arg(env = "MADARA_ORCHESTRATOR_HALT_SCOPE", long, value_enum, default_value_t = HaltScope::Global)]
+    pub halt_scope: HaltScope,
+
+    /// The number of failed jobs needed to halt the workers.
+    #[arg(env = "MADARA_ORCHESTRATOR_HALT_FAILURE_THRESHOLD", long, default_value_t = 1)]
+    pub halt_failure_threshold: usize,
+
+    /// Only count the jobs which failed in this many seconds.
+    /// All the failed jobs count if not set.
+    #[arg(env = "MADARA_ORCHESTRATOR_HALT_FAILURE_WINDOW", long)]
+    pub halt_failure_window: Option<u64>,
+
+    /// How long, in seconds, a job can stay waiting for processing, locked for processing or
+    /// pending verification without being updated before being reaped.
+    #[arg(env = "MADARA_ORCHESTRATOR_STUCK_JOB_DEADLINE", long, default_value = Some("3600"))]
+    pub stuck_job_deadline: Option<u64>,
+
+    /// Per job type deadlines in seconds overriding the default one, e.g.
+    /// `SnosRun=7200,ProofCreation=1800`.
+    #[arg(env = "MADARA_ORCHESTRATOR_STUCK_JOB_DEADLINES", long)]
+    pub stuck_job_deadlines: Option<String>,
+
+    /// The number of times a stuck job is put back in its queue before being marked as stale.
+    #[arg(env = "MADARA_ORCHESTRATOR_STUCK_JOB_MAX_REQUEUES", long, default_value = Some("3"))]
+    pub stuck_job_max_requeues: Option<usize>,
+
+    /// How long, in seconds, the in-flight jobs are given to finish on shutdown.
+    /// Should be shorter than the grace period of the orchestrator pods when running on Kubernetes.
+    #[arg(env = "MADARA_ORCHESTRATOR_SHUTDOWN_TIMEOUT", long, default_value = Some("30"))]
+    pub shutdown_timeout: Option<u64>,
+
+    /// How often, in seconds, the number of jobs per status and the depth of the queues are
+    /// polled for the metrics.
+    #[arg(env = "MADARA_ORCHESTRATOR_BACKLOG_METRICS_INTERVAL", long, default_value = Some("30"))]
+    pub backlog_metrics_interval: Option<u64>,
+
+    /// The number of messages of a queue handled at once by the orchestrator. The state updates
+    /// are processed one at a time unless overridden, to keep the settlement nonces in order.
+    #[arg(env = "MADARA_ORCHESTRATOR_QUEUE_CONCURRENCY", long, default_value = Some("4"))]
+    pub queue_concurrency: Option<usize>,
+
+    /// Per queue concurrencies overriding the default one, e.g.
+    /// `update_state_job_processing=2,snos_job_verification=8`.
+    #[arg(env = "MADARA_ORCHESTRATOR_QUEUE_CONCURRENCY_OVERRIDES", long)]
+    pub queue_concurrency_overrides: Option<String>,
+
+    /// The number of messages received from a queue at once, at most 10.
+    #[arg(env = "MADARA_ORCHESTRATOR_QUEUE_RECEIVE_BATCH_SIZE", long, default_value = Some("10"))]
+    pub queue_receive_batch_size: Option<usize>,
+
+    /// How long, in seconds, a receive waits for messages to arrive in an empty queue, at most 20.
+    #[arg(env = "MADARA_ORCHESTRATOR_QUEUE_RECEIVE_WAIT", long, default_value = Some("10"))]
+    pub queue_receive_wait: Option<u64>,
+
+    /// The number of times a job message is put back in its queue after a transient error, before
+    /// the job is alerted on like any other failure.
+    #[arg(env = "MADARA_ORCHESTRATOR_QUEUE_TRANSIENT_RETRIES", long, default_value = Some("3"))]
+    pub queue_transient_retries: Option<usize>,
+
+    /// Per queue transient retries overriding the default ones, e.g.
+    /// `update_state_job_processing=0,snos_job_processing=5`.
+    #[arg(env = "MADARA_ORCHESTRATOR_QUEUE_TRANSIENT_RETRIES_OVERRIDES", long)]
+    pub queue_transient_retries_overrides: Option<String>,
+
+    /// How long, in seconds, a job message waits before its first retry after a transient error.
+    /// The following retries wait exponentially longer, up to 15 minutes.
+    #[arg(env = "MADARA_ORCHESTRATOR_QUEUE_TRANSIENT_RETRY_DELAY", long, default_value = Some("10"))]
+    pub queue_transient_retry_delay: Option<u64>,
+
+    /// Queues this replica doesn't consume, e.g. `proving_job_processing,proving_job_verification`,
+    /// to split the queues between replicas with different roles.
+    #[arg(env = "MADARA_ORCHESTRATOR_DISABLED_CONSUMERS", long)]
+    pub disabled_consumers: Option<String>,
 }
@@ -23,4 +23,12 @@ pub struct EthereumSettlementCliArgs {
     /// The address of the Starknet operator.
     #[arg(env = "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS", long)]
     pub starknet_operator_address: Option<String>,
+
+    /// The address of the GPS verifier contract used to register proofs.
+    #[arg(env = "MADARA_ORCHESTRATOR_ETHEREUM_GPS_VERIFIER_CONTRACT_ADDRESS", long)]
+    pub ethereum_gps_verifier_contract_address: Option<String>,
+
+    /// The address of the memory page fact registry used by the GPS verifier.
+    #[arg(env = "MADARA_ORCHESTRATOR_ETHEREUM_MEMORY_PAGE_FACT_REGISTRY_ADDRESS", long)]
+    pub ethereum_memory_page_fact_registry_address: Option<String>,
 }
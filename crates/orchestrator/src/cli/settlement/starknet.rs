@@ -27,4 +27,8 @@ pub struct StarknetSettlementCliArgs {
     /// The number of seconds to wait for finality.
     #[arg(env = "MADARA_ORCHESTRATOR_STARKNET_FINALITY_RETRY_WAIT_IN_SECS", long)]
     pub starknet_finality_retry_wait_in_secs: Option<u64>,
+
+    /// The address of the fact registry contract used to register proofs.
+    #[arg(env = "MADARA_ORCHESTRATOR_STARKNET_FACT_REGISTRY_ADDRESS", long)]
+    pub starknet_fact_registry_address: Option<String>,
 }
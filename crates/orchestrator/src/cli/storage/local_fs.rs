@@ -0,0 +1,14 @@
+use clap::Args;
+
+/// Parameters used to config the local filesystem storage.
+#[derive(Debug, Clone, Args)]
+#[group()] // Note: we are not using root_dir in requires_all because it has a default value.
+pub struct LocalFsCliArgs {
+    /// Use the local filesystem storage
+    #[arg(long)]
+    pub local_fs: bool,
+
+    /// The directory under which the data is stored.
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_FS_ROOT_DIR", long, default_value = Some("./orchestrator-data"))]
+    pub local_fs_root_dir: Option<String>,
+}
@@ -1,8 +1,12 @@
 use crate::data_storage::aws_s3::AWSS3ValidatedArgs;
+use crate::data_storage::local_fs::LocalFsValidatedArgs;
 
 pub mod aws_s3;
+pub mod local_fs;
 
 #[derive(Clone, Debug)]
 pub enum StorageValidatedArgs {
     AWSS3(AWSS3ValidatedArgs),
+    LocalFs(LocalFsValidatedArgs),
+    InMemory,
 }
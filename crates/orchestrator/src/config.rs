@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 #[cfg(feature = "testing")]
 use alloy::providers::RootProvider;
@@ -20,6 +21,9 @@ use starknet::providers::{JsonRpcClient, Url};
 use starknet_settlement_client::StarknetSettlementClient;
 
 use crate::alerts::aws_sns::AWSSNS;
+use crate::alerts::fan_out::FanOutAlerts;
+use crate::alerts::in_memory::InMemoryAlerts;
+use crate::alerts::webhook::WebhookAlerts;
 use crate::alerts::Alerts;
 use crate::cli::alert::AlertValidatedArgs;
 use crate::cli::da::DaValidatedArgs;
@@ -32,13 +36,24 @@ use crate::cli::snos::SNOSParams;
 use crate::cli::storage::StorageValidatedArgs;
 use crate::cli::RunCmd;
 use crate::data_storage::aws_s3::AWSS3;
+use crate::data_storage::in_memory::InMemoryStorage;
+use crate::data_storage::local_fs::LocalFsStorage;
 use crate::data_storage::DataStorage;
+use crate::database::in_memory::InMemoryDb;
 use crate::database::mongodb::MongoDb;
+use crate::database::postgres::PostgresDb;
 use crate::database::Database;
-use crate::helpers::{JobProcessingState, ProcessingLocks};
+use crate::helpers::{JobProcessingState, ProcessingLocks, SNOS_JOB_PROCESSING_LOCK};
+use crate::queue::amqp::AmqpQueue;
+use crate::queue::consumer::QueueConsumerParams;
+use crate::queue::in_memory::InMemoryQueue;
 use crate::queue::sqs::SqsQueue;
 use crate::queue::QueueProvider;
 use crate::routes::ServerParams;
+use crate::shutdown::ShutdownCoordinator;
+use crate::workers::circuit_breaker::CircuitBreakerParams;
+use crate::workers::leader_election::HeldLeaderships;
+use crate::workers::stuck_job_reaper::StuckJobReaperParams;
 
 /// The app config. It can be accessed from anywhere inside the service
 /// by calling `config` function.
@@ -63,6 +78,10 @@ pub struct Config {
     alerts: Box<dyn Alerts>,
     /// Locks
     processing_locks: ProcessingLocks,
+    /// Graceful shutdown coordinator
+    shutdown: ShutdownCoordinator,
+    /// Leaderships of the singleton workers held by this replica
+    leaderships: HeldLeaderships,
 }
 
 #[derive(Debug, Clone)]
@@ -70,6 +89,14 @@ pub struct ServiceParams {
     pub max_block_to_process: Option<u64>,
     pub min_block_to_process: Option<u64>,
     pub max_concurrent_snos_jobs: Option<usize>,
+    pub job_processing_lease_ttl: Duration,
+    pub worker_leader_lease_ttl: Duration,
+    pub register_proofs: bool,
+    pub circuit_breaker: CircuitBreakerParams,
+    pub stuck_job_reaper: StuckJobReaperParams,
+    pub shutdown_timeout: Duration,
+    pub backlog_metrics_interval: Duration,
+    pub queue_consumers: QueueConsumerParams,
 }
 
 pub struct OrchestratorParams {
@@ -91,12 +118,15 @@ pub struct OrchestratorParams {
 #[derive(Clone)]
 pub enum ProviderConfig {
     AWS(Box<SdkConfig>),
+    /// No cloud provider, the services run in process
+    Local,
 }
 
 impl ProviderConfig {
     pub fn get_aws_client_or_panic(&self) -> &SdkConfig {
         match self {
             ProviderConfig::AWS(config) => config.as_ref(),
+            ProviderConfig::Local => panic!("AWS config is not available with the local provider"),
         }
     }
 }
@@ -166,8 +196,11 @@ pub async fn init_config(run_cmd: &RunCmd) -> color_eyre::Result<Arc<Config>> {
     let queue_params = run_cmd.validate_queue_params().map_err(|e| eyre!("Failed to validate queue params: {e}"))?;
     let queue = build_queue_client(&queue_params, provider_config.clone()).await;
 
-    let snos_processing_lock =
-        JobProcessingState::new(orchestrator_params.service_config.max_concurrent_snos_jobs.unwrap_or(1));
+    let snos_processing_lock = JobProcessingState::new(
+        SNOS_JOB_PROCESSING_LOCK,
+        orchestrator_params.service_config.max_concurrent_snos_jobs.unwrap_or(1),
+        orchestrator_params.service_config.job_processing_lease_ttl,
+    );
     let processing_locks = ProcessingLocks { snos_job_processing_lock: Arc::new(snos_processing_lock) };
 
     Ok(Arc::new(Config::new(
@@ -210,6 +243,8 @@ impl Config {
             storage,
             alerts,
             processing_locks,
+            shutdown: ShutdownCoordinator::new(),
+            leaderships: HeldLeaderships::new(),
         }
     }
 
@@ -287,6 +322,16 @@ impl Config {
     pub fn processing_locks(&self) -> &ProcessingLocks {
         &self.processing_locks
     }
+
+    /// Returns the graceful shutdown coordinator
+    pub fn shutdown(&self) -> &ShutdownCoordinator {
+        &self.shutdown
+    }
+
+    /// Returns the leaderships held by this replica
+    pub fn leaderships(&self) -> &HeldLeaderships {
+        &self.leaderships
+    }
 }
 
 /// Builds the provider config
@@ -295,6 +340,7 @@ pub async fn build_provider_config(provider_params: &ProviderValidatedArgs) -> A
         ProviderValidatedArgs::AWS(aws_params) => {
             Arc::new(ProviderConfig::AWS(Box::new(get_aws_config(aws_params).await)))
         }
+        ProviderValidatedArgs::Local => Arc::new(ProviderConfig::Local),
     }
 }
 
@@ -329,12 +375,21 @@ pub async fn build_settlement_client(
             }
             #[cfg(feature = "testing")]
             {
-                Ok(Box::new(EthereumSettlementClient::with_test_params(
+                let client = EthereumSettlementClient::with_test_params(
                     RootProvider::new_http(ethereum_settlement_params.ethereum_rpc_url.clone()),
                     ethereum_settlement_params.l1_core_contract_address,
                     ethereum_settlement_params.ethereum_rpc_url.clone(),
                     Some(ethereum_settlement_params.starknet_operator_address),
-                )))
+                );
+                match (
+                    ethereum_settlement_params.gps_verifier_contract_address,
+                    ethereum_settlement_params.memory_page_fact_registry_address,
+                ) {
+                    (Some(verifier_address), Some(memory_page_fact_registry_address)) => {
+                        Ok(Box::new(client.with_gps_verifier(verifier_address, memory_page_fact_registry_address)))
+                    }
+                    _ => Ok(Box::new(client)),
+                }
             }
         }
         SettlementValidatedArgs::Starknet(starknet_settlement_params) => {
@@ -352,6 +407,8 @@ pub async fn build_storage_client(
             let aws_config = provider_config.get_aws_client_or_panic();
             Box::new(AWSS3::new_with_args(aws_s3_params, aws_config).await)
         }
+        StorageValidatedArgs::LocalFs(local_fs_params) => Box::new(LocalFsStorage::new_with_args(local_fs_params)),
+        StorageValidatedArgs::InMemory => Box::new(InMemoryStorage::new()),
     }
 }
 
@@ -364,6 +421,15 @@ pub async fn build_alert_client(
             let aws_config = provider_config.get_aws_client_or_panic();
             Box::new(AWSSNS::new_with_args(aws_sns_params, aws_config).await)
         }
+        AlertValidatedArgs::Webhook(webhook_params) => Box::new(WebhookAlerts::new_with_args(webhook_params)),
+        AlertValidatedArgs::InMemory => Box::new(InMemoryAlerts::new()),
+        AlertValidatedArgs::FanOut(alert_params) => {
+            let mut sinks: Vec<Box<dyn Alerts>> = Vec::with_capacity(alert_params.len());
+            for sink_params in alert_params {
+                sinks.push(Box::pin(build_alert_client(sink_params, provider_config.clone())).await);
+            }
+            Box::new(FanOutAlerts::new(sinks))
+        }
     }
 }
 
@@ -376,11 +442,15 @@ pub async fn build_queue_client(
             let aws_config = provider_config.get_aws_client_or_panic();
             Box::new(SqsQueue::new_with_args(aws_sqs_params.clone(), aws_config))
         }
+        QueueValidatedArgs::AMQP(amqp_params) => Box::new(AmqpQueue::new_with_args(amqp_params.clone())),
+        QueueValidatedArgs::InMemory => Box::new(InMemoryQueue::new()),
     }
 }
 
 pub async fn build_database_client(database_params: &DatabaseValidatedArgs) -> Box<dyn Database + Send + Sync> {
     match database_params {
         DatabaseValidatedArgs::MongoDB(mongodb_params) => Box::new(MongoDb::new_with_args(mongodb_params).await),
+        DatabaseValidatedArgs::Postgres(postgres_params) => Box::new(PostgresDb::new_with_args(postgres_params).await),
+        DatabaseValidatedArgs::InMemory => Box::new(InMemoryDb::new()),
     }
 }
@@ -7,8 +7,9 @@ use aws_sdk_scheduler::types::{FlexibleTimeWindow, FlexibleTimeWindowMode, Targe
 use aws_sdk_sqs::types::QueueAttributeName;
 use aws_sdk_sqs::Client as SqsClient;
 use color_eyre::eyre::Ok;
+use tokio::time::sleep;
 
-use super::{get_worker_trigger_message, TriggerArns};
+use super::{get_worker_trigger_message, TriggerArns, WORKER_TRIGGERS};
 use crate::cron::Cron;
 use crate::queue::job_queue::WorkerTriggerType;
 
@@ -66,8 +67,19 @@ impl AWSEventBridge {
 }
 
 #[async_trait]
-#[allow(unreachable_patterns)]
 impl Cron for AWSEventBridge {
+    async fn setup(&self) -> color_eyre::Result<()> {
+        let trigger_arns = self.create_cron().await?;
+        sleep(Duration::from_secs(15)).await;
+        for trigger in WORKER_TRIGGERS.iter() {
+            self.add_cron_target_queue(trigger, &trigger_arns).await?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(unreachable_patterns)]
+impl AWSEventBridge {
     async fn create_cron(&self) -> color_eyre::Result<TriggerArns> {
         // Get Queue Info
         let queue_url = self.queue_client.get_queue_url().queue_name(&self.target_queue_name).send().await?;
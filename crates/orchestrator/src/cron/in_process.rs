@@ -0,0 +1,187 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::time::sleep;
+
+use crate::config::Config;
+use crate::cron::Cron;
+use crate::queue::job_queue::{get_worker_handler_from_worker_trigger_type, WorkerTriggerType};
+
+/// When a worker should be triggered.
+#[derive(Debug, Clone)]
+pub enum WorkerSchedule {
+    /// Every given duration
+    Interval(Duration),
+    /// Following a cron expression, with seconds: `sec min hour day_of_month month day_of_week
+    /// [year]`
+    CronExpression(Box<::cron::Schedule>),
+}
+
+impl FromStr for WorkerSchedule {
+    type Err = String;
+
+    /// A number is read as an interval in seconds, anything else as a cron expression.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(secs) = s.parse::<u64>() {
+            if secs == 0 {
+                return Err("Worker interval must be at least 1 second".to_string());
+            }
+            return Ok(WorkerSchedule::Interval(Duration::from_secs(secs)));
+        }
+        ::cron::Schedule::from_str(s)
+            .map(|schedule| WorkerSchedule::CronExpression(Box::new(schedule)))
+            .map_err(|e| format!("Invalid cron expression {:?}: {}", s, e))
+    }
+}
+
+impl WorkerSchedule {
+    /// Time to wait until the next tick, `None` if the schedule doesn't have any upcoming tick.
+    fn duration_until_next_tick(&self) -> Option<Duration> {
+        match self {
+            WorkerSchedule::Interval(interval) => Some(*interval),
+            WorkerSchedule::CronExpression(schedule) => {
+                let now = Utc::now();
+                let next_tick = schedule.after(&now).next()?;
+                Some((next_tick - now).to_std().unwrap_or_default())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkerCronSchedule {
+    pub worker: WorkerTriggerType,
+    pub schedule: WorkerSchedule,
+}
+
+#[derive(Debug, Clone)]
+pub struct InProcessCronValidatedArgs {
+    /// Workers without a schedule are never triggered
+    pub schedules: Vec<WorkerCronSchedule>,
+}
+
+/// InProcessCron triggers the workers from within the orchestrator using tokio timers,
+/// each worker following its own schedule.
+pub struct InProcessCron {
+    schedules: Vec<WorkerCronSchedule>,
+}
+
+impl InProcessCron {
+    pub fn new_with_args(params: &InProcessCronValidatedArgs) -> Self {
+        Self { schedules: params.schedules.clone() }
+    }
+}
+
+#[async_trait]
+impl Cron for InProcessCron {
+    /// Nothing to create, the schedules live in the orchestrator process.
+    async fn setup(&self) -> color_eyre::Result<()> {
+        Ok(())
+    }
+
+    async fn start(&self, config: Arc<Config>) -> color_eyre::Result<()> {
+        for WorkerCronSchedule { worker, schedule } in self.schedules.iter().cloned() {
+            tracing::info!(category = "cron", worker = %worker, schedule = ?schedule, "Scheduling worker");
+            tokio::spawn(run_schedule(worker, schedule, config.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// Resets the running flag of a worker once its run is over, even if it panicked.
+struct RunningGuard(Arc<AtomicBool>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Triggers the worker on every tick of its schedule.
+/// A tick is skipped if the previous run of the worker is still going.
+async fn run_schedule(worker_trigger_type: WorkerTriggerType, schedule: WorkerSchedule, config: Arc<Config>) {
+    let is_running = Arc::new(AtomicBool::new(false));
+
+    while let Some(delay) = schedule.duration_until_next_tick() {
+        tokio::select! {
+            _ = sleep(delay) => {}
+            _ = config.shutdown().draining() => {
+                tracing::info!(
+                    category = "cron",
+                    worker = %worker_trigger_type,
+                    "Shutting down, the worker won't be triggered anymore"
+                );
+                return;
+            }
+        }
+
+        if is_running.swap(true, Ordering::AcqRel) {
+            tracing::debug!(category = "cron", worker = %worker_trigger_type, "Previous run of the worker is still going, skipping tick");
+            continue;
+        }
+
+        let running_guard = RunningGuard(is_running.clone());
+        let worker = get_worker_handler_from_worker_trigger_type(worker_trigger_type.clone());
+        let worker_trigger_type = worker_trigger_type.clone();
+        let config = config.clone();
+        // tracked so that the shutdown waits for the run, which is cut off at the shutdown deadline
+        let shutdown = config.shutdown().clone();
+        shutdown.spawn(async move {
+            let _running_guard = running_guard;
+            let result = tokio::select! {
+                result = worker.run_worker_if_enabled(config.clone()) => result,
+                _ = config.shutdown().deadline_reached() => {
+                    tracing::warn!(
+                        category = "cron",
+                        worker = %worker_trigger_type,
+                        "Shutdown deadline reached, worker run cut off"
+                    );
+                    return;
+                }
+            };
+            if let Err(e) = result {
+                tracing::error!(category = "cron", worker = %worker_trigger_type, error = ?e, "Failed to run worker");
+                if let Err(e) = config.alerts().send_alert_message(e.to_string()).await {
+                    tracing::error!(category = "cron", error = ?e, "Failed to send alert");
+                }
+            }
+        });
+    }
+
+    tracing::warn!(category = "cron", worker = %worker_trigger_type, "No upcoming tick, the worker won't be triggered anymore");
+}
+
+#[cfg(test)]
+mod in_process_cron_test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("60", true)]
+    #[case(" 10 ", true)]
+    #[case("0 */5 * * * *", true)]
+    #[case("0", false)]
+    #[case("every minute", false)]
+    fn test_parse_worker_schedule(#[case] schedule: &str, #[case] is_valid: bool) {
+        assert_eq!(WorkerSchedule::from_str(schedule).is_ok(), is_valid);
+    }
+
+    #[rstest]
+    fn test_duration_until_next_tick() {
+        let interval = WorkerSchedule::from_str("30").unwrap();
+        assert_eq!(interval.duration_until_next_tick(), Some(Duration::from_secs(30)));
+
+        let every_second = WorkerSchedule::from_str("* * * * * *").unwrap();
+        assert!(every_second.duration_until_next_tick().unwrap() <= Duration::from_secs(1));
+
+        // the year is in the past, there's no upcoming tick
+        let never = WorkerSchedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(never.duration_until_next_tick(), None);
+    }
+}
@@ -1,20 +1,22 @@
-use std::time::Duration;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use lazy_static::lazy_static;
-use tokio::time::sleep;
 
+use crate::config::Config;
 use crate::queue::job_queue::WorkerTriggerType;
+use crate::queue::registry::JOB_QUEUES;
 
 pub mod event_bridge;
+pub mod in_process;
 
 lazy_static! {
-    pub static ref WORKER_TRIGGERS: Vec<WorkerTriggerType> = vec![
-        WorkerTriggerType::Snos,
-        WorkerTriggerType::Proving,
-        WorkerTriggerType::DataSubmission,
-        WorkerTriggerType::UpdateState
-    ];
+    /// The workers of the registered job types, and the stuck job reaper.
+    pub static ref WORKER_TRIGGERS: Vec<WorkerTriggerType> = JOB_QUEUES
+        .iter()
+        .map(|registration| registration.worker_trigger.clone())
+        .chain([WorkerTriggerType::StuckJobReaper])
+        .collect();
 }
 
 #[derive(Debug, Clone)]
@@ -22,20 +24,17 @@ pub struct TriggerArns {
     queue_arn: String,
     role_arn: String,
 }
+
+/// Cron Trait
+///
+/// The Cron trait is used to trigger the workers periodically. Crons hosted by a cloud provider
+/// push the `WorkerTriggerType` messages onto the worker trigger queue and are created by the
+/// `setup` command, while in process crons run the workers from within the orchestrator once
+/// started by the `run` command.
 #[async_trait]
-pub trait Cron {
-    async fn create_cron(&self) -> color_eyre::Result<TriggerArns>;
-    async fn add_cron_target_queue(
-        &self,
-        trigger_type: &WorkerTriggerType,
-        trigger_arns: &TriggerArns,
-    ) -> color_eyre::Result<()>;
-    async fn setup(&self) -> color_eyre::Result<()> {
-        let trigger_arns = self.create_cron().await?;
-        sleep(Duration::from_secs(15)).await;
-        for trigger in WORKER_TRIGGERS.iter() {
-            self.add_cron_target_queue(trigger, &trigger_arns).await?;
-        }
+pub trait Cron: Send + Sync {
+    async fn setup(&self) -> color_eyre::Result<()>;
+    async fn start(&self, _config: Arc<Config>) -> color_eyre::Result<()> {
         Ok(())
     }
 }
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+
+use crate::data_storage::DataStorage;
+
+/// InMemoryStorage keeps the data in the process memory, keyed by the storage key.
+/// It's meant for local runs and tests, everything is lost when the orchestrator stops.
+#[derive(Default)]
+pub struct InMemoryStorage {
+    objects: RwLock<HashMap<String, Bytes>>,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DataStorage for InMemoryStorage {
+    /// Function to get the data stored at Key.
+    async fn get_data(&self, key: &str) -> Result<Bytes> {
+        let objects = self.objects.read().map_err(|e| eyre!("In memory storage lock is poisoned: {}", e))?;
+        let data = objects.get(key).cloned().ok_or_else(|| eyre!("No data found for key: {}", key))?;
+
+        tracing::debug!(
+            log_type = "DataStorage",
+            category = "data_storage_call",
+            data_bytes = data.len(),
+            "Successfully retrieved data from memory, key={}",
+            key
+        );
+        Ok(data)
+    }
+
+    /// Function to put the data at Key.
+    async fn put_data(&self, data: Bytes, key: &str) -> Result<()> {
+        let data_bytes = data.len();
+        self.objects
+            .write()
+            .map_err(|e| eyre!("In memory storage lock is poisoned: {}", e))?
+            .insert(key.to_string(), data);
+
+        tracing::debug!(
+            log_type = "DataStorage",
+            category = "data_storage_call",
+            data_bytes,
+            "Successfully put data in memory, key={}",
+            key
+        );
+        Ok(())
+    }
+
+    /// Nothing to create, the data lives in the process memory.
+    async fn create_bucket(&self, _bucket_name: &str) -> Result<()> {
+        Ok(())
+    }
+}
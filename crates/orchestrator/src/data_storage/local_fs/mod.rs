@@ -0,0 +1,90 @@
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use color_eyre::eyre::{eyre, Context};
+use color_eyre::Result;
+use uuid::Uuid;
+
+use crate::data_storage::DataStorage;
+
+#[derive(Debug, Clone)]
+pub struct LocalFsValidatedArgs {
+    pub root_dir: PathBuf,
+}
+
+/// LocalFsStorage stores the data as files under a root directory on the local filesystem.
+/// Keys are used as paths relative to the root directory, e.g. `<block_number>/snos_output.json`.
+pub struct LocalFsStorage {
+    root_dir: PathBuf,
+}
+
+impl LocalFsStorage {
+    /// To init the struct with main settings
+    pub fn new_with_args(local_fs_config: &LocalFsValidatedArgs) -> Self {
+        Self { root_dir: local_fs_config.root_dir.clone() }
+    }
+
+    /// Resolves the key to a path under the root directory.
+    /// Keys which would escape the root directory are rejected.
+    fn path_for_key(&self, key: &str) -> Result<PathBuf> {
+        let key_path = Path::new(key);
+        if key.is_empty() || !key_path.components().all(|component| matches!(component, Component::Normal(_))) {
+            return Err(eyre!("Invalid storage key: {}", key));
+        }
+        Ok(self.root_dir.join(key_path))
+    }
+}
+
+/// Implementation of `DataStorage` for `LocalFsStorage`
+/// contains the function for getting the data and putting the data
+/// by taking the key as an argument.
+#[async_trait]
+impl DataStorage for LocalFsStorage {
+    /// Function to get the data from the file stored at Key.
+    async fn get_data(&self, key: &str) -> Result<Bytes> {
+        let path = self.path_for_key(key)?;
+        let data = tokio::fs::read(&path).await.context(format!("Failed to read file: {}", path.display()))?;
+
+        tracing::debug!(
+            log_type = "DataStorage",
+            category = "data_storage_call",
+            data_bytes = data.len(),
+            "Successfully retrieved data from {}, key={}",
+            self.root_dir.display(),
+            key
+        );
+        Ok(Bytes::from(data))
+    }
+
+    /// Function to put the data in the file at Key.
+    /// The data is written to a temporary file which is then renamed, so that readers never see a
+    /// partially written file.
+    async fn put_data(&self, data: Bytes, key: &str) -> Result<()> {
+        let path = self.path_for_key(key)?;
+        let parent = path.parent().ok_or_else(|| eyre!("Invalid storage key: {}", key))?;
+        tokio::fs::create_dir_all(parent).await.context(format!("Failed to create directory: {}", parent.display()))?;
+
+        let file_name = path.file_name().ok_or_else(|| eyre!("Invalid storage key: {}", key))?;
+        let tmp_path = parent.join(format!(".{}.{}.tmp", file_name.to_string_lossy(), Uuid::new_v4()));
+        tokio::fs::write(&tmp_path, &data).await.context(format!("Failed to write file: {}", tmp_path.display()))?;
+        if let Err(e) = tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(e).context(format!("Failed to move file to: {}", path.display()));
+        }
+
+        tracing::debug!(
+            log_type = "DataStorage",
+            category = "data_storage_call",
+            "Successfully put data into {}. key={}",
+            self.root_dir.display(),
+            key
+        );
+        Ok(())
+    }
+
+    /// Creates the directory used as bucket.
+    async fn create_bucket(&self, bucket_name: &str) -> Result<()> {
+        tokio::fs::create_dir_all(bucket_name).await.context(format!("Failed to create directory: {}", bucket_name))
+    }
+}
@@ -1,4 +1,6 @@
 pub mod aws_s3;
+pub mod in_memory;
+pub mod local_fs;
 pub mod types;
 
 use async_trait::async_trait;
@@ -11,7 +13,7 @@ use crate::cli::storage::StorageValidatedArgs;
 /// Data Storage Trait
 ///
 /// DataStorage trait contains the functions used to store and get the data from
-/// the cloud provider storage.
+/// the cloud provider storage or the local filesystem.
 /// The proposed storage format is :
 ///     ----<block_number>
 ///         ----<cairo_pie.json> (stored during the SNOS job)
@@ -26,6 +28,10 @@ pub trait DataStorage: Send + Sync {
     async fn setup(&self, storage_params: &StorageValidatedArgs) -> Result<()> {
         match storage_params {
             StorageValidatedArgs::AWSS3(aws_s3_params) => self.create_bucket(&aws_s3_params.bucket_name).await,
+            StorageValidatedArgs::LocalFs(local_fs_params) => {
+                self.create_bucket(&local_fs_params.root_dir.to_string_lossy()).await
+            }
+            StorageValidatedArgs::InMemory => Ok(()),
         }
     }
 }
@@ -0,0 +1,393 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{SubsecRound, Utc};
+use color_eyre::eyre::{eyre, Context};
+use color_eyre::Result;
+use uuid::Uuid;
+
+use crate::database::{Database, Fence, JobCursor, JobFilter};
+use crate::jobs::types::{JobItem, JobItemUpdates, JobStatus, JobType};
+use crate::jobs::JobError;
+use crate::queue::dead_letter::DeadLetterAuditEntry;
+
+/// InMemoryDb keeps the jobs in the process memory. It's meant for local runs and tests,
+/// everything is lost when the orchestrator stops.
+///
+/// Jobs are kept in insertion order so the queries return them in creation order,
+/// same as the other implementations.
+#[derive(Default)]
+pub struct InMemoryDb {
+    jobs: RwLock<Vec<JobItem>>,
+    /// Holders of each lease semaphore, with the expiry of their lease
+    leases: Mutex<HashMap<String, Vec<(Uuid, Instant)>>>,
+    /// Leader of each election
+    leaders: Mutex<HashMap<String, Leader>>,
+    /// Dead letter audit log, oldest first
+    dead_letter_audit_log: Mutex<Vec<DeadLetterAuditEntry>>,
+}
+
+struct Leader {
+    holder: Uuid,
+    fencing_token: u64,
+    expires_at: Instant,
+}
+
+impl InMemoryDb {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert_job(&self, job: JobItem) -> Result<JobItem, JobError> {
+        let mut jobs = self.write_jobs().map_err(|e| JobError::Other(e.into()))?;
+        if jobs.iter().any(|existing| existing.job_type == job.job_type && existing.internal_id == job.internal_id) {
+            return Err(JobError::JobAlreadyExists { internal_id: job.internal_id, job_type: job.job_type });
+        }
+        jobs.push(job.clone());
+        Ok(job)
+    }
+
+    fn read_jobs(&self) -> Result<RwLockReadGuard<'_, Vec<JobItem>>> {
+        self.jobs.read().map_err(|e| eyre!("In memory database lock is poisoned: {}", e))
+    }
+
+    fn write_jobs(&self) -> Result<RwLockWriteGuard<'_, Vec<JobItem>>> {
+        self.jobs.write().map_err(|e| eyre!("In memory database lock is poisoned: {}", e))
+    }
+
+    fn lock_dead_letter_audit_log(&self) -> Result<MutexGuard<'_, Vec<DeadLetterAuditEntry>>> {
+        self.dead_letter_audit_log.lock().map_err(|e| eyre!("In memory database lock is poisoned: {}", e))
+    }
+
+    fn lock_leaders(&self) -> Result<MutexGuard<'_, HashMap<String, Leader>>> {
+        self.leaders.lock().map_err(|e| eyre!("In memory database lock is poisoned: {}", e))
+    }
+
+    /// Locks the leases, dropping the expired ones.
+    fn lock_leases(&self) -> Result<MutexGuard<'_, HashMap<String, Vec<(Uuid, Instant)>>>> {
+        let mut leases = self.leases.lock().map_err(|e| eyre!("In memory database lock is poisoned: {}", e))?;
+        let now = Instant::now();
+        leases.values_mut().for_each(|holders| holders.retain(|(_, expires_at)| *expires_at > now));
+        Ok(leases)
+    }
+}
+
+/// Internal ids are block numbers, so they're compared numerically when possible.
+fn compare_internal_ids(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+#[async_trait]
+impl Database for InMemoryDb {
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn create_job(&self, job: JobItem) -> Result<JobItem, JobError> {
+        self.insert_job(job)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn create_job_fenced(&self, job: JobItem, fence: &Fence) -> Result<JobItem, JobError> {
+        // the leaders stay locked until the job is inserted, so the leadership can't be taken over
+        // in between
+        let leaders = self.lock_leaders().map_err(|e| JobError::Other(e.into()))?;
+        if leaders.get(&fence.election).map(|leader| leader.fencing_token) != Some(fence.fencing_token) {
+            return Err(JobError::StaleFence { election: fence.election.clone(), fencing_token: fence.fencing_token });
+        }
+        let result = self.insert_job(job);
+        drop(leaders);
+        result
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_job_by_id(&self, id: Uuid) -> Result<Option<JobItem>> {
+        Ok(self.read_jobs()?.iter().find(|job| job.id == id).cloned())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_job_by_internal_id_and_type(&self, internal_id: &str, job_type: &JobType) -> Result<Option<JobItem>> {
+        Ok(self.read_jobs()?.iter().find(|job| job.internal_id == internal_id && &job.job_type == job_type).cloned())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn update_job(&self, current_job: &JobItem, updates: JobItemUpdates) -> Result<JobItem> {
+        let JobItemUpdates { internal_id, job_type, status, external_id, metadata } = updates;
+
+        // throw an error if there's no field to be updated
+        if internal_id.is_none()
+            && job_type.is_none()
+            && status.is_none()
+            && external_id.is_none()
+            && metadata.is_none()
+        {
+            return Err(eyre!("No field to be updated, likely a false call"));
+        }
+
+        let mut jobs = self.write_jobs()?;
+        let Some(job) = jobs.iter_mut().find(|job| job.id == current_job.id && job.version == current_job.version)
+        else {
+            tracing::warn!(job_id = %current_job.id, category = "db_call", "Failed to update job. Job version is likely outdated");
+            return Err(eyre!("Failed to update job. Job version is likely outdated"));
+        };
+
+        if let Some(internal_id) = internal_id {
+            job.internal_id = internal_id;
+        }
+        if let Some(job_type) = job_type {
+            job.job_type = job_type;
+        }
+        if let Some(status) = status {
+            job.status = status;
+        }
+        if let Some(external_id) = external_id {
+            job.external_id = external_id;
+        }
+        if let Some(metadata) = metadata {
+            job.metadata = metadata;
+        }
+        job.version = current_job.version + 1;
+        job.updated_at = Utc::now().round_subsecs(0);
+
+        tracing::debug!(job_id = %current_job.id, category = "db_call", "Job updated successfully");
+        Ok(job.clone())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn heartbeat_job(&self, id: Uuid, status: JobStatus) -> Result<bool> {
+        let mut jobs = self.write_jobs()?;
+        match jobs.iter_mut().find(|job| job.id == id && job.status == status) {
+            Some(job) => {
+                job.updated_at = Utc::now().round_subsecs(0);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_latest_job_by_type(&self, job_type: JobType) -> Result<Option<JobItem>> {
+        Ok(self
+            .read_jobs()?
+            .iter()
+            .filter(|job| job.job_type == job_type)
+            .max_by(|a, b| compare_internal_ids(&a.internal_id, &b.internal_id))
+            .cloned())
+    }
+
+    /// function to get jobs that don't have a successor job.
+    ///
+    /// `job_a_type` : Type of job that we need to get that doesn't have any successor.
+    ///
+    /// `job_a_status` : Status of job A.
+    ///
+    /// `job_b_type` : Type of job that we need to have as a successor for Job A.
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_without_successor(
+        &self,
+        job_a_type: JobType,
+        job_a_status: JobStatus,
+        job_b_type: JobType,
+    ) -> Result<Vec<JobItem>> {
+        let jobs = self.read_jobs()?;
+        Ok(jobs
+            .iter()
+            .filter(|job_a| job_a.job_type == job_a_type && job_a.status == job_a_status)
+            .filter(|job_a| {
+                !jobs.iter().any(|job_b| job_b.job_type == job_b_type && job_b.internal_id == job_a.internal_id)
+            })
+            .cloned()
+            .collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_latest_job_by_type_and_status(
+        &self,
+        job_type: JobType,
+        job_status: JobStatus,
+    ) -> Result<Option<JobItem>> {
+        Ok(self
+            .read_jobs()?
+            .iter()
+            .filter(|job| job.job_type == job_type && job.status == job_status)
+            .max_by(|a, b| compare_internal_ids(&a.internal_id, &b.internal_id))
+            .cloned())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_after_internal_id_by_job_type(
+        &self,
+        job_type: JobType,
+        job_status: JobStatus,
+        internal_id: String,
+    ) -> Result<Vec<JobItem>> {
+        let internal_id_number: u64 =
+            internal_id.parse().wrap_err(format!("Internal ID {} is not a number", internal_id))?;
+        Ok(self
+            .read_jobs()?
+            .iter()
+            .filter(|job| job.job_type == job_type && job.status == job_status)
+            .filter(|job| job.internal_id.parse::<u64>().is_ok_and(|id| id > internal_id_number))
+            .cloned()
+            .collect())
+    }
+
+    #[tracing::instrument(skip(self, limit), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_by_statuses(&self, job_status: Vec<JobStatus>, limit: Option<i64>) -> Result<Vec<JobItem>> {
+        let jobs = self.read_jobs()?;
+        let matching_jobs = jobs.iter().filter(|job| job_status.contains(&job.status)).cloned();
+        Ok(match limit {
+            Some(limit) => matching_jobs.take(usize::try_from(limit).unwrap_or(0)).collect(),
+            None => matching_jobs.collect(),
+        })
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs(&self, filter: JobFilter, cursor: Option<JobCursor>, limit: i64) -> Result<Vec<JobItem>> {
+        let mut jobs: Vec<JobItem> = self
+            .read_jobs()?
+            .iter()
+            .filter(|job| filter.matches(job))
+            .filter(|job| cursor.map_or(true, |cursor| (job.created_at, job.id) > (cursor.created_at, cursor.id)))
+            .cloned()
+            .collect();
+        jobs.sort_by_key(|job| (job.created_at, job.id));
+        jobs.truncate(usize::try_from(limit).unwrap_or(0));
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_first_block_of_jobs(&self, filter: JobFilter) -> Result<Option<u64>> {
+        Ok(self
+            .read_jobs()?
+            .iter()
+            .filter(|job| filter.matches(job))
+            .filter_map(|job| job.internal_id.parse::<u64>().ok())
+            .min())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_by_block_range(&self, from_block: u64, to_block: u64) -> Result<Vec<JobItem>> {
+        let mut jobs: Vec<JobItem> =
+            self.read_jobs()?.iter().filter(|job| job.covers_blocks(from_block, to_block)).cloned().collect();
+        jobs.sort_by_key(|job| (job.created_at, job.id));
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn count_jobs_by_type_and_status(&self) -> Result<Vec<(JobType, JobStatus, u64)>> {
+        let mut counts: Vec<(JobType, JobStatus, u64)> = Vec::new();
+        for job in self.read_jobs()?.iter() {
+            match counts.iter_mut().find(|(job_type, status, _)| *job_type == job.job_type && *status == job.status) {
+                Some((_, _, count)) => *count += 1,
+                None => counts.push((job.job_type.clone(), job.status.clone(), 1)),
+            }
+        }
+        Ok(counts)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn acquire_lease(&self, name: &str, max_holders: usize, holder: Uuid, ttl: Duration) -> Result<bool> {
+        let mut leases = self.lock_leases()?;
+        let holders = leases.entry(name.to_string()).or_default();
+        let expires_at = Instant::now() + ttl;
+        if let Some(lease) = holders.iter_mut().find(|(lease_holder, _)| *lease_holder == holder) {
+            lease.1 = expires_at;
+            return Ok(true);
+        }
+        if holders.len() >= max_holders {
+            return Ok(false);
+        }
+        holders.push((holder, expires_at));
+        Ok(true)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn renew_lease(&self, name: &str, holder: Uuid, ttl: Duration) -> Result<bool> {
+        let mut leases = self.lock_leases()?;
+        let lease = leases
+            .get_mut(name)
+            .and_then(|holders| holders.iter_mut().find(|(lease_holder, _)| *lease_holder == holder));
+        Ok(match lease {
+            Some(lease) => {
+                lease.1 = Instant::now() + ttl;
+                true
+            }
+            None => false,
+        })
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn release_lease(&self, name: &str, holder: Uuid) -> Result<()> {
+        if let Some(holders) = self.lock_leases()?.get_mut(name) {
+            holders.retain(|(lease_holder, _)| *lease_holder != holder);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_lease_holders(&self, name: &str) -> Result<Vec<Uuid>> {
+        Ok(self
+            .lock_leases()?
+            .get(name)
+            .map(|holders| holders.iter().map(|(holder, _)| *holder).collect())
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn acquire_leadership(&self, name: &str, candidate: Uuid, ttl: Duration) -> Result<Option<u64>> {
+        let mut leaders = self.lock_leaders()?;
+        let now = Instant::now();
+        let leader =
+            leaders.entry(name.to_string()).or_insert(Leader { holder: candidate, fencing_token: 0, expires_at: now });
+
+        if leader.expires_at > now && leader.holder != candidate {
+            return Ok(None);
+        }
+        if leader.expires_at <= now {
+            leader.holder = candidate;
+            leader.fencing_token += 1;
+        }
+        leader.expires_at = now + ttl;
+        Ok(Some(leader.fencing_token))
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn renew_leadership(&self, name: &str, candidate: Uuid, fencing_token: u64, ttl: Duration) -> Result<bool> {
+        let mut leaders = self.lock_leaders()?;
+        let now = Instant::now();
+        Ok(match leaders.get_mut(name) {
+            Some(leader)
+                if leader.holder == candidate && leader.fencing_token == fencing_token && leader.expires_at > now =>
+            {
+                leader.expires_at = now + ttl;
+                true
+            }
+            _ => false,
+        })
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn release_leadership(&self, name: &str, candidate: Uuid, fencing_token: u64) -> Result<()> {
+        if let Some(leader) = self.lock_leaders()?.get_mut(name) {
+            if leader.holder == candidate && leader.fencing_token == fencing_token {
+                leader.expires_at = Instant::now();
+            }
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn create_dead_letter_audit_entry(&self, entry: DeadLetterAuditEntry) -> Result<()> {
+        self.lock_dead_letter_audit_log()?.push(entry);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_dead_letter_audit_entries(&self, limit: i64) -> Result<Vec<DeadLetterAuditEntry>> {
+        Ok(self.lock_dead_letter_audit_log()?.iter().rev().take(limit.max(0) as usize).cloned().collect())
+    }
+}
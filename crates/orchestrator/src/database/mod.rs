@@ -1,14 +1,104 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
 use ::mongodb::bson::doc;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{eyre, Context};
 use color_eyre::Result;
 use mockall::automock;
 use uuid::Uuid;
 
-use crate::jobs::types::{JobItem, JobStatus, JobType};
+use crate::jobs::types::{ExternalId, JobItem, JobStatus, JobType};
 use crate::jobs::JobError;
+use crate::queue::dead_letter::DeadLetterAuditEntry;
 
+/// In memory, for local runs and tests
+pub mod in_memory;
 /// MongoDB
 pub mod mongodb;
+/// Postgres
+pub mod postgres;
+
+/// Filters applied when listing jobs, every field is optional.
+/// The internal id bounds and the creation, update and failure time bounds are inclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobFilter {
+    pub job_type: Option<JobType>,
+    pub status: Option<JobStatus>,
+    pub external_id: Option<ExternalId>,
+    pub internal_id_from: Option<u64>,
+    pub internal_id_to: Option<u64>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub updated_after: Option<DateTime<Utc>>,
+    /// Jobs which failed before `CommonMetadata::failed_at` was recorded use their update time
+    pub failed_after: Option<DateTime<Utc>>,
+}
+
+impl JobFilter {
+    /// Whether the job passes the filter, used by the implementations that filter in process.
+    pub fn matches(&self, job: &JobItem) -> bool {
+        let internal_id = job.internal_id.parse::<u64>().ok();
+        self.job_type.as_ref().map_or(true, |job_type| &job.job_type == job_type)
+            && self.status.as_ref().map_or(true, |status| &job.status == status)
+            && self.external_id.as_ref().map_or(true, |external_id| &job.external_id == external_id)
+            && self.internal_id_from.map_or(true, |from| internal_id.is_some_and(|id| id >= from))
+            && self.internal_id_to.map_or(true, |to| internal_id.is_some_and(|id| id <= to))
+            && self.created_after.map_or(true, |after| job.created_at >= after)
+            && self.created_before.map_or(true, |before| job.created_at <= before)
+            && self.updated_after.map_or(true, |after| job.updated_at >= after)
+            && self.failed_after.map_or(true, |after| job.failed_at() >= after)
+    }
+}
+
+/// The leadership a write is made under, see `Database::acquire_leadership`.
+///
+/// A fenced write is rejected once another candidate took the leadership over, so that a former
+/// leader which doesn't know yet it lost the leadership can't write anymore.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fence {
+    pub election: String,
+    pub fencing_token: u64,
+}
+
+/// Position of a job in the job listing.
+///
+/// Jobs are listed by creation time, ties broken by id, so the cursor of the last job of a page
+/// is enough to fetch the next page even if jobs are created in the meantime.
+/// It's exposed as `<created_at in microseconds>_<id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl From<&JobItem> for JobCursor {
+    fn from(job: &JobItem) -> Self {
+        Self { created_at: job.created_at, id: job.id }
+    }
+}
+
+impl fmt::Display for JobCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.created_at.timestamp_micros(), self.id)
+    }
+}
+
+impl FromStr for JobCursor {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (created_at, id) = s.split_once('_').ok_or_else(|| eyre!("Invalid cursor: {}", s))?;
+        let created_at = created_at.parse::<i64>().wrap_err(format!("Invalid cursor timestamp: {}", created_at))?;
+        Ok(Self {
+            created_at: DateTime::from_timestamp_micros(created_at)
+                .ok_or_else(|| eyre!("Cursor timestamp out of range: {}", created_at))?,
+            id: Uuid::parse_str(id).wrap_err(format!("Invalid cursor id: {}", id))?,
+        })
+    }
+}
 
 /// Database Trait
 ///
@@ -25,9 +115,17 @@ pub mod mongodb;
 #[async_trait]
 pub trait Database: Send + Sync {
     async fn create_job(&self, job: JobItem) -> Result<JobItem, JobError>;
+    /// Creates the job like `create_job`, if the election of `fence` is still led with its
+    /// fencing token. Fails with `JobError::StaleFence` otherwise.
+    async fn create_job_fenced(&self, job: JobItem, fence: &Fence) -> Result<JobItem, JobError>;
     async fn get_job_by_id(&self, id: Uuid) -> Result<Option<JobItem>>;
     async fn get_job_by_internal_id_and_type(&self, internal_id: &str, job_type: &JobType) -> Result<Option<JobItem>>;
     async fn update_job(&self, current_job: &JobItem, updates: crate::jobs::types::JobItemUpdates) -> Result<JobItem>;
+    /// Refreshes the update time of the job `id` if it's still in `status`, without changing its
+    /// version. Keeps a job being handled for long from being taken for a stuck one.
+    ///
+    /// Returns false if the job isn't in `status` anymore.
+    async fn heartbeat_job(&self, id: Uuid, status: JobStatus) -> Result<bool>;
     async fn get_latest_job_by_type(&self, job_type: JobType) -> Result<Option<JobItem>>;
     async fn get_jobs_without_successor(
         &self,
@@ -48,4 +146,61 @@ pub trait Database: Send + Sync {
     ) -> Result<Vec<JobItem>>;
 
     async fn get_jobs_by_statuses(&self, status: Vec<JobStatus>, limit: Option<i64>) -> Result<Vec<JobItem>>;
+
+    /// Lists up to `limit` jobs matching `filter`, ordered by creation time then id and starting
+    /// right after `cursor` if any.
+    async fn get_jobs(&self, filter: JobFilter, cursor: Option<JobCursor>, limit: i64) -> Result<Vec<JobItem>>;
+
+    /// Lists the jobs handling any block between `from_block` and `to_block`, both included,
+    /// ordered by creation time. See `JobItem::covers_blocks`.
+    async fn get_jobs_by_block_range(&self, from_block: u64, to_block: u64) -> Result<Vec<JobItem>>;
+
+    /// The lowest internal id, i.e. the first block, of the jobs matching `filter`. `None` if no
+    /// job matches.
+    async fn get_first_block_of_jobs(&self, filter: JobFilter) -> Result<Option<u64>>;
+
+    /// Counts the jobs of each type and status. Pairs without any job are left out.
+    async fn count_jobs_by_type_and_status(&self) -> Result<Vec<(JobType, JobStatus, u64)>>;
+
+    /// Tries to take one of the `max_holders` slots of the lease semaphore `name` for `holder`, for
+    /// `ttl`. Slots whose lease expired are free, so the slots of a crashed holder are freed once
+    /// its leases expire. A holder which already holds a slot keeps it and gets its lease renewed.
+    ///
+    /// Returns whether `holder` holds a slot.
+    async fn acquire_lease(&self, name: &str, max_holders: usize, holder: Uuid, ttl: Duration) -> Result<bool>;
+
+    /// Extends the lease of `holder` on the lease semaphore `name` by `ttl` from now.
+    ///
+    /// Returns false if `holder` doesn't hold a slot anymore, e.g. its lease expired and was taken.
+    async fn renew_lease(&self, name: &str, holder: Uuid, ttl: Duration) -> Result<bool>;
+
+    /// Frees the slot of `holder` on the lease semaphore `name`, if any.
+    async fn release_lease(&self, name: &str, holder: Uuid) -> Result<()>;
+
+    /// Lists the holders of the unexpired leases of the lease semaphore `name`.
+    async fn get_lease_holders(&self, name: &str) -> Result<Vec<Uuid>>;
+
+    /// Tries to make `candidate` the leader of the election `name` for `ttl`. The leadership is
+    /// taken if there's no leader or the lease of the leader expired. A candidate which already
+    /// leads keeps the leadership and gets its lease renewed.
+    ///
+    /// Returns the fencing token of the leadership if `candidate` leads, `None` otherwise. The
+    /// fencing token increases every time the leadership changes hands, so it tells the
+    /// successive leaders apart.
+    async fn acquire_leadership(&self, name: &str, candidate: Uuid, ttl: Duration) -> Result<Option<u64>>;
+
+    /// Extends the lease of `candidate` on the election `name` by `ttl` from now.
+    ///
+    /// Returns false if `candidate` doesn't lead with `fencing_token` anymore.
+    async fn renew_leadership(&self, name: &str, candidate: Uuid, fencing_token: u64, ttl: Duration) -> Result<bool>;
+
+    /// Ends the leadership of `candidate` on the election `name`, if it still leads with
+    /// `fencing_token`. The fencing token is kept for the next leader.
+    async fn release_leadership(&self, name: &str, candidate: Uuid, fencing_token: u64) -> Result<()>;
+
+    /// Appends `entry` to the dead letter audit log.
+    async fn create_dead_letter_audit_entry(&self, entry: DeadLetterAuditEntry) -> Result<()>;
+
+    /// Lists the `limit` latest entries of the dead letter audit log, newest first.
+    async fn get_dead_letter_audit_entries(&self, limit: i64) -> Result<Vec<DeadLetterAuditEntry>>;
 }
@@ -1,26 +1,31 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_std::stream::StreamExt;
 use async_trait::async_trait;
-use chrono::{SubsecRound, Utc};
+use chrono::{DateTime, SubsecRound, Utc};
 use color_eyre::eyre::eyre;
 use color_eyre::Result;
 use futures::TryStreamExt;
+use mongodb::bson::serde_helpers::{chrono_datetime_as_bson_datetime, uuid_1_as_binary};
 use mongodb::bson::{doc, Bson, Document};
+use mongodb::error::{ErrorKind, WriteFailure};
 use mongodb::options::{
     ClientOptions, FindOneAndUpdateOptions, FindOneOptions, FindOptions, ReturnDocument, ServerApi, ServerApiVersion,
     UpdateOptions,
 };
 use mongodb::{bson, Client, Collection};
 use opentelemetry::KeyValue;
+use serde::{Deserialize, Serialize};
 use url::Url;
 use utils::ToDocument;
 use uuid::Uuid;
 
-use crate::database::Database;
+use crate::database::{Database, Fence, JobCursor, JobFilter};
 use crate::jobs::types::{JobItem, JobItemUpdates, JobStatus, JobType};
 use crate::jobs::JobError;
 use crate::metrics::ORCHESTRATOR_METRICS;
+use crate::queue::dead_letter::{DeadLetterAction, DeadLetterAuditEntry};
+use crate::queue::QueueType;
 
 mod utils;
 
@@ -65,6 +70,128 @@ impl MongoDb {
     fn get_job_collection(&self) -> Collection<JobItem> {
         self.client.database(&self.database_name).collection("jobs")
     }
+
+    /// Each document of the leases collection is a slot of a lease semaphore, with `name:slot` as
+    /// `_id`, so that a slot can't be inserted twice.
+    fn get_lease_collection(&self) -> Collection<Document> {
+        self.client.database(&self.database_name).collection("leases")
+    }
+
+    /// Each document of the leaders collection is an election, with its name as `_id`.
+    fn get_leader_collection(&self) -> Collection<LeaderDocument> {
+        self.client.database(&self.database_name).collection("leaders")
+    }
+
+    fn get_dead_letter_audit_collection(&self) -> Collection<DeadLetterAuditDocument> {
+        self.client.database(&self.database_name).collection("dead_letter_audit_log")
+    }
+}
+
+/// A slot of a lease semaphore, see `Database::acquire_lease`.
+#[derive(Debug, Deserialize)]
+struct LeaseDocument {
+    #[serde(with = "uuid_1_as_binary")]
+    holder: Uuid,
+}
+
+/// The leader of an election, see `Database::acquire_leadership`.
+#[derive(Debug, Deserialize)]
+struct LeaderDocument {
+    fencing_token: i64,
+}
+
+/// An entry of the dead letter audit log, see `Database::create_dead_letter_audit_entry`.
+#[derive(Debug, Serialize, Deserialize)]
+struct DeadLetterAuditDocument {
+    #[serde(rename = "_id", with = "uuid_1_as_binary")]
+    id: Uuid,
+    #[serde(with = "uuid_1_as_binary")]
+    job_id: Uuid,
+    action: DeadLetterAction,
+    actor: String,
+    source_queue: QueueType,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    created_at: DateTime<Utc>,
+}
+
+impl From<DeadLetterAuditEntry> for DeadLetterAuditDocument {
+    fn from(entry: DeadLetterAuditEntry) -> Self {
+        Self {
+            id: entry.id,
+            job_id: entry.job_id,
+            action: entry.action,
+            actor: entry.actor,
+            source_queue: entry.source_queue,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+impl From<DeadLetterAuditDocument> for DeadLetterAuditEntry {
+    fn from(document: DeadLetterAuditDocument) -> Self {
+        Self {
+            id: document.id,
+            job_id: document.job_id,
+            action: document.action,
+            actor: document.actor,
+            source_queue: document.source_queue,
+            created_at: document.created_at,
+        }
+    }
+}
+
+/// The number of jobs of a type and status, see `Database::count_jobs_by_type_and_status`.
+#[derive(Debug, Deserialize)]
+struct JobCountDocument {
+    #[serde(rename = "_id")]
+    group: JobCountGroup,
+    count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobCountGroup {
+    job_type: JobType,
+    status: JobStatus,
+}
+
+/// The conditions of `filter`, to be combined with `$and`.
+fn job_filter_conditions(filter: &JobFilter) -> Result<Vec<Document>> {
+    let mut conditions: Vec<Document> = Vec::new();
+    if let Some(job_type) = &filter.job_type {
+        conditions.push(doc! { "job_type": bson::to_bson(job_type)? });
+    }
+    if let Some(status) = &filter.status {
+        conditions.push(doc! { "status": bson::to_bson(status)? });
+    }
+    if let Some(external_id) = &filter.external_id {
+        conditions.push(doc! { "external_id": bson::to_bson(external_id)? });
+    }
+    // internal ids are block numbers stored as strings
+    if let Some(internal_id_from) = filter.internal_id_from {
+        conditions.push(doc! { "$expr": { "$gte": [{ "$toLong": "$internal_id" }, internal_id_from as i64] } });
+    }
+    if let Some(internal_id_to) = filter.internal_id_to {
+        conditions.push(doc! { "$expr": { "$lte": [{ "$toLong": "$internal_id" }, internal_id_to as i64] } });
+    }
+    if let Some(created_after) = filter.created_after {
+        conditions.push(doc! { "created_at": { "$gte": created_after } });
+    }
+    if let Some(created_before) = filter.created_before {
+        conditions.push(doc! { "created_at": { "$lte": created_before } });
+    }
+    if let Some(updated_after) = filter.updated_after {
+        conditions.push(doc! { "updated_at": { "$gte": updated_after } });
+    }
+    // the failure time is stored in seconds, jobs failed before it was recorded use their update time
+    if let Some(failed_after) = filter.failed_after {
+        conditions.push(doc! {
+            "$or": [
+                { "metadata.common.failed_at": { "$gte": failed_after.timestamp() } },
+                { "metadata.common.failed_at": Bson::Null, "updated_at": { "$gte": failed_after } },
+            ]
+        });
+    }
+    Ok(conditions)
 }
 
 #[async_trait]
@@ -107,6 +234,23 @@ impl Database for MongoDb {
         }
     }
 
+    /// MongoDB can't lock the leader while the job is inserted without a replica set, so the
+    /// fencing token is checked right before the insert. A takeover in between isn't caught, it's
+    /// unlikely as the former leader only loses the leadership once its lease expired.
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn create_job_fenced(&self, job: JobItem, fence: &Fence) -> Result<JobItem, JobError> {
+        let filter = doc! { "_id": &fence.election };
+        let leader = self
+            .get_leader_collection()
+            .find_one(filter, None)
+            .await
+            .map_err(|e| JobError::Other(e.to_string().into()))?;
+        if leader.map(|leader| leader.fencing_token) != Some(fence.fencing_token as i64) {
+            return Err(JobError::StaleFence { election: fence.election.clone(), fencing_token: fence.fencing_token });
+        }
+        self.create_job(job).await
+    }
+
     #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
     async fn get_job_by_id(&self, id: Uuid) -> Result<Option<JobItem>> {
         let start = Instant::now();
@@ -183,6 +327,24 @@ impl Database for MongoDb {
         }
     }
 
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn heartbeat_job(&self, id: Uuid, status: JobStatus) -> Result<bool> {
+        let start = Instant::now();
+        let filter = doc! {
+            "id": id,
+            "status": bson::to_bson(&status)?,
+        };
+        let update = doc! {
+            "$set": { "updated_at": Bson::DateTime(Utc::now().round_subsecs(0).into()) }
+        };
+        let result = self.get_job_collection().update_one(filter, update, None).await?;
+        tracing::debug!(job_id = %id, category = "db_call", "Refreshed the update time of the job");
+        let attributes = [KeyValue::new("db_operation_name", "heartbeat_job")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(result.matched_count == 1)
+    }
+
     #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
     async fn get_latest_job_by_type(&self, job_type: JobType) -> Result<Option<JobItem>> {
         let start = Instant::now();
@@ -431,4 +593,345 @@ impl Database for MongoDb {
         ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
         Ok(jobs)
     }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs(&self, filter: JobFilter, cursor: Option<JobCursor>, limit: i64) -> Result<Vec<JobItem>> {
+        let start = Instant::now();
+        let mut conditions = job_filter_conditions(&filter)?;
+        if let Some(cursor) = cursor {
+            conditions.push(doc! {
+                "$or": [
+                    { "created_at": { "$gt": cursor.created_at } },
+                    { "created_at": cursor.created_at, "id": { "$gt": cursor.id } },
+                ]
+            });
+        }
+        let filter = if conditions.is_empty() {
+            doc! {}
+        } else {
+            doc! { "$and": conditions }
+        };
+
+        let find_options = FindOptions::builder().sort(doc! { "created_at": 1, "id": 1 }).limit(Some(limit)).build();
+
+        let jobs: Vec<JobItem> = self.get_job_collection().find(filter, find_options).await?.try_collect().await?;
+        tracing::debug!(job_count = jobs.len(), category = "db_call", "Listed jobs");
+        let attributes = [KeyValue::new("db_operation_name", "get_jobs")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_by_block_range(&self, from_block: u64, to_block: u64) -> Result<Vec<JobItem>> {
+        let start = Instant::now();
+        let (from_block, to_block) = (from_block as i64, to_block as i64);
+        let block_job_types = [JobType::SnosRun, JobType::ProofCreation, JobType::DataSubmission]
+            .iter()
+            .map(bson::to_bson)
+            .collect::<Result<Vec<Bson>, _>>()?;
+        // state transition and proof registration jobs cover several blocks, listed in their metadata
+        let filter = doc! {
+            "$or": [
+                {
+                    "job_type": { "$in": block_job_types },
+                    "$expr": {
+                        "$and": [
+                            { "$gte": [{ "$toLong": "$internal_id" }, from_block] },
+                            { "$lte": [{ "$toLong": "$internal_id" }, to_block] },
+                        ]
+                    }
+                },
+                {
+                    "job_type": bson::to_bson(&JobType::StateTransition)?,
+                    "metadata.specific.blocks_to_settle": { "$elemMatch": { "$gte": from_block, "$lte": to_block } },
+                },
+                {
+                    "job_type": bson::to_bson(&JobType::ProofRegistration)?,
+                    "metadata.specific.from_block": { "$lte": to_block },
+                    "metadata.specific.to_block": { "$gte": from_block },
+                },
+            ]
+        };
+        let find_options = FindOptions::builder().sort(doc! { "created_at": 1, "id": 1 }).build();
+
+        let jobs: Vec<JobItem> = self.get_job_collection().find(filter, find_options).await?.try_collect().await?;
+        tracing::debug!(
+            job_count = jobs.len(),
+            from_block,
+            to_block,
+            category = "db_call",
+            "Fetched jobs by block range"
+        );
+        let attributes = [KeyValue::new("db_operation_name", "get_jobs_by_block_range")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_first_block_of_jobs(&self, filter: JobFilter) -> Result<Option<u64>> {
+        let start = Instant::now();
+        let conditions = job_filter_conditions(&filter)?;
+        let filter = if conditions.is_empty() {
+            doc! {}
+        } else {
+            doc! { "$and": conditions }
+        };
+        let pipeline = vec![
+            doc! { "$match": filter },
+            doc! { "$group": { "_id": Bson::Null, "first_block": { "$min": { "$toLong": "$internal_id" } } } },
+        ];
+
+        let first_block = match self.get_job_collection().aggregate(pipeline, None).await?.try_next().await? {
+            Some(document) => Some(document.get_i64("first_block")? as u64),
+            None => None,
+        };
+        tracing::debug!(first_block = ?first_block, category = "db_call", "Fetched the first block of the jobs");
+        let attributes = [KeyValue::new("db_operation_name", "get_first_block_of_jobs")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(first_block)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn count_jobs_by_type_and_status(&self) -> Result<Vec<(JobType, JobStatus, u64)>> {
+        let start = Instant::now();
+        let pipeline = vec![doc! {
+            "$group": {
+                "_id": { "job_type": "$job_type", "status": "$status" },
+                "count": { "$sum": 1 }
+            }
+        }];
+
+        let counts: Vec<(JobType, JobStatus, u64)> = self
+            .get_job_collection()
+            .aggregate(pipeline, None)
+            .await?
+            .try_collect::<Vec<Document>>()
+            .await?
+            .into_iter()
+            .map(|document| {
+                let count: JobCountDocument = bson::from_document(document)?;
+                Ok((count.group.job_type, count.group.status, count.count as u64))
+            })
+            .collect::<Result<_>>()?;
+        tracing::debug!(count_groups = counts.len(), category = "db_call", "Counted jobs by type and status");
+        let attributes = [KeyValue::new("db_operation_name", "count_jobs_by_type_and_status")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(counts)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn acquire_lease(&self, name: &str, max_holders: usize, holder: Uuid, ttl: Duration) -> Result<bool> {
+        let start = Instant::now();
+        if self.renew_lease(name, holder, ttl).await? {
+            return Ok(true);
+        }
+
+        let now = Utc::now();
+        let expires_at = now + chrono::Duration::from_std(ttl)?;
+        let options = UpdateOptions::builder().upsert(true).build();
+        let mut acquired = false;
+        for slot in 0..max_holders {
+            // a slot with an unexpired lease doesn't match the filter, so the upsert tries to insert
+            // it again and fails on its `_id`
+            let filter = doc! {
+                "_id": format!("{}:{}", name, slot),
+                "expires_at": { "$lte": now }
+            };
+            let update = doc! {
+                "$set": { "name": name, "slot": slot as i64, "holder": holder, "expires_at": expires_at }
+            };
+            match self.get_lease_collection().update_one(filter, update, options.clone()).await {
+                Ok(_) => {
+                    acquired = true;
+                    break;
+                }
+                Err(e) if is_duplicate_key_error(&e) => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        tracing::debug!(lease = %name, holder = %holder, acquired, category = "db_call", "Tried to acquire lease");
+        let attributes = [KeyValue::new("db_operation_name", "acquire_lease")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(acquired)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn renew_lease(&self, name: &str, holder: Uuid, ttl: Duration) -> Result<bool> {
+        let start = Instant::now();
+        let now = Utc::now();
+        let filter = doc! {
+            "name": name,
+            "holder": holder,
+            "expires_at": { "$gt": now }
+        };
+        let update = doc! {
+            "$set": { "expires_at": now + chrono::Duration::from_std(ttl)? }
+        };
+        let result = self.get_lease_collection().update_one(filter, update, None).await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "renew_lease")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(result.matched_count > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn release_lease(&self, name: &str, holder: Uuid) -> Result<()> {
+        let start = Instant::now();
+        let filter = doc! {
+            "name": name,
+            "holder": holder
+        };
+        self.get_lease_collection().delete_many(filter, None).await?;
+
+        tracing::debug!(lease = %name, holder = %holder, category = "db_call", "Released lease");
+        let attributes = [KeyValue::new("db_operation_name", "release_lease")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_lease_holders(&self, name: &str) -> Result<Vec<Uuid>> {
+        let start = Instant::now();
+        let filter = doc! {
+            "name": name,
+            "expires_at": { "$gt": Utc::now() }
+        };
+        let leases: Vec<LeaseDocument> = self
+            .get_lease_collection()
+            .clone_with_type::<LeaseDocument>()
+            .find(filter, None)
+            .await?
+            .try_collect()
+            .await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "get_lease_holders")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(leases.into_iter().map(|lease| lease.holder).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn acquire_leadership(&self, name: &str, candidate: Uuid, ttl: Duration) -> Result<Option<u64>> {
+        let start = Instant::now();
+        let now = Utc::now();
+        let expires_at = now + chrono::Duration::from_std(ttl)?;
+        let options = FindOneAndUpdateOptions::builder().return_document(ReturnDocument::After).build();
+
+        // the leader keeps its leadership
+        let filter = doc! {
+            "_id": name,
+            "holder": candidate,
+            "expires_at": { "$gt": now }
+        };
+        let update = doc! {
+            "$set": { "expires_at": expires_at }
+        };
+        let mut leader = self.get_leader_collection().find_one_and_update(filter, update, options.clone()).await?;
+
+        if leader.is_none() {
+            // a leadership which expired is taken over. If the leader's lease is still running, the
+            // filter doesn't match and the upsert fails on the `_id`
+            let filter = doc! {
+                "_id": name,
+                "expires_at": { "$lte": now }
+            };
+            let update = doc! {
+                "$set": { "holder": candidate, "expires_at": expires_at },
+                "$inc": { "fencing_token": 1_i64 }
+            };
+            let options =
+                FindOneAndUpdateOptions::builder().upsert(true).return_document(ReturnDocument::After).build();
+            leader = match self.get_leader_collection().find_one_and_update(filter, update, options).await {
+                Ok(leader) => leader,
+                Err(e) if is_duplicate_key_error(&e) => None,
+                Err(e) => return Err(e.into()),
+            };
+        }
+
+        let fencing_token = leader.map(|leader| leader.fencing_token as u64);
+        tracing::debug!(election = %name, candidate = %candidate, ?fencing_token, category = "db_call", "Tried to acquire leadership");
+        let attributes = [KeyValue::new("db_operation_name", "acquire_leadership")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(fencing_token)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn renew_leadership(&self, name: &str, candidate: Uuid, fencing_token: u64, ttl: Duration) -> Result<bool> {
+        let start = Instant::now();
+        let now = Utc::now();
+        let filter = doc! {
+            "_id": name,
+            "holder": candidate,
+            "fencing_token": fencing_token as i64,
+            "expires_at": { "$gt": now }
+        };
+        let update = doc! {
+            "$set": { "expires_at": now + chrono::Duration::from_std(ttl)? }
+        };
+        let result = self.get_leader_collection().update_one(filter, update, None).await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "renew_leadership")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(result.matched_count > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn release_leadership(&self, name: &str, candidate: Uuid, fencing_token: u64) -> Result<()> {
+        let start = Instant::now();
+        // the document is kept so the next leader gets the next fencing token
+        let filter = doc! {
+            "_id": name,
+            "holder": candidate,
+            "fencing_token": fencing_token as i64
+        };
+        let update = doc! {
+            "$set": { "expires_at": Utc::now() }
+        };
+        self.get_leader_collection().update_one(filter, update, None).await?;
+
+        tracing::debug!(election = %name, candidate = %candidate, fencing_token, category = "db_call", "Released leadership");
+        let attributes = [KeyValue::new("db_operation_name", "release_leadership")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn create_dead_letter_audit_entry(&self, entry: DeadLetterAuditEntry) -> Result<()> {
+        let start = Instant::now();
+        self.get_dead_letter_audit_collection().insert_one(DeadLetterAuditDocument::from(entry), None).await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "create_dead_letter_audit_entry")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_dead_letter_audit_entries(&self, limit: i64) -> Result<Vec<DeadLetterAuditEntry>> {
+        let start = Instant::now();
+        let find_options = FindOptions::builder().sort(doc! { "created_at": -1, "_id": -1 }).limit(Some(limit)).build();
+        let entries: Vec<DeadLetterAuditDocument> =
+            self.get_dead_letter_audit_collection().find(None, find_options).await?.try_collect().await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "get_dead_letter_audit_entries")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(entries.into_iter().map(DeadLetterAuditEntry::from).collect())
+    }
+}
+
+/// Whether the operation failed because a document with the same `_id` or unique key exists.
+fn is_duplicate_key_error(error: &mongodb::error::Error) -> bool {
+    matches!(*error.kind, ErrorKind::Write(WriteFailure::WriteError(ref write_error)) if write_error.code == 11000)
 }
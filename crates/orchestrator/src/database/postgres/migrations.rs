@@ -0,0 +1,145 @@
+use color_eyre::eyre::Context;
+use color_eyre::Result;
+use sqlx::{Connection, PgConnection, PgPool, Row};
+
+/// A schema migration, applied once and in order of `version`.
+pub struct Migration {
+    pub version: i32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Migrations of the orchestrator schema.
+/// Applied migrations must never be edited, changes to the schema go in a new migration.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_jobs_table",
+        sql: r#"
+        CREATE TABLE jobs (
+            seq BIGSERIAL NOT NULL,
+            id UUID PRIMARY KEY,
+            internal_id TEXT NOT NULL,
+            job_type TEXT NOT NULL,
+            status TEXT NOT NULL,
+            external_id JSONB NOT NULL,
+            metadata JSONB NOT NULL,
+            version INTEGER NOT NULL,
+            created_at TIMESTAMPTZ NOT NULL,
+            updated_at TIMESTAMPTZ NOT NULL,
+            UNIQUE (job_type, internal_id)
+        );
+        CREATE INDEX jobs_job_type_status_idx ON jobs (job_type, status);
+        CREATE INDEX jobs_status_idx ON jobs (status);
+    "#,
+    },
+    Migration {
+        version: 2,
+        name: "index_jobs_by_creation",
+        sql: r#"
+        CREATE INDEX jobs_created_at_id_idx ON jobs (created_at, id);
+    "#,
+    },
+    Migration {
+        version: 3,
+        name: "create_leases_table",
+        sql: r#"
+        CREATE TABLE leases (
+            name TEXT NOT NULL,
+            slot INTEGER NOT NULL,
+            holder UUID NOT NULL,
+            expires_at TIMESTAMPTZ NOT NULL,
+            PRIMARY KEY (name, slot)
+        );
+        CREATE INDEX leases_name_holder_idx ON leases (name, holder);
+    "#,
+    },
+    Migration {
+        version: 4,
+        name: "create_leaders_table",
+        sql: r#"
+        CREATE TABLE leaders (
+            name TEXT PRIMARY KEY,
+            holder UUID NOT NULL,
+            fencing_token BIGINT NOT NULL,
+            expires_at TIMESTAMPTZ NOT NULL
+        );
+    "#,
+    },
+    Migration {
+        version: 5,
+        name: "create_dead_letter_audit_log_table",
+        sql: r#"
+        CREATE TABLE dead_letter_audit_log (
+            id UUID PRIMARY KEY,
+            job_id UUID NOT NULL,
+            action TEXT NOT NULL,
+            actor TEXT NOT NULL,
+            source_queue TEXT NOT NULL,
+            created_at TIMESTAMPTZ NOT NULL
+        );
+        CREATE INDEX dead_letter_audit_log_created_at_idx ON dead_letter_audit_log (created_at);
+    "#,
+    },
+    Migration {
+        version: 6,
+        name: "add_jobs_block_number",
+        sql: r#"
+        ALTER TABLE jobs ADD COLUMN block_number BIGINT GENERATED ALWAYS AS
+            (CASE WHEN internal_id ~ '^[0-9]+$' THEN internal_id::BIGINT END) STORED;
+        CREATE INDEX jobs_job_type_block_number_idx ON jobs (job_type, block_number DESC NULLS LAST);
+    "#,
+    },
+];
+
+/// Key of the advisory lock taken while migrating, so that replicas starting together
+/// don't apply the same migration twice.
+const MIGRATIONS_LOCK_KEY: i64 = 0x6f72_6368_6573_7472;
+
+/// Applies the migrations which haven't been applied yet.
+/// Each migration runs in its own transaction, together with its bookkeeping in
+/// `schema_migrations`.
+pub async fn run_migrations(pool: &PgPool) -> Result<()> {
+    let mut conn = pool.acquire().await.context("Failed to acquire a connection to run the migrations")?;
+    sqlx::query("SELECT pg_advisory_lock($1)").bind(MIGRATIONS_LOCK_KEY).execute(&mut *conn).await?;
+
+    let result = apply_pending_migrations(&mut conn).await;
+
+    sqlx::query("SELECT pg_advisory_unlock($1)").bind(MIGRATIONS_LOCK_KEY).execute(&mut *conn).await?;
+    result
+}
+
+async fn apply_pending_migrations(conn: &mut PgConnection) -> Result<()> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+        )",
+    )
+    .execute(&mut *conn)
+    .await
+    .context("Failed to create the schema_migrations table")?;
+
+    let current_version: i32 = sqlx::query("SELECT COALESCE(MAX(version), 0) AS version FROM schema_migrations")
+        .fetch_one(&mut *conn)
+        .await?
+        .try_get("version")?;
+
+    for migration in MIGRATIONS.iter().filter(|migration| migration.version > current_version) {
+        let mut tx = conn.begin().await?;
+        sqlx::raw_sql(migration.sql)
+            .execute(&mut *tx)
+            .await
+            .context(format!("Failed to apply migration {} ({})", migration.version, migration.name))?;
+        sqlx::query("INSERT INTO schema_migrations (version, name) VALUES ($1, $2)")
+            .bind(migration.version)
+            .bind(migration.name)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        tracing::info!(version = migration.version, name = migration.name, "Applied database migration");
+    }
+
+    Ok(())
+}
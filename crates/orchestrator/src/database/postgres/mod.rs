@@ -0,0 +1,664 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, SubsecRound, Utc};
+use color_eyre::eyre::{eyre, Context};
+use color_eyre::Result;
+use opentelemetry::KeyValue;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::postgres::{PgPoolOptions, PgRow};
+use sqlx::types::Json;
+use sqlx::{PgExecutor, PgPool, Postgres, QueryBuilder, Row};
+use url::Url;
+use uuid::Uuid;
+
+use crate::database::{Database, Fence, JobCursor, JobFilter};
+use crate::jobs::metadata::JobMetadata;
+use crate::jobs::types::{ExternalId, JobItem, JobItemUpdates, JobStatus, JobType};
+use crate::jobs::JobError;
+use crate::metrics::ORCHESTRATOR_METRICS;
+use crate::queue::dead_letter::DeadLetterAuditEntry;
+
+mod migrations;
+
+const JOB_COLUMNS: &str = "id, internal_id, job_type, status, external_id, metadata, version, created_at, updated_at";
+
+#[derive(Debug, Clone)]
+pub struct PostgresValidatedArgs {
+    pub connection_url: Url,
+    pub max_connections: u32,
+}
+
+pub struct PostgresDb {
+    pool: PgPool,
+}
+
+impl PostgresDb {
+    pub async fn new_with_args(postgres_params: &PostgresValidatedArgs) -> Self {
+        let pool = PgPoolOptions::new()
+            .max_connections(postgres_params.max_connections)
+            .connect(postgres_params.connection_url.as_str())
+            .await
+            .expect("Failed to connect to Postgres");
+        migrations::run_migrations(&pool).await.expect("Failed to run the Postgres migrations");
+        tracing::debug!("Connected to Postgres and applied the migrations.");
+
+        Self { pool }
+    }
+
+    /// The pool uses Arc internally, reducing the cost of clone.
+    pub fn pool(&self) -> PgPool {
+        self.pool.clone()
+    }
+}
+
+/// Enums are stored as their serde representation, same as in MongoDB.
+fn enum_to_text<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(text) => Ok(text),
+        other => Err(eyre!("Expected enum to serialize into a string, got {}", other)),
+    }
+}
+
+fn text_to_enum<T: DeserializeOwned>(text: String) -> Result<T> {
+    Ok(serde_json::from_value(serde_json::Value::String(text))?)
+}
+
+fn row_to_job(row: &PgRow) -> Result<JobItem> {
+    Ok(JobItem {
+        id: row.try_get("id")?,
+        internal_id: row.try_get("internal_id")?,
+        job_type: text_to_enum(row.try_get("job_type")?)?,
+        status: text_to_enum(row.try_get("status")?)?,
+        external_id: row.try_get::<Json<ExternalId>, _>("external_id")?.0,
+        metadata: row.try_get::<Json<JobMetadata>, _>("metadata")?.0,
+        version: row.try_get("version")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+fn row_to_dead_letter_audit_entry(row: &PgRow) -> Result<DeadLetterAuditEntry> {
+    Ok(DeadLetterAuditEntry {
+        id: row.try_get("id")?,
+        job_id: row.try_get("job_id")?,
+        action: text_to_enum(row.try_get("action")?)?,
+        actor: row.try_get("actor")?,
+        source_queue: text_to_enum(row.try_get("source_queue")?)?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+/// Inserts `job`, returns false if a job of the same type and internal id already exists.
+async fn insert_job<'e, E: PgExecutor<'e>>(executor: E, job: &JobItem) -> Result<bool, JobError> {
+    let job_type = enum_to_text(&job.job_type).map_err(|e| JobError::Other(e.into()))?;
+    let status = enum_to_text(&job.status).map_err(|e| JobError::Other(e.into()))?;
+
+    // the unique (job_type, internal_id) constraint prevents creating the same job twice
+    let result = sqlx::query(
+        "INSERT INTO jobs (id, internal_id, job_type, status, external_id, metadata, version, created_at, updated_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (job_type, internal_id) DO NOTHING",
+    )
+    .bind(job.id)
+    .bind(&job.internal_id)
+    .bind(job_type)
+    .bind(status)
+    .bind(Json(&job.external_id))
+    .bind(Json(&job.metadata))
+    .bind(job.version)
+    .bind(job.created_at)
+    .bind(job.updated_at)
+    .execute(executor)
+    .await
+    .map_err(|e| JobError::Other(e.to_string().into()))?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Appends the conditions of `filter` to a query ending with a `WHERE` clause.
+fn push_job_filter(query: &mut QueryBuilder<Postgres>, filter: &JobFilter) -> Result<()> {
+    if let Some(job_type) = &filter.job_type {
+        query.push(" AND job_type = ").push_bind(enum_to_text(job_type)?);
+    }
+    if let Some(status) = &filter.status {
+        query.push(" AND status = ").push_bind(enum_to_text(status)?);
+    }
+    if let Some(external_id) = &filter.external_id {
+        query.push(" AND external_id = ").push_bind(Json(external_id.clone()));
+    }
+    // `block_number` is the internal id of the block jobs, as a number
+    if let Some(internal_id_from) = filter.internal_id_from {
+        query.push(" AND block_number >= ").push_bind(internal_id_from as i64);
+    }
+    if let Some(internal_id_to) = filter.internal_id_to {
+        query.push(" AND block_number <= ").push_bind(internal_id_to as i64);
+    }
+    if let Some(created_after) = filter.created_after {
+        query.push(" AND created_at >= ").push_bind(created_after);
+    }
+    if let Some(created_before) = filter.created_before {
+        query.push(" AND created_at <= ").push_bind(created_before);
+    }
+    if let Some(updated_after) = filter.updated_after {
+        query.push(" AND updated_at >= ").push_bind(updated_after);
+    }
+    // the failure time is stored in seconds, jobs failed before it was recorded use their update time
+    if let Some(failed_after) = filter.failed_after {
+        query
+            .push(" AND COALESCE(TO_TIMESTAMP((metadata->'common'->>'failed_at')::BIGINT), updated_at) >= ")
+            .push_bind(failed_after);
+    }
+    Ok(())
+}
+
+fn record_db_call(operation_name: &'static str, start: Instant) {
+    let attributes = [KeyValue::new("db_operation_name", operation_name)];
+    ORCHESTRATOR_METRICS.db_calls_response_time.record(start.elapsed().as_secs_f64(), &attributes);
+}
+
+#[async_trait]
+impl Database for PostgresDb {
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn create_job(&self, job: JobItem) -> Result<JobItem, JobError> {
+        let start = Instant::now();
+        if insert_job(&self.pool, &job).await? {
+            record_db_call("create_job", start);
+            Ok(job)
+        } else {
+            Err(JobError::JobAlreadyExists { internal_id: job.internal_id, job_type: job.job_type })
+        }
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn create_job_fenced(&self, job: JobItem, fence: &Fence) -> Result<JobItem, JobError> {
+        let start = Instant::now();
+        let mut tx = self.pool.begin().await.map_err(|e| JobError::Other(e.to_string().into()))?;
+
+        // the shared lock on the leader row holds a takeover back until the job is inserted
+        let fencing_token: Option<i64> = sqlx::query("SELECT fencing_token FROM leaders WHERE name = $1 FOR SHARE")
+            .bind(&fence.election)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| JobError::Other(e.to_string().into()))?
+            .map(|row| row.try_get::<i64, _>("fencing_token"))
+            .transpose()
+            .map_err(|e| JobError::Other(e.to_string().into()))?;
+        if fencing_token != Some(fence.fencing_token as i64) {
+            return Err(JobError::StaleFence { election: fence.election.clone(), fencing_token: fence.fencing_token });
+        }
+
+        if !insert_job(&mut *tx, &job).await? {
+            return Err(JobError::JobAlreadyExists { internal_id: job.internal_id, job_type: job.job_type });
+        }
+        tx.commit().await.map_err(|e| JobError::Other(e.to_string().into()))?;
+        record_db_call("create_job_fenced", start);
+        Ok(job)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_job_by_id(&self, id: Uuid) -> Result<Option<JobItem>> {
+        let start = Instant::now();
+        let row = sqlx::query(&format!("SELECT {JOB_COLUMNS} FROM jobs WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        tracing::debug!(job_id = %id, category = "db_call", "Fetched job by ID");
+        record_db_call("get_job_by_id", start);
+        row.as_ref().map(row_to_job).transpose()
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_job_by_internal_id_and_type(&self, internal_id: &str, job_type: &JobType) -> Result<Option<JobItem>> {
+        let start = Instant::now();
+        let row = sqlx::query(&format!("SELECT {JOB_COLUMNS} FROM jobs WHERE internal_id = $1 AND job_type = $2"))
+            .bind(internal_id)
+            .bind(enum_to_text(job_type)?)
+            .fetch_optional(&self.pool)
+            .await?;
+        tracing::debug!(internal_id = %internal_id, job_type = ?job_type, category = "db_call", "Fetched job by internal ID and type");
+        record_db_call("get_job_by_internal_id_and_type", start);
+        row.as_ref().map(row_to_job).transpose()
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn update_job(&self, current_job: &JobItem, updates: JobItemUpdates) -> Result<JobItem> {
+        let start = Instant::now();
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new("UPDATE jobs SET ");
+        let mut set_clauses = query.separated(", ");
+        let mut has_updates = false;
+
+        if let Some(internal_id) = updates.internal_id {
+            set_clauses.push("internal_id = ").push_bind_unseparated(internal_id);
+            has_updates = true;
+        }
+        if let Some(job_type) = updates.job_type {
+            set_clauses.push("job_type = ").push_bind_unseparated(enum_to_text(&job_type)?);
+            has_updates = true;
+        }
+        if let Some(status) = updates.status {
+            set_clauses.push("status = ").push_bind_unseparated(enum_to_text(&status)?);
+            has_updates = true;
+        }
+        if let Some(external_id) = updates.external_id {
+            set_clauses.push("external_id = ").push_bind_unseparated(Json(external_id));
+            has_updates = true;
+        }
+        if let Some(metadata) = updates.metadata {
+            set_clauses.push("metadata = ").push_bind_unseparated(Json(metadata));
+            has_updates = true;
+        }
+
+        // throw an error if there's no field to be updated
+        if !has_updates {
+            return Err(eyre!("No field to be updated, likely a false call"));
+        }
+
+        // Add additional fields that are always updated
+        let updated_at: DateTime<Utc> = Utc::now().round_subsecs(0);
+        set_clauses.push("version = ").push_bind_unseparated(current_job.version + 1);
+        set_clauses.push("updated_at = ").push_bind_unseparated(updated_at);
+
+        // the version filter makes the update fail if the job has been updated in the meantime
+        query.push(" WHERE id = ").push_bind(current_job.id);
+        query.push(" AND version = ").push_bind(current_job.version);
+        query.push(format!(" RETURNING {JOB_COLUMNS}"));
+
+        let row = query.build().fetch_optional(&self.pool).await?;
+        match row {
+            Some(row) => {
+                tracing::debug!(job_id = %current_job.id, category = "db_call", "Job updated successfully");
+                record_db_call("update_job", start);
+                row_to_job(&row)
+            }
+            None => {
+                tracing::warn!(job_id = %current_job.id, category = "db_call", "Failed to update job. Job version is likely outdated");
+                Err(eyre!("Failed to update job. Job version is likely outdated"))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn heartbeat_job(&self, id: Uuid, status: JobStatus) -> Result<bool> {
+        let start = Instant::now();
+        let updated_at: DateTime<Utc> = Utc::now().round_subsecs(0);
+        let result = sqlx::query("UPDATE jobs SET updated_at = $1 WHERE id = $2 AND status = $3")
+            .bind(updated_at)
+            .bind(id)
+            .bind(enum_to_text(&status)?)
+            .execute(&self.pool)
+            .await?;
+        tracing::debug!(job_id = %id, category = "db_call", "Refreshed the update time of the job");
+        record_db_call("heartbeat_job", start);
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_latest_job_by_type(&self, job_type: JobType) -> Result<Option<JobItem>> {
+        let start = Instant::now();
+        let row = sqlx::query(&format!(
+            "SELECT {JOB_COLUMNS} FROM jobs WHERE job_type = $1 ORDER BY block_number DESC NULLS LAST LIMIT 1"
+        ))
+        .bind(enum_to_text(&job_type)?)
+        .fetch_optional(&self.pool)
+        .await?;
+        tracing::debug!(job_type = ?job_type, category = "db_call", "Fetching latest job by type");
+        record_db_call("get_latest_job_by_type", start);
+        row.as_ref().map(row_to_job).transpose()
+    }
+
+    /// function to get jobs that don't have a successor job.
+    ///
+    /// `job_a_type` : Type of job that we need to get that doesn't have any successor.
+    ///
+    /// `job_a_status` : Status of job A.
+    ///
+    /// `job_b_type` : Type of job that we need to have as a successor for Job A.
+    ///
+    /// Jobs are returned in creation order.
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_without_successor(
+        &self,
+        job_a_type: JobType,
+        job_a_status: JobStatus,
+        job_b_type: JobType,
+    ) -> Result<Vec<JobItem>> {
+        let start = Instant::now();
+        let rows = sqlx::query(&format!(
+            "SELECT {JOB_COLUMNS} FROM jobs AS job_a WHERE job_a.job_type = $1 AND job_a.status = $2 AND NOT EXISTS \
+             (SELECT 1 FROM jobs AS job_b WHERE job_b.job_type = $3 AND job_b.internal_id = job_a.internal_id) ORDER \
+             BY job_a.seq"
+        ))
+        .bind(enum_to_text(&job_a_type)?)
+        .bind(enum_to_text(&job_a_status)?)
+        .bind(enum_to_text(&job_b_type)?)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let jobs = rows.iter().map(row_to_job).collect::<Result<Vec<JobItem>>>()?;
+        tracing::debug!(job_count = jobs.len(), category = "db_call", "Retrieved jobs without successor");
+        record_db_call("get_jobs_without_successor", start);
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_latest_job_by_type_and_status(
+        &self,
+        job_type: JobType,
+        job_status: JobStatus,
+    ) -> Result<Option<JobItem>> {
+        let start = Instant::now();
+        let row = sqlx::query(&format!(
+            "SELECT {JOB_COLUMNS} FROM jobs WHERE job_type = $1 AND status = $2 ORDER BY block_number DESC NULLS LAST \
+             LIMIT 1"
+        ))
+        .bind(enum_to_text(&job_type)?)
+        .bind(enum_to_text(&job_status)?)
+        .fetch_optional(&self.pool)
+        .await?;
+        tracing::debug!(job_type = ?job_type, job_status = ?job_status, category = "db_call", "Fetched latest job by type and status");
+        record_db_call("get_latest_job_by_type_and_status", start);
+        row.as_ref().map(row_to_job).transpose()
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_after_internal_id_by_job_type(
+        &self,
+        job_type: JobType,
+        job_status: JobStatus,
+        internal_id: String,
+    ) -> Result<Vec<JobItem>> {
+        let start = Instant::now();
+        let internal_id_number: i64 =
+            internal_id.parse().wrap_err(format!("Internal ID {} is not a number", internal_id))?;
+        let rows = sqlx::query(&format!(
+            "SELECT {JOB_COLUMNS} FROM jobs WHERE job_type = $1 AND status = $2 AND block_number > $3 ORDER BY seq"
+        ))
+        .bind(enum_to_text(&job_type)?)
+        .bind(enum_to_text(&job_status)?)
+        .bind(internal_id_number)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let jobs = rows.iter().map(row_to_job).collect::<Result<Vec<JobItem>>>()?;
+        tracing::debug!(job_type = ?job_type, job_status = ?job_status, internal_id = internal_id, category = "db_call", "Fetched jobs after internal ID by job type");
+        record_db_call("get_jobs_after_internal_id_by_job_type", start);
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self, limit), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_by_statuses(&self, job_status: Vec<JobStatus>, limit: Option<i64>) -> Result<Vec<JobItem>> {
+        let start = Instant::now();
+        let statuses = job_status.iter().map(enum_to_text).collect::<Result<Vec<String>>>()?;
+        // a NULL limit returns all the rows
+        let rows = sqlx::query(&format!("SELECT {JOB_COLUMNS} FROM jobs WHERE status = ANY($1) ORDER BY seq LIMIT $2"))
+            .bind(statuses)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let jobs = rows.iter().map(row_to_job).collect::<Result<Vec<JobItem>>>()?;
+        tracing::debug!(job_count = jobs.len(), category = "db_call", "Retrieved jobs by statuses");
+        record_db_call("get_jobs_by_statuses", start);
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs(&self, filter: JobFilter, cursor: Option<JobCursor>, limit: i64) -> Result<Vec<JobItem>> {
+        let start = Instant::now();
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(format!("SELECT {JOB_COLUMNS} FROM jobs WHERE TRUE"));
+        push_job_filter(&mut query, &filter)?;
+        if let Some(cursor) = cursor {
+            query
+                .push(" AND (created_at, id) > (")
+                .push_bind(cursor.created_at)
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+        query.push(" ORDER BY created_at, id LIMIT ").push_bind(limit);
+
+        let rows = query.build().fetch_all(&self.pool).await?;
+        let jobs = rows.iter().map(row_to_job).collect::<Result<Vec<JobItem>>>()?;
+        tracing::debug!(job_count = jobs.len(), category = "db_call", "Listed jobs");
+        record_db_call("get_jobs", start);
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs_by_block_range(&self, from_block: u64, to_block: u64) -> Result<Vec<JobItem>> {
+        let start = Instant::now();
+        let block_job_types = [JobType::SnosRun, JobType::ProofCreation, JobType::DataSubmission]
+            .iter()
+            .map(enum_to_text)
+            .collect::<Result<Vec<String>>>()?;
+        // state transition and proof registration jobs cover several blocks, listed in their metadata
+        let rows = sqlx::query(&format!(
+            "SELECT {JOB_COLUMNS} FROM jobs WHERE (job_type = ANY($1) AND block_number BETWEEN $2 AND $3) OR \
+             (job_type = $4 AND EXISTS (SELECT 1 FROM \
+             jsonb_array_elements_text(metadata->'specific'->'blocks_to_settle') AS block WHERE block::NUMERIC \
+             BETWEEN $2 AND $3)) OR (job_type = $5 AND (metadata->'specific'->>'from_block')::NUMERIC <= $3 AND \
+             (metadata->'specific'->>'to_block')::NUMERIC >= $2) ORDER BY created_at, id"
+        ))
+        .bind(block_job_types)
+        .bind(from_block as i64)
+        .bind(to_block as i64)
+        .bind(enum_to_text(&JobType::StateTransition)?)
+        .bind(enum_to_text(&JobType::ProofRegistration)?)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let jobs = rows.iter().map(row_to_job).collect::<Result<Vec<JobItem>>>()?;
+        tracing::debug!(
+            job_count = jobs.len(),
+            from_block,
+            to_block,
+            category = "db_call",
+            "Fetched jobs by block range"
+        );
+        record_db_call("get_jobs_by_block_range", start);
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_first_block_of_jobs(&self, filter: JobFilter) -> Result<Option<u64>> {
+        let start = Instant::now();
+        let mut query: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT MIN(block_number) AS first_block FROM jobs WHERE TRUE");
+        push_job_filter(&mut query, &filter)?;
+
+        let first_block: Option<i64> = query.build().fetch_one(&self.pool).await?.try_get("first_block")?;
+        tracing::debug!(first_block = ?first_block, category = "db_call", "Fetched the first block of the jobs");
+        record_db_call("get_first_block_of_jobs", start);
+        Ok(first_block.map(|block| block as u64))
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn count_jobs_by_type_and_status(&self) -> Result<Vec<(JobType, JobStatus, u64)>> {
+        let start = Instant::now();
+        let rows = sqlx::query("SELECT job_type, status, COUNT(*) AS count FROM jobs GROUP BY job_type, status")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let counts = rows
+            .iter()
+            .map(|row| {
+                let count: i64 = row.try_get("count")?;
+                Ok((text_to_enum(row.try_get("job_type")?)?, text_to_enum(row.try_get("status")?)?, count as u64))
+            })
+            .collect::<Result<Vec<(JobType, JobStatus, u64)>>>()?;
+        tracing::debug!(count_groups = counts.len(), category = "db_call", "Counted jobs by type and status");
+        record_db_call("count_jobs_by_type_and_status", start);
+        Ok(counts)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn acquire_lease(&self, name: &str, max_holders: usize, holder: Uuid, ttl: Duration) -> Result<bool> {
+        let start = Instant::now();
+        if self.renew_lease(name, holder, ttl).await? {
+            return Ok(true);
+        }
+
+        let mut acquired = false;
+        for slot in 0..max_holders {
+            // the conflicting slot is only taken over if its lease expired, the row lock taken by
+            // the upsert makes this atomic
+            let result = sqlx::query(
+                "INSERT INTO leases (name, slot, holder, expires_at) VALUES ($1, $2, $3, now() + make_interval(secs \
+                 => $4)) ON CONFLICT (name, slot) DO UPDATE SET holder = EXCLUDED.holder, expires_at = \
+                 EXCLUDED.expires_at WHERE leases.expires_at <= now()",
+            )
+            .bind(name)
+            .bind(slot as i32)
+            .bind(holder)
+            .bind(ttl.as_secs_f64())
+            .execute(&self.pool)
+            .await?;
+
+            if result.rows_affected() == 1 {
+                acquired = true;
+                break;
+            }
+        }
+
+        tracing::debug!(lease = %name, holder = %holder, acquired, category = "db_call", "Tried to acquire lease");
+        record_db_call("acquire_lease", start);
+        Ok(acquired)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn renew_lease(&self, name: &str, holder: Uuid, ttl: Duration) -> Result<bool> {
+        let start = Instant::now();
+        let result = sqlx::query(
+            "UPDATE leases SET expires_at = now() + make_interval(secs => $3) WHERE name = $1 AND holder = $2 AND \
+             expires_at > now()",
+        )
+        .bind(name)
+        .bind(holder)
+        .bind(ttl.as_secs_f64())
+        .execute(&self.pool)
+        .await?;
+
+        record_db_call("renew_lease", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn release_lease(&self, name: &str, holder: Uuid) -> Result<()> {
+        let start = Instant::now();
+        sqlx::query("DELETE FROM leases WHERE name = $1 AND holder = $2")
+            .bind(name)
+            .bind(holder)
+            .execute(&self.pool)
+            .await?;
+
+        tracing::debug!(lease = %name, holder = %holder, category = "db_call", "Released lease");
+        record_db_call("release_lease", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_lease_holders(&self, name: &str) -> Result<Vec<Uuid>> {
+        let start = Instant::now();
+        let rows = sqlx::query("SELECT holder FROM leases WHERE name = $1 AND expires_at > now() ORDER BY slot")
+            .bind(name)
+            .fetch_all(&self.pool)
+            .await?;
+
+        record_db_call("get_lease_holders", start);
+        rows.iter().map(|row| Ok(row.try_get("holder")?)).collect()
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn acquire_leadership(&self, name: &str, candidate: Uuid, ttl: Duration) -> Result<Option<u64>> {
+        let start = Instant::now();
+        // the leader keeps its fencing token, a new leader takes the next one. Nothing is returned
+        // if another candidate leads
+        let row = sqlx::query(
+            "INSERT INTO leaders (name, holder, fencing_token, expires_at) VALUES ($1, $2, 1, now() + \
+             make_interval(secs => $3)) ON CONFLICT (name) DO UPDATE SET fencing_token = CASE WHEN leaders.holder = \
+             EXCLUDED.holder AND leaders.expires_at > now() THEN leaders.fencing_token ELSE leaders.fencing_token + 1 \
+             END, holder = EXCLUDED.holder, expires_at = EXCLUDED.expires_at WHERE leaders.expires_at <= now() OR \
+             leaders.holder = EXCLUDED.holder RETURNING fencing_token",
+        )
+        .bind(name)
+        .bind(candidate)
+        .bind(ttl.as_secs_f64())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let fencing_token =
+            row.map(|row| row.try_get::<i64, _>("fencing_token")).transpose()?.map(|token| token as u64);
+        tracing::debug!(election = %name, candidate = %candidate, ?fencing_token, category = "db_call", "Tried to acquire leadership");
+        record_db_call("acquire_leadership", start);
+        Ok(fencing_token)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn renew_leadership(&self, name: &str, candidate: Uuid, fencing_token: u64, ttl: Duration) -> Result<bool> {
+        let start = Instant::now();
+        let result = sqlx::query(
+            "UPDATE leaders SET expires_at = now() + make_interval(secs => $4) WHERE name = $1 AND holder = $2 AND \
+             fencing_token = $3 AND expires_at > now()",
+        )
+        .bind(name)
+        .bind(candidate)
+        .bind(fencing_token as i64)
+        .bind(ttl.as_secs_f64())
+        .execute(&self.pool)
+        .await?;
+
+        record_db_call("renew_leadership", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn release_leadership(&self, name: &str, candidate: Uuid, fencing_token: u64) -> Result<()> {
+        let start = Instant::now();
+        // the row is kept so the next leader gets the next fencing token
+        sqlx::query("UPDATE leaders SET expires_at = now() WHERE name = $1 AND holder = $2 AND fencing_token = $3")
+            .bind(name)
+            .bind(candidate)
+            .bind(fencing_token as i64)
+            .execute(&self.pool)
+            .await?;
+
+        tracing::debug!(election = %name, candidate = %candidate, fencing_token, category = "db_call", "Released leadership");
+        record_db_call("release_leadership", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn create_dead_letter_audit_entry(&self, entry: DeadLetterAuditEntry) -> Result<()> {
+        let start = Instant::now();
+        sqlx::query(
+            "INSERT INTO dead_letter_audit_log (id, job_id, action, actor, source_queue, created_at) VALUES ($1, $2, \
+             $3, $4, $5, $6)",
+        )
+        .bind(entry.id)
+        .bind(entry.job_id)
+        .bind(enum_to_text(&entry.action)?)
+        .bind(&entry.actor)
+        .bind(enum_to_text(&entry.source_queue)?)
+        .bind(entry.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        record_db_call("create_dead_letter_audit_entry", start);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_dead_letter_audit_entries(&self, limit: i64) -> Result<Vec<DeadLetterAuditEntry>> {
+        let start = Instant::now();
+        let rows = sqlx::query(
+            "SELECT id, job_id, action, actor, source_queue, created_at FROM dead_letter_audit_log ORDER BY \
+             created_at DESC, id DESC LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        record_db_call("get_dead_letter_audit_entries", start);
+        rows.iter().map(row_to_dead_letter_audit_entry).collect()
+    }
+}
@@ -1,8 +1,8 @@
 use std::collections::HashSet;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use tokio::sync::{Mutex, Semaphore, SemaphorePermit};
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 use crate::config::Config;
@@ -10,57 +10,142 @@ use crate::jobs::types::JobItem;
 use crate::jobs::JobError;
 use crate::queue::job_queue::add_job_to_process_queue;
 
+/// Name of the lease semaphore limiting the number of SNOS jobs processed at once.
+pub const SNOS_JOB_PROCESSING_LOCK: &str = "snos_job_processing";
+
+/// How long a job processing lease lasts without being renewed, if not configured.
+pub const DEFAULT_JOB_PROCESSING_LEASE_TTL: Duration = Duration::from_secs(60);
+
+/// How long to wait for a permit before putting the job back in the process queue.
+const LEASE_ACQUIRE_TIMEOUT: Duration = Duration::from_millis(100);
+const LEASE_ACQUIRE_RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
 pub struct ProcessingLocks {
     pub snos_job_processing_lock: Arc<JobProcessingState>,
 }
 
+/// A semaphore limiting the number of jobs processed at once by all the orchestrator replicas
+/// sharing the database, usable by any job type through `Job::job_processing_lock`.
+///
+/// A permit is a lease stored in the database, renewed in the background while its job is
+/// processed. The permits of a replica which crashed are freed once their leases expire.
 pub struct JobProcessingState {
-    pub semaphore: Semaphore,
-    pub active_jobs: Mutex<HashSet<Uuid>>,
+    /// Name of the lease semaphore in the database, shared by the replicas
+    pub name: String,
+    pub max_parallel_jobs: usize,
+    /// How long a lease lasts without being renewed
+    pub lease_ttl: Duration,
 }
+
 impl JobProcessingState {
-    pub fn new(max_parallel_jobs: usize) -> Self {
-        JobProcessingState { semaphore: Semaphore::new(max_parallel_jobs), active_jobs: Mutex::new(HashSet::new()) }
+    pub fn new(name: impl Into<String>, max_parallel_jobs: usize, lease_ttl: Duration) -> Self {
+        JobProcessingState { name: name.into(), max_parallel_jobs, lease_ttl }
     }
 
-    pub async fn get_active_jobs(&self) -> HashSet<Uuid> {
-        self.active_jobs.lock().await.clone()
+    /// Jobs holding a permit, on any replica.
+    pub async fn get_active_jobs(&self, config: &Config) -> Result<HashSet<Uuid>, JobError> {
+        let holders =
+            config.database().get_lease_holders(&self.name).await.map_err(|e| JobError::LockError(e.to_string()))?;
+        Ok(holders.into_iter().collect())
     }
 
-    pub fn get_available_permits(&self) -> usize {
-        self.semaphore.available_permits()
+    pub async fn get_available_permits(&self, config: &Config) -> Result<usize, JobError> {
+        Ok(self.max_parallel_jobs.saturating_sub(self.get_active_jobs(config).await?.len()))
     }
 
-    pub async fn try_acquire_lock<'a>(
-        &'a self,
-        job: &JobItem,
-        config: Arc<Config>,
-    ) -> Result<SemaphorePermit<'a>, JobError> {
+    pub async fn try_acquire_lock(&self, job: &JobItem, config: Arc<Config>) -> Result<JobLease, JobError> {
         // Trying to acquire permit with a timeout.
-        match tokio::time::timeout(Duration::from_millis(100), self.semaphore.acquire()).await {
-            Ok(Ok(permit)) => {
-                {
-                    let mut active_jobs = self.active_jobs.lock().await;
-                    active_jobs.insert(job.id);
-                    drop(active_jobs);
-                }
-                tracing::info!(job_id = %job.id, "Job {} acquired lock", job.id);
-                Ok(permit)
+        let deadline = Instant::now() + LEASE_ACQUIRE_TIMEOUT;
+        loop {
+            let acquired = config
+                .database()
+                .acquire_lease(&self.name, self.max_parallel_jobs, job.id, self.lease_ttl)
+                .await
+                .map_err(|e| JobError::LockError(e.to_string()))?;
+            if acquired {
+                tracing::info!(job_id = %job.id, lock = %self.name, "Job {} acquired lock", job.id);
+                return Ok(JobLease::new(self.name.clone(), job.id, self.lease_ttl, config));
             }
-            Err(_) => {
-                tracing::error!(job_id = %job.id, "Job {} waiting - at max capacity ({} available permits)", job.id, self.get_available_permits());
-                add_job_to_process_queue(job.id, &job.job_type, config.clone()).await?;
-                Err(JobError::MaxCapacityReached)
+            if Instant::now() >= deadline {
+                break;
             }
-            Ok(Err(e)) => Err(JobError::LockError(e.to_string())),
+            tokio::time::sleep(LEASE_ACQUIRE_RETRY_INTERVAL).await;
+        }
+
+        tracing::error!(job_id = %job.id, lock = %self.name, "Job {} waiting - at max capacity ({} parallel jobs)", job.id, self.max_parallel_jobs);
+        // the job is still waiting for processing, not stuck
+        if let Err(e) = config.database().heartbeat_job(job.id, job.status.clone()).await {
+            tracing::warn!(job_id = %job.id, error = ?e, "Failed to refresh the update time of the job");
         }
+        add_job_to_process_queue(job.id, &job.job_type, config.clone()).await?;
+        Err(JobError::MaxCapacityReached)
+    }
+
+    pub async fn try_release_lock(&self, lease: JobLease) -> Result<(), JobError> {
+        lease.release().await
+    }
+}
+
+/// A permit of a `JobProcessingState`, renewed in the background until released.
+///
+/// If it's dropped without being released, e.g. because the processing failed, it's released in
+/// the background.
+pub struct JobLease {
+    name: String,
+    holder: Uuid,
+    config: Arc<Config>,
+    heartbeat: JoinHandle<()>,
+    released: bool,
+}
+
+impl JobLease {
+    fn new(name: String, holder: Uuid, ttl: Duration, config: Arc<Config>) -> Self {
+        let heartbeat = tokio::spawn(renew_lease_periodically(name.clone(), holder, ttl, config.clone()));
+        Self { name, holder, config, heartbeat, released: false }
     }
 
-    pub async fn try_release_lock<'a>(&'a self, permit: SemaphorePermit<'a>, job_id: &Uuid) -> Result<(), JobError> {
-        let mut active_jobs = self.active_jobs.lock().await;
-        active_jobs.remove(job_id);
-        drop(active_jobs); // Explicitly drop the lock (optional but clear)
-        drop(permit); // Explicitly drop the permit (optional but clear)
+    pub async fn release(mut self) -> Result<(), JobError> {
+        self.heartbeat.abort();
+        self.released = true;
+        self.config
+            .database()
+            .release_lease(&self.name, self.holder)
+            .await
+            .map_err(|e| JobError::LockError(e.to_string()))?;
+        tracing::info!(job_id = %self.holder, lock = %self.name, "Job {} released lock", self.holder);
         Ok(())
     }
 }
+
+impl Drop for JobLease {
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+        if self.released {
+            return;
+        }
+        let (name, holder, config) = (self.name.clone(), self.holder, self.config.clone());
+        tokio::spawn(async move {
+            if let Err(e) = config.database().release_lease(&name, holder).await {
+                // the lease is freed anyway once it expires
+                tracing::warn!(job_id = %holder, lock = %name, error = ?e, "Failed to release lock");
+            }
+        });
+    }
+}
+
+/// Renews the lease three times per TTL, so that a single missed renewal doesn't lose it.
+async fn renew_lease_periodically(name: String, holder: Uuid, ttl: Duration, config: Arc<Config>) {
+    loop {
+        tokio::time::sleep(ttl / 3).await;
+        match config.database().renew_lease(&name, holder, ttl).await {
+            Ok(true) => {}
+            Ok(false) => {
+                tracing::error!(job_id = %holder, lock = %name, "Job lost its lock, its lease expired before being renewed");
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(job_id = %holder, lock = %name, error = ?e, "Failed to renew lock");
+            }
+        }
+    }
+}
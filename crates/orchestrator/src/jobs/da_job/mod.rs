@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::ops::{Add, Mul, Rem};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use chrono::{SubsecRound, Utc};
@@ -21,6 +22,7 @@ use super::{Job, JobError, OtherError};
 use crate::config::Config;
 use crate::helpers;
 use crate::jobs::metadata::{DaMetadata, JobMetadata, JobSpecificMetadata};
+use crate::jobs::retry_policy::RetryPolicy;
 use crate::jobs::state_update_job::utils::biguint_vec_to_u8_vec;
 
 lazy_static! {
@@ -119,7 +121,7 @@ impl Job for DaJob {
             .wrap_err("Failed to get state Update.".to_string())
             .map_err(|e| {
                 tracing::error!(job_id = ?job.id, error = ?e, "Failed to get state update");
-                JobError::Other(OtherError(e))
+                JobError::Transient(OtherError(e))
             })?;
 
         let state_update = match state_update {
@@ -244,8 +246,17 @@ impl Job for DaJob {
         3
     }
 
-    fn verification_polling_delay_seconds(&self) -> u64 {
-        60
+    fn process_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::fixed(Duration::from_secs(60))
+    }
+
+    fn verification_polling_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(300),
+            jitter: 0.1,
+        }
     }
 
     fn job_processing_lock(
@@ -3,6 +3,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::jobs::types::JobStatus;
+use crate::queue::QueueType;
+use crate::telemetry::propagation::TraceContext;
+
 /// Common metadata fields shared across all job types.
 ///
 /// # Field Management
@@ -35,4 +39,57 @@ pub struct CommonMetadata {
     pub verification_completed_at: Option<DateTime<Utc>>,
     /// Reason for job failure if any
     pub failure_reason: Option<String>,
+    /// When the job failed, timed out its verification or was marked stale, if it did
+    #[serde(default, with = "chrono::serde::ts_seconds_option")]
+    pub failed_at: Option<DateTime<Utc>>,
+    /// When the next processing or verification attempt of the job is scheduled, if any
+    #[serde(default, with = "chrono::serde::ts_seconds_option")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Actions taken by the stuck job reaper on the job, oldest first
+    #[serde(default)]
+    pub reaper_actions: Vec<ReaperAction>,
+    /// Trace context of the creation of the job, continued by the jobs of the next stage of the
+    /// same block
+    #[serde(default)]
+    pub trace_context: TraceContext,
+    /// The message of the job moved to the dead letter queue, until it's replayed or discarded
+    #[serde(default)]
+    pub dead_letter: Option<DeadLetter>,
+}
+
+/// An action taken by the stuck job reaper on a job which stayed too long in the same status.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReaperAction {
+    /// What was done with the job
+    pub kind: ReaperActionKind,
+    /// Status the job was stuck in
+    pub stuck_status: JobStatus,
+    /// When the action was taken
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub taken_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReaperActionKind {
+    /// The job was put back in its process queue
+    Reprocessed,
+    /// The job was put back in its verification queue
+    Reverified,
+    /// The job was marked as stale
+    MarkedStale,
+}
+
+/// A message of a job which was moved to the dead letter queue after being received too many
+/// times from its queue, see `queue::dead_letter`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeadLetter {
+    /// Queue the message was moved from, which it's replayed to
+    pub source_queue: QueueType,
+    /// Times the message was received from its queue before being moved
+    pub receive_count: i32,
+    /// Status the job was left in
+    pub job_status: JobStatus,
+    /// When the message was received from the dead letter queue
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub dead_lettered_at: DateTime<Utc>,
 }
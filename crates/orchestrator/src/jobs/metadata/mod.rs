@@ -6,6 +6,7 @@
 
 mod common;
 mod da;
+mod proof_registration;
 mod proving;
 mod snos;
 mod state_update;
@@ -15,6 +16,7 @@ use color_eyre::eyre;
 use color_eyre::eyre::eyre;
 pub use common::*;
 pub use da::*;
+pub use proof_registration::*;
 pub use proving::*;
 use serde::{Deserialize, Serialize};
 pub use snos::*;
@@ -35,6 +37,8 @@ pub enum JobSpecificMetadata {
     Proving(ProvingMetadata),
     /// Data availability job metadata
     Da(DaMetadata),
+    /// Proof registration job metadata
+    ProofRegistration(ProofRegistrationMetadata),
 }
 
 /// Macro to implement TryInto for JobSpecificMetadata variants
@@ -58,6 +62,7 @@ impl_try_into_metadata!(Snos, SnosMetadata);
 impl_try_into_metadata!(Proving, ProvingMetadata);
 impl_try_into_metadata!(Da, DaMetadata);
 impl_try_into_metadata!(StateUpdate, StateUpdateMetadata);
+impl_try_into_metadata!(ProofRegistration, ProofRegistrationMetadata);
 
 /// Complete job metadata containing both common and job-specific fields.
 ///
@@ -0,0 +1,27 @@
+//! Metadata for proof registration jobs.
+
+use serde::{Deserialize, Serialize};
+
+/// Metadata specific to proof registration jobs.
+///
+/// # Field Management
+/// - Worker-initialized fields: block range, proof task id, fact and proof path
+/// - Job-populated fields: tx_hash (during processing)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProofRegistrationMetadata {
+    // Worker-initialized fields
+    /// First block covered by the proof
+    pub from_block: u64,
+    /// Last block covered by the proof
+    pub to_block: u64,
+    /// External id of the proving task that generated the proof
+    pub proof_task_id: String,
+    /// Fact expected to be registered by the proof
+    pub fact: Option<String>,
+    /// Path to the proof in storage, downloaded by the proving job
+    pub proof_path: Option<String>,
+
+    // Job-populated fields
+    /// Transaction hash of the registration
+    pub tx_hash: Option<String>,
+}
@@ -1,11 +1,12 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::fmt;
 use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use color_eyre::eyre::{eyre, Context};
 use conversion::parse_string;
 use da_job::DaError;
@@ -14,20 +15,29 @@ use mockall::automock;
 use mockall_double::double;
 use opentelemetry::KeyValue;
 use proving_job::ProvingError;
+use register_proof_job::ProofRegistrationError;
 use snos_job::error::FactError;
 use snos_job::SnosError;
 use state_update_job::StateUpdateError;
 use types::{ExternalId, JobItemUpdates};
 use uuid::Uuid;
 
+use crate::alerts::types::Alert;
 use crate::config::Config;
+use crate::database::Fence;
 use crate::helpers::JobProcessingState;
 #[double]
 use crate::jobs::job_handler_factory::factory;
 use crate::jobs::metadata::JobMetadata;
+use crate::jobs::retry_policy::RetryPolicy;
 use crate::jobs::types::{JobItem, JobStatus, JobType, JobVerificationStatus};
 use crate::metrics::ORCHESTRATOR_METRICS;
-use crate::queue::job_queue::{add_job_to_process_queue, add_job_to_verification_queue, ConsumptionError};
+use crate::queue::dead_letter::dead_letter_of;
+use crate::queue::job_queue::{
+    add_job_to_process_queue, add_job_to_process_queue_with_delay, add_job_to_verification_queue, ConsumptionError,
+};
+use crate::queue::DeadLetterSource;
+use crate::telemetry::propagation::current_trace_context;
 
 pub mod conversion;
 pub mod da_job;
@@ -35,6 +45,7 @@ pub mod job_handler_factory;
 pub mod metadata;
 pub mod proving_job;
 pub mod register_proof_job;
+pub mod retry_policy;
 pub mod snos_job;
 pub mod state_update_job;
 pub mod types;
@@ -59,6 +70,14 @@ pub enum JobError {
     #[error("Failed to find job with id {id:?}")]
     JobNotFound { id: Uuid },
 
+    /// Indicates the job has no message in the dead letter queue to replay or discard
+    #[error("Job with id {id:?} isn't in the dead letter queue")]
+    NotDeadLettered { id: Uuid },
+
+    /// Indicates a write made under a leadership which was taken over since, see `Fence`
+    #[error("The leadership of {election} with fencing token {fencing_token} was taken over, rejecting the write")]
+    StaleFence { election: String, fencing_token: u64 },
+
     /// Indicates a metadata counter would overflow if incremented
     #[error("Incrementing key {} in metadata would exceed u64::MAX", key)]
     KeyOutOfBounds { key: String },
@@ -75,6 +94,10 @@ pub enum JobError {
     #[error("Proving Error: {0}")]
     StateUpdateJobError(#[from] StateUpdateError),
 
+    /// Wraps errors from proof registration operations
+    #[error("Proof Registration Error: {0}")]
+    ProofRegistrationJobError(#[from] ProofRegistrationError),
+
     /// Wraps errors from SNOS operations
     #[error("Snos Error: {0}")]
     SnosJobError(#[from] SnosError),
@@ -98,6 +121,40 @@ pub enum JobError {
     /// Indicates an error occurred while extracting the processing lock
     #[error("Error extracting processing lock: {0}")]
     LockError(String),
+
+    /// Wraps errors which are likely to go away if the operation is retried, e.g. a database or
+    /// RPC call which timed out
+    #[error("Transient error: {0}")]
+    Transient(OtherError),
+}
+
+impl JobError {
+    /// Whether retrying the operation later may succeed. The messages of the jobs failing with a
+    /// transient error are put back in their queue, up to the transient retry limit of the queue,
+    /// the other errors are alerted on.
+    ///
+    /// Failing to read from or write to the storage is transient, the job handlers only write
+    /// files they would write the same way again.
+    ///
+    /// The processing lock errors aren't, the job messages failing with them are put back in their
+    /// queue without using up their transient retries, see `handle_job_message`.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            JobError::Transient(_)
+                | JobError::SnosJobError(
+                    SnosError::CairoPieUnstorable { .. }
+                        | SnosError::SnosOutputUnstorable { .. }
+                        | SnosError::ProgramOutputUnstorable { .. }
+                )
+                | JobError::ProvingJobError(
+                    ProvingError::CairoPIEFileFetchFailed(_)
+                        | ProvingError::ProofDownloadFailed(_)
+                        | ProvingError::ProofStorageFailed(_)
+                )
+                | JobError::ProofRegistrationJobError(ProofRegistrationError::ProofFetchFailed(_))
+        )
+    }
 }
 
 /// Wrapper Type for Other(<>) job type
@@ -191,16 +248,24 @@ pub trait Job: Send + Sync {
     async fn verify_job(&self, config: Arc<Config>, job: &mut JobItem) -> Result<JobVerificationStatus, JobError>;
 
     /// Should return the maximum number of attempts to process the job. A new attempt is made
-    /// every time the verification returns `JobVerificationStatus::Rejected`
+    /// every time the verification returns `JobVerificationStatus::Rejected`, after a delay
+    /// depending on `process_retry_policy`
     fn max_process_attempts(&self) -> u64;
 
     /// Should return the maximum number of attempts to verify the job. A new attempt is made
-    /// every few seconds depending on the result `verification_polling_delay_seconds`
+    /// after a delay depending on `verification_polling_policy`
     fn max_verification_attempts(&self) -> u64;
 
-    /// Should return the number of seconds to wait before polling for verification
-    fn verification_polling_delay_seconds(&self) -> u64;
+    /// Should return how long to wait before processing the job again after its verification was
+    /// rejected, out of the number of processing attempts made
+    fn process_retry_policy(&self) -> RetryPolicy;
+
+    /// Should return how long to wait before polling for verification, out of the number of
+    /// verification attempts made
+    fn verification_polling_policy(&self) -> RetryPolicy;
 
+    /// Should return the lease semaphore limiting how many jobs of this type are processed at once
+    /// by all the replicas, if any. See `JobProcessingState`.
     fn job_processing_lock(&self, config: Arc<Config>) -> Option<Arc<JobProcessingState>>;
 }
 
@@ -210,6 +275,7 @@ pub trait Job: Send + Sync {
 /// * `job_type` - Type of job to create
 /// * `internal_id` - Unique identifier for internal tracking
 /// * `metadata` - Additional key-value pairs for the job
+/// * `fence` - Leadership the job is created under, if any. See `Fence`
 /// * `config` - Shared configuration
 ///
 /// # Returns
@@ -228,6 +294,7 @@ pub async fn create_job(
     job_type: JobType,
     internal_id: String,
     metadata: JobMetadata,
+    fence: Option<&Fence>,
     config: Arc<Config>,
 ) -> Result<(), JobError> {
     let start = Instant::now();
@@ -259,8 +326,12 @@ pub async fn create_job(
     }
 
     let job_handler = factory::get_job_handler(&job_type).await;
-    let job_item = job_handler.create_job(config.clone(), internal_id.clone(), metadata).await?;
-    config.database().create_job(job_item.clone()).await?;
+    let mut job_item = job_handler.create_job(config.clone(), internal_id.clone(), metadata).await?;
+    job_item.metadata.common.trace_context = current_trace_context();
+    match fence {
+        Some(fence) => config.database().create_job_fenced(job_item.clone(), fence).await?,
+        None => config.database().create_job(job_item.clone()).await?,
+    };
     println!("Job item inside the create job function: {:?}", job_item);
     add_job_to_process_queue(job_item.id, &job_type, config.clone())
         .await
@@ -298,6 +369,8 @@ pub async fn create_job(
 /// * `Created` -> `LockedForProcessing` -> `PendingVerification`
 /// * `VerificationFailed` -> `LockedForProcessing` -> `PendingVerification`
 /// * `PendingRetry` -> `LockedForProcessing` -> `PendingVerification`
+/// * `LockedForProcessing` -> `PendingRetry` (on a transient processing error)
+/// * `LockedForProcessing` -> `Failed` (on any other processing error)
 ///
 /// # Metrics
 /// * Updates block gauge
@@ -343,7 +416,8 @@ pub async fn process_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError>
     let job_handler = factory::get_job_handler(&job.job_type).await;
     let job_processing_locks = job_handler.job_processing_lock(config.clone());
 
-    let permit = if let Some(ref processing_locks) = job_processing_locks {
+    // the lease is released in the background if the processing fails before the end
+    let lease = if let Some(ref processing_locks) = job_processing_locks {
         Some(processing_locks.try_acquire_lock(&job, config.clone()).await?)
     } else {
         None
@@ -354,6 +428,7 @@ pub async fn process_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError>
     // outdated
     tracing::debug!(job_id = ?id, "Updating job status to LockedForProcessing");
     job.metadata.common.process_started_at = Some(Utc::now());
+    job.metadata.common.next_attempt_at = None;
     let mut job = config
         .database()
         .update_job(
@@ -365,12 +440,21 @@ pub async fn process_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError>
         )
         .await
         .map_err(|e| {
+            // most likely another consumer locked the job first, retrying the message wouldn't help
             tracing::error!(job_id = ?id, error = ?e, "Failed to update job status");
             JobError::Other(OtherError(e))
         })?;
 
     tracing::debug!(job_id = ?id, job_type = ?job.job_type, "Getting job handler");
-    let external_id = match AssertUnwindSafe(job_handler.process_job(config.clone(), &mut job)).catch_unwind().await {
+    // the update time of the job is refreshed while it's processed, so that the stuck job reaper
+    // doesn't take a long processing for a crashed one
+    let keep_alive = keep_job_alive(id, job.job_type.clone(), config.clone());
+    let processing = AssertUnwindSafe(job_handler.process_job(config.clone(), &mut job)).catch_unwind();
+    let processed = tokio::select! {
+        processed = processing => processed,
+        never = keep_alive => match never {},
+    };
+    let external_id = match processed {
         Ok(Ok(external_id)) => {
             tracing::debug!(job_id = ?id, "Successfully processed job");
             // Add the time of processing to the metadata.
@@ -378,10 +462,20 @@ pub async fn process_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError>
 
             external_id
         }
+        Ok(Err(e)) if e.is_transient() => {
+            // the job is unlocked so that its message, put back in the queue, processes it again
+            tracing::warn!(job_id = ?id, error = ?e, "Transient error while processing job");
+            if let Err(update_error) = config
+                .database()
+                .update_job(&job, JobItemUpdates::new().update_status(JobStatus::PendingRetry).build())
+                .await
+            {
+                // the stuck job reaper unlocks it eventually
+                tracing::error!(job_id = ?id, error = ?update_error, "Failed to unlock job after a transient error");
+            }
+            return Err(e);
+        }
         Ok(Err(e)) => {
-            // TODO: I think most of the times the errors will not be fixed automatically
-            // if we just retry. But for some failures like DB issues, it might be possible
-            // that retrying will work. So we can add a retry logic here to improve robustness.
             tracing::error!(job_id = ?id, error = ?e, "Failed to process job");
             return move_job_to_failed(&job, config.clone(), format!("Processing failed: {}", e)).await;
         }
@@ -405,6 +499,10 @@ pub async fn process_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError>
     // Increment process attempt counter
     job.metadata.common.process_attempt_no += 1;
 
+    let verification_delay =
+        job_handler.verification_polling_policy().delay(job.metadata.common.verification_attempt_no);
+    job.metadata.common.next_attempt_at = Some(next_attempt_at(verification_delay));
+
     // Update job status and metadata
     tracing::debug!(job_id = ?id, "Updating job status to PendingVerification");
     config
@@ -424,15 +522,8 @@ pub async fn process_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError>
         })?;
 
     // Add to verification queue
-    tracing::debug!(job_id = ?id, "Adding job to verification queue");
-    add_job_to_verification_queue(
-        job.id,
-        &job.job_type,
-        Duration::from_secs(job_handler.verification_polling_delay_seconds()),
-        config.clone(),
-    )
-    .await
-    .map_err(|e| {
+    tracing::debug!(job_id = ?id, delay = ?verification_delay, "Adding job to verification queue");
+    add_job_to_verification_queue(job.id, &job.job_type, verification_delay, config.clone()).await.map_err(|e| {
         tracing::error!(job_id = ?id, error = ?e, "Failed to add job to verification queue");
         JobError::Other(OtherError(e))
     })?;
@@ -453,11 +544,17 @@ pub async fn process_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError>
     let duration = start.elapsed();
     ORCHESTRATOR_METRICS.successful_job_operations.add(1.0, &attributes);
     ORCHESTRATOR_METRICS.jobs_response_time.record(duration.as_secs_f64(), &attributes);
+    if let Some(process_completed_at) = job.metadata.common.process_completed_at {
+        ORCHESTRATOR_METRICS.job_time_to_process.record(
+            seconds_between(job.created_at, process_completed_at),
+            &[KeyValue::new("operation_job_type", format!("{:?}", job.job_type))],
+        );
+    }
     register_block_gauge(job.job_type, &job.internal_id, external_id.into(), &attributes)?;
 
-    if let Some(permit) = permit {
+    if let Some(lease) = lease {
         if let Some(ref processing_locks) = job_processing_locks {
-            processing_locks.try_release_lock(permit, &job.id).await?;
+            processing_locks.try_release_lock(lease).await?;
         }
     }
 
@@ -523,13 +620,14 @@ pub async fn verify_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
     tracing::debug!(job_id = ?id, "Verifying job with handler");
 
     job.metadata.common.verification_started_at = Some(Utc::now());
+    job.metadata.common.next_attempt_at = None;
     let mut job = config
         .database()
         .update_job(&job, JobItemUpdates::new().update_metadata(job.metadata.clone()).build())
         .await
         .map_err(|e| {
             tracing::error!(job_id = ?id, error = ?e, "Failed to update job status");
-            JobError::Other(OtherError(e))
+            JobError::Transient(OtherError(e))
         })?;
 
     let verification_status = job_handler.verify_job(config.clone(), &mut job).await?;
@@ -545,18 +643,24 @@ pub async fn verify_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
     match verification_status {
         JobVerificationStatus::Verified => {
             tracing::info!(job_id = ?id, "Job verified successfully");
-            // Calculate verification time if processing completion timestamp exists
+            let verification_completed_at = Utc::now();
+            let job_type_attributes = [KeyValue::new("operation_job_type", format!("{:?}", job.job_type))];
+            // Calculate verification time if verification start timestamp exists
             if let Some(verification_time) = job.metadata.common.verification_started_at {
-                let time_taken = (Utc::now() - verification_time).num_milliseconds();
-                ORCHESTRATOR_METRICS
-                    .verification_time
-                    .record(time_taken as f64, &[KeyValue::new("operation_job_type", format!("{:?}", job.job_type))]);
+                let time_taken = seconds_between(verification_time, verification_completed_at);
+                ORCHESTRATOR_METRICS.verification_time_seconds.record(time_taken, &job_type_attributes);
+                ORCHESTRATOR_METRICS.verification_time.record(time_taken * 1000.0, &job_type_attributes);
             } else {
-                tracing::warn!("Failed to calculate verification time: Missing processing completion timestamp");
+                tracing::warn!("Failed to calculate verification time: Missing verification start timestamp");
+            }
+            if let Some(process_completed_at) = job.metadata.common.process_completed_at {
+                ORCHESTRATOR_METRICS
+                    .job_time_to_verify
+                    .record(seconds_between(process_completed_at, verification_completed_at), &job_type_attributes);
             }
 
             // Update verification completed timestamp and update status
-            job.metadata.common.verification_completed_at = Some(Utc::now());
+            job.metadata.common.verification_completed_at = Some(verification_completed_at);
             config
                 .database()
                 .update_job(
@@ -581,9 +685,14 @@ pub async fn verify_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
             operation_job_status = Some(JobStatus::VerificationFailed);
 
             if job.metadata.common.process_attempt_no < job_handler.max_process_attempts() {
+                // the first retry waits the base delay
+                let process_delay =
+                    job_handler.process_retry_policy().delay(job.metadata.common.process_attempt_no.saturating_sub(1));
+                job.metadata.common.next_attempt_at = Some(next_attempt_at(process_delay));
                 tracing::info!(
                     job_id = ?id,
                     attempt = job.metadata.common.process_attempt_no + 1,
+                    delay = ?process_delay,
                     "Verification failed. Retrying job processing"
                 );
 
@@ -601,7 +710,7 @@ pub async fn verify_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
                         tracing::error!(job_id = ?id, error = ?e, "Failed to update job status to VerificationFailed");
                         JobError::Other(OtherError(e))
                     })?;
-                add_job_to_process_queue(job.id, &job.job_type, config.clone())
+                add_job_to_process_queue_with_delay(job.id, &job.job_type, process_delay, config.clone())
                     .await
                     .map_err(|e| JobError::Other(OtherError(e)))?;
             } else {
@@ -622,9 +731,16 @@ pub async fn verify_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
 
             if job.metadata.common.verification_attempt_no >= job_handler.max_verification_attempts() {
                 tracing::warn!(job_id = ?id, "Max verification attempts reached. Marking job as timed out");
+                job.metadata.common.failed_at = Some(Utc::now());
                 config
                     .database()
-                    .update_job(&job, JobItemUpdates::new().update_status(JobStatus::VerificationTimeout).build())
+                    .update_job(
+                        &job,
+                        JobItemUpdates::new()
+                            .update_status(JobStatus::VerificationTimeout)
+                            .update_metadata(job.metadata.clone())
+                            .build(),
+                    )
                     .await
                     .map_err(|e| {
                         tracing::error!(job_id = ?id, error = ?e, "Failed to update job status to VerificationTimeout");
@@ -634,6 +750,9 @@ pub async fn verify_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
             } else {
                 // Increment verification attempts
                 job.metadata.common.verification_attempt_no += 1;
+                let verification_delay =
+                    job_handler.verification_polling_policy().delay(job.metadata.common.verification_attempt_no);
+                job.metadata.common.next_attempt_at = Some(next_attempt_at(verification_delay));
 
                 config
                     .database()
@@ -644,18 +763,13 @@ pub async fn verify_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
                         JobError::Other(OtherError(e))
                     })?;
 
-                tracing::debug!(job_id = ?id, "Adding job back to verification queue");
-                add_job_to_verification_queue(
-                    job.id,
-                    &job.job_type,
-                    Duration::from_secs(job_handler.verification_polling_delay_seconds()),
-                    config.clone(),
-                )
-                .await
-                .map_err(|e| {
-                    tracing::error!(job_id = ?id, error = ?e, "Failed to add job to verification queue");
-                    JobError::Other(OtherError(e))
-                })?;
+                tracing::debug!(job_id = ?id, delay = ?verification_delay, "Adding job back to verification queue");
+                add_job_to_verification_queue(job.id, &job.job_type, verification_delay, config.clone())
+                    .await
+                    .map_err(|e| {
+                        tracing::error!(job_id = ?id, error = ?e, "Failed to add job to verification queue");
+                        JobError::Other(OtherError(e))
+                    })?;
             }
         }
     };
@@ -702,7 +816,7 @@ pub async fn retry_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
         "General retry job started for block"
     );
 
-    if job.status != JobStatus::Failed {
+    if !matches!(job.status, JobStatus::Failed | JobStatus::Stale) {
         tracing::error!(
             job_id = ?id,
             status = ?job.status,
@@ -765,6 +879,7 @@ pub async fn retry_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
 ///
 /// # Arguments
 /// * `id` - UUID of the job to handle failure for
+/// * `source` - What the broker tells about the dead lettered message
 /// * `config` - Shared configuration
 ///
 /// # Returns
@@ -773,10 +888,12 @@ pub async fn retry_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
 /// # Notes
 /// * Logs error if the job status `Completed` is existing on DL queue
 /// * Updates job status to Failed and records failure reason in metadata
+/// * Records the dead letter in metadata, for it to be replayed or discarded, also for jobs already
+///   failed
 /// * Updates metrics for failed jobs
 #[tracing::instrument(skip(config), fields(job_status, job_type), ret, err)]
-pub async fn handle_job_failure(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
-    let job = get_job(id, config.clone()).await?.clone();
+pub async fn handle_job_failure(id: Uuid, source: DeadLetterSource, config: Arc<Config>) -> Result<(), JobError> {
+    let mut job = get_job(id, config.clone()).await?.clone();
     let internal_id = job.internal_id.clone();
     tracing::info!(log_type = "starting", category = "general", function_type = "handle_job_failure", block_no = %internal_id, "General handle job failure started for block");
 
@@ -785,10 +902,57 @@ pub async fn handle_job_failure(id: Uuid, config: Arc<Config>) -> Result<(), Job
 
     tracing::debug!(job_id = ?id, job_status = ?job.status, job_type = ?job.job_type, block_no = %internal_id, "Job details for failure handling for block");
     let status = job.status.clone().to_string();
+    if job.status == JobStatus::Failed {
+        // the job failed before its message was dead lettered, its dead letter is recorded all the
+        // same for it to be replayed or discarded
+        if job.metadata.common.dead_letter.is_none() {
+            job.metadata.common.dead_letter = Some(dead_letter_of(&job, source));
+            config
+                .database()
+                .update_job(&job, JobItemUpdates::new().update_metadata(job.metadata.clone()).build())
+                .await?;
+        }
+        tracing::warn!(job_id = ?id, "Job already marked as failed, skipping processing");
+        return Ok(());
+    }
+    // kept until the dead letter is replayed or discarded
+    job.metadata.common.dead_letter = Some(dead_letter_of(&job, source));
     move_job_to_failed(&job, config.clone(), format!("Received failure queue message for job with status: {}", status))
         .await
 }
 
+/// Fails a job whose processing message ran out of transient retries, as nothing would process
+/// it again otherwise.
+///
+/// # Arguments
+/// * `id` - UUID of the job whose processing kept failing
+/// * `error` - The last transient error
+/// * `config` - Shared configuration
+///
+/// # Returns
+/// * `Result<bool, JobError>` - Whether the job was failed, and alerted on, or an error
+///
+/// # Notes
+/// * Jobs which aren't waiting to be processed are left as they are, e.g. a job locked by another
+///   replica
+pub async fn fail_job_after_transient_retries(
+    id: Uuid,
+    error: &JobError,
+    config: Arc<Config>,
+) -> Result<bool, JobError> {
+    let job = get_job(id, config.clone()).await?;
+    if !matches!(job.status, JobStatus::Created | JobStatus::VerificationFailed | JobStatus::PendingRetry) {
+        return Ok(false);
+    }
+    move_job_to_failed(&job, config, format!("Processing failed after its transient retries: {}", error)).await?;
+    Ok(true)
+}
+
+/// Seconds elapsed from `from` to `to`, with a millisecond precision.
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_milliseconds() as f64 / 1000.0
+}
+
 fn register_block_gauge(
     job_type: JobType,
     internal_id: &str,
@@ -839,8 +1003,10 @@ async fn move_job_to_failed(job: &JobItem, config: Arc<Config>, reason: String)
     let internal_id = job.internal_id.clone();
 
     tracing::debug!(job_id = ?job.id, "Updating job status to Failed in database");
+    let alert = Alert::job_failed(job, reason.clone());
     // Update failure information in common metadata
     job_metadata.common.failure_reason = Some(reason);
+    job_metadata.common.failed_at = Some(Utc::now());
 
     match config
         .database()
@@ -858,6 +1024,10 @@ async fn move_job_to_failed(job: &JobItem, config: Arc<Config>, reason: String)
             ORCHESTRATOR_METRICS
                 .failed_jobs
                 .add(1.0, &[KeyValue::new("operation_job_type", format!("{:?}", job.job_type))]);
+            // the job is failed either way, a lost alert shouldn't bring it back to the queue
+            if let Err(e) = config.alerts().send_alert(&alert).await {
+                tracing::error!(job_id = ?job.id, error = ?e, "Failed to send the failed job alert");
+            }
             Ok(())
         }
         Err(e) => {
@@ -883,13 +1053,31 @@ async fn move_job_to_failed(job: &JobItem, config: Arc<Config>, reason: String)
 /// # Returns
 /// * `Result<JobItem, JobError>` - The job if found, or JobNotFound error
 async fn get_job(id: Uuid, config: Arc<Config>) -> Result<JobItem, JobError> {
-    let job = config.database().get_job_by_id(id).await.map_err(|e| JobError::Other(OtherError(e)))?;
+    let job = config.database().get_job_by_id(id).await.map_err(|e| JobError::Transient(OtherError(e)))?;
     match job {
         Some(job) => Ok(job),
         None => Err(JobError::JobNotFound { id }),
     }
 }
 
+/// Refreshes the update time of the job `id` locked for processing three times per stuck job
+/// deadline of `job_type`. Never resolves.
+async fn keep_job_alive(id: Uuid, job_type: JobType, config: Arc<Config>) -> Infallible {
+    let period = config.service_config().stuck_job_reaper.deadline(&job_type) / 3;
+    loop {
+        tokio::time::sleep(period).await;
+        if let Err(e) = config.database().heartbeat_job(id, JobStatus::LockedForProcessing).await {
+            tracing::warn!(job_id = %id, error = ?e, "Failed to refresh the update time of the job");
+        }
+    }
+}
+
+/// When an attempt scheduled `delay` from now is due, as stored in
+/// `CommonMetadata::next_attempt_at`.
+fn next_attempt_at(delay: Duration) -> DateTime<Utc> {
+    Utc::now() + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::zero())
+}
+
 /// Increments a numeric value in the job metadata
 ///
 /// # Arguments
@@ -981,6 +1169,8 @@ pub async fn queue_job_for_verification(id: Uuid, config: Arc<Config>) -> Result
     // Reset verification attempts and increment retry counter in common metadata
     job.metadata.common.verification_attempt_no = 0;
     job.metadata.common.verification_retry_attempt_no += 1;
+    let verification_delay = job_handler.verification_polling_policy().delay(0);
+    job.metadata.common.next_attempt_at = Some(next_attempt_at(verification_delay));
 
     tracing::debug!(
         job_id = ?id,
@@ -1002,14 +1192,7 @@ pub async fn queue_job_for_verification(id: Uuid, config: Arc<Config>) -> Result
         .map_err(|e| JobError::Other(OtherError(e)))?;
 
     // Add to verification queue with appropriate delay
-    add_job_to_verification_queue(
-        id,
-        &job.job_type,
-        Duration::from_secs(job_handler.verification_polling_delay_seconds()),
-        config,
-    )
-    .await
-    .map_err(|e| {
+    add_job_to_verification_queue(id, &job.job_type, verification_delay, config).await.map_err(|e| {
         tracing::error!(
             job_id = ?id,
             error = ?e,
@@ -1099,4 +1282,16 @@ mod tests {
             assert_eq!(result, 0);
         }
     }
+
+    /// Tests for JobError::is_transient function
+    mod test_is_transient {
+        use super::*;
+
+        #[test]
+        /// Tests that the processing lock errors don't use up the transient retries
+        fn lock_errors_are_not_transient() {
+            assert!(!JobError::LockError("Failed to acquire the lease".to_string()).is_transient());
+            assert!(!JobError::MaxCapacityReached.is_transient());
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use cairo_vm::vm::runners::cairo_pie::CairoPie;
@@ -13,6 +14,7 @@ use super::{Job, JobError, OtherError};
 use crate::config::Config;
 use crate::helpers;
 use crate::jobs::metadata::{JobMetadata, ProvingInputType, ProvingMetadata};
+use crate::jobs::retry_policy::RetryPolicy;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ProvingError {
@@ -25,6 +27,15 @@ pub enum ProvingError {
     #[error("Not able to get the PIE file from AWS S3 bucket.")]
     CairoPIEFileFetchFailed(String),
 
+    #[error("Not able to download the proof from the prover: {0}")]
+    ProofDownloadFailed(String),
+
+    #[error("Not able to convert the proof to the settlement layer format: {0}")]
+    ProofConversionFailed(String),
+
+    #[error("Not able to store the proof: {0}")]
+    ProofStorageFailed(String),
+
     #[error("Other error: {0}")]
     Other(#[from] OtherError),
 }
@@ -105,6 +116,7 @@ impl Job for ProvingJob {
             .wrap_err("Prover Client Error".to_string())
             .map_err(|e| {
                 tracing::error!(job_id = %job.internal_id, error = %e, "Failed to submit task to prover client");
+                // not transient, the task may have been created anyway
                 JobError::Other(OtherError(e))
             })?;
 
@@ -163,7 +175,7 @@ impl Job for ProvingJob {
                     error = %e,
                     "Failed to get task status from prover client"
                 );
-                JobError::Other(OtherError(e))
+                JobError::Transient(OtherError(e))
             })?;
 
         match task_status {
@@ -186,7 +198,20 @@ impl Job for ProvingJob {
                         "Downloading and storing proof to path: {}",
                         download_path
                     );
-                    // TODO: Implement proof download and storage
+                    let proof = config.prover_client().get_proof(&task_id).await.map_err(|e| {
+                        tracing::error!(job_id = %job.internal_id, error = %e, "Failed to download proof");
+                        ProvingError::ProofDownloadFailed(e.to_string())
+                    })?;
+                    // the proof registration job registers the proof as stored, it has to be in the format
+                    // of the settlement layer
+                    let proof = config.settlement_client().convert_proof(&proof).map_err(|e| {
+                        tracing::error!(job_id = %job.internal_id, error = %e, "Failed to convert proof");
+                        ProvingError::ProofConversionFailed(e.to_string())
+                    })?;
+                    config.storage().put_data(proof.into(), &download_path).await.map_err(|e| {
+                        tracing::error!(job_id = %job.internal_id, error = %e, "Failed to store proof");
+                        ProvingError::ProofStorageFailed(e.to_string())
+                    })?;
                 }
 
                 tracing::info!(
@@ -224,8 +249,22 @@ impl Job for ProvingJob {
         300
     }
 
-    fn verification_polling_delay_seconds(&self) -> u64 {
-        30
+    fn process_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(600),
+            jitter: 0.2,
+        }
+    }
+
+    fn verification_polling_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_secs(30),
+            multiplier: 1.5,
+            max_delay: Duration::from_secs(120),
+            jitter: 0.2,
+        }
     }
 
     fn job_processing_lock(
@@ -1,17 +1,36 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use chrono::{SubsecRound, Utc};
 use color_eyre::Result;
+use settlement_client_interface::SettlementVerificationStatus;
+use thiserror::Error;
 use uuid::Uuid;
 
-use super::JobError;
+use super::{JobError, OtherError};
 use crate::config::Config;
 use crate::helpers;
-use crate::jobs::metadata::JobMetadata;
+use crate::jobs::metadata::{JobMetadata, JobSpecificMetadata, ProofRegistrationMetadata};
+use crate::jobs::retry_policy::RetryPolicy;
 use crate::jobs::types::{JobItem, JobStatus, JobType, JobVerificationStatus};
 use crate::jobs::Job;
 
+#[derive(Error, Debug, PartialEq)]
+pub enum ProofRegistrationError {
+    #[error("Proof path is not specified - proof registration job #{internal_id:?}")]
+    ProofPathNotFound { internal_id: String },
+
+    #[error("Not able to get the proof from storage: {0}")]
+    ProofFetchFailed(String),
+
+    #[error("Tx {tx_hash:?} should not be pending.")]
+    TxnShouldNotBePending { tx_hash: String },
+
+    #[error("Other error: {0}")]
+    Other(#[from] OtherError),
+}
+
 pub struct RegisterProofJob;
 
 #[async_trait]
@@ -41,33 +60,134 @@ impl Job for RegisterProofJob {
         Ok(job_item)
     }
 
-    #[tracing::instrument(fields(category = "proof_registry"), skip(self, _config), ret, err)]
-    async fn process_job(&self, _config: Arc<Config>, _job: &mut JobItem) -> Result<String, JobError> {
-        // Get proof from storage and submit on chain for verification
-        // We need to implement a generic trait for this to support multiple
-        // base layers
-        todo!()
+    #[tracing::instrument(fields(category = "proof_registry"), skip(self, config), ret, err)]
+    async fn process_job(&self, config: Arc<Config>, job: &mut JobItem) -> Result<String, JobError> {
+        let internal_id = job.internal_id.clone();
+        tracing::info!(
+            log_type = "starting",
+            category = "proof_registry",
+            function_type = "process_job",
+            job_id = ?job.id,
+            block_no = %internal_id,
+            "Proof registration job processing started."
+        );
+
+        let mut registration_metadata: ProofRegistrationMetadata =
+            job.metadata.specific.clone().try_into().map_err(|e| {
+                tracing::error!(job_id = %internal_id, error = %e, "Invalid metadata type for proof registration job");
+                JobError::Other(OtherError(e))
+            })?;
+
+        let proof_path = registration_metadata
+            .proof_path
+            .clone()
+            .ok_or_else(|| ProofRegistrationError::ProofPathNotFound { internal_id: internal_id.clone() })?;
+
+        tracing::debug!(job_id = %internal_id, %proof_path, "Fetching proof file");
+        let proof = config.storage().get_data(&proof_path).await.map_err(|e| {
+            tracing::error!(job_id = %internal_id, error = %e, "Failed to fetch proof file");
+            ProofRegistrationError::ProofFetchFailed(e.to_string())
+        })?;
+
+        tracing::debug!(
+            job_id = %internal_id,
+            from_block = %registration_metadata.from_block,
+            to_block = %registration_metadata.to_block,
+            fact = ?registration_metadata.fact,
+            "Registering proof on the settlement layer"
+        );
+
+        let tx_hash = config.settlement_client().register_proof(proof.to_vec()).await.map_err(|e| {
+            tracing::error!(job_id = %internal_id, error = %e, "Failed to register proof on the settlement layer");
+            // not transient, the transaction may have been sent anyway
+            JobError::Other(OtherError(e))
+        })?;
+
+        registration_metadata.tx_hash = Some(tx_hash.clone());
+        job.metadata.specific = JobSpecificMetadata::ProofRegistration(registration_metadata);
+
+        tracing::info!(
+            log_type = "completed",
+            category = "proof_registry",
+            function_type = "process_job",
+            job_id = ?job.id,
+            block_no = %internal_id,
+            %tx_hash,
+            "Proof registration job processed successfully."
+        );
+
+        Ok(tx_hash)
     }
 
-    #[tracing::instrument(fields(category = "proof_registry"), skip(self, _config), ret, err)]
-    async fn verify_job(&self, _config: Arc<Config>, job: &mut JobItem) -> Result<JobVerificationStatus, JobError> {
+    #[tracing::instrument(fields(category = "proof_registry"), skip(self, config), ret, err)]
+    async fn verify_job(&self, config: Arc<Config>, job: &mut JobItem) -> Result<JobVerificationStatus, JobError> {
         let internal_id = job.internal_id.clone();
         tracing::info!(log_type = "starting", category = "proof_registry", function_type = "verify_job", job_id = ?job.id,  block_no = %internal_id, "Proof registration job verification started.");
+
         // verify that the proof transaction has been included on chain
-        tracing::info!(log_type = "completed", category = "proof_registry", function_type = "verify_job", job_id = ?job.id,  block_no = %internal_id, "Proof registration job verification completed.");
-        todo!()
+        let tx_hash: String = job
+            .external_id
+            .unwrap_string()
+            .map_err(|e| {
+                tracing::error!(job_id = %internal_id, error = %e, "Failed to unwrap external_id");
+                JobError::Other(OtherError(e))
+            })?
+            .into();
+
+        let settlement_client = config.settlement_client();
+        let mut tx_inclusion_status =
+            settlement_client.verify_tx_inclusion(&tx_hash).await.map_err(|e| JobError::Transient(OtherError(e)))?;
+
+        // If the tx is still pending, we wait for it to be finalized and check again the status.
+        if let SettlementVerificationStatus::Pending = tx_inclusion_status {
+            tracing::debug!(job_id = %internal_id, %tx_hash, "Transaction pending, waiting for finality");
+            settlement_client.wait_for_tx_finality(&tx_hash).await.map_err(|e| JobError::Transient(OtherError(e)))?;
+            tx_inclusion_status = settlement_client
+                .verify_tx_inclusion(&tx_hash)
+                .await
+                .map_err(|e| JobError::Transient(OtherError(e)))?;
+        }
+
+        match tx_inclusion_status {
+            SettlementVerificationStatus::Verified => {
+                tracing::info!(log_type = "completed", category = "proof_registry", function_type = "verify_job", job_id = ?job.id,  block_no = %internal_id, "Proof registration job verification completed.");
+            }
+            SettlementVerificationStatus::Rejected(_) => {
+                tracing::warn!(log_type = "failed/rejected", category = "proof_registry", function_type = "verify_job", job_id = ?job.id,  block_no = %internal_id, %tx_hash, "Proof registration transaction rejected.");
+            }
+            SettlementVerificationStatus::Pending => {
+                tracing::error!(job_id = %internal_id, %tx_hash, "Transaction still pending after finality check");
+                Err(ProofRegistrationError::TxnShouldNotBePending { tx_hash: tx_hash.clone() })?
+            }
+        }
+
+        Ok(tx_inclusion_status.into())
     }
 
     fn max_process_attempts(&self) -> u64 {
-        todo!()
+        2
     }
 
     fn max_verification_attempts(&self) -> u64 {
-        todo!()
+        10
+    }
+
+    fn process_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(600),
+            jitter: 0.2,
+        }
     }
 
-    fn verification_polling_delay_seconds(&self) -> u64 {
-        todo!()
+    fn verification_polling_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_secs(60),
+            multiplier: 1.5,
+            max_delay: Duration::from_secs(300),
+            jitter: 0.1,
+        }
     }
 
     fn job_processing_lock(
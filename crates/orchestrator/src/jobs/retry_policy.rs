@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Longest delay a queue message can be scheduled with, SQS doesn't accept longer ones.
+pub const MAX_QUEUE_DELAY: Duration = Duration::from_secs(900);
+
+/// How long to wait before the next attempt of a job operation, e.g. polling the verification
+/// of a job or processing it again after its verification was rejected.
+///
+/// The delay grows exponentially with the number of attempts already made, up to `max_delay`,
+/// and is randomly spread by `jitter` so that jobs failing together aren't retried together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Delay before the first attempt
+    pub base_delay: Duration,
+    /// Factor applied to the delay after every attempt
+    pub multiplier: f64,
+    /// Upper bound of the delay, jitter included
+    pub max_delay: Duration,
+    /// Fraction of the delay randomly added or removed, between 0 and 1
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    /// A policy waiting `delay` before every attempt.
+    pub const fn fixed(delay: Duration) -> Self {
+        Self { base_delay: delay, multiplier: 1.0, max_delay: delay, jitter: 0.0 }
+    }
+
+    /// The delay before the next attempt once `attempt` attempts were made, without jitter.
+    pub fn backoff(&self, attempt: u64) -> Duration {
+        let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
+        let delay = self.base_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        // the delay overflows to infinity for large attempts, which the cap takes care of
+        Duration::from_secs_f64(delay.min(self.max_delay.as_secs_f64()))
+    }
+
+    /// The delay before the next attempt once `attempt` attempts were made.
+    pub fn delay(&self, attempt: u64) -> Duration {
+        self.delay_with_spread(attempt, rand::thread_rng().gen_range(-1.0..=1.0))
+    }
+
+    /// The delay with `spread`, between -1 and 1, of the jitter applied. It's never longer than
+    /// `MAX_QUEUE_DELAY`, as the next attempt is scheduled through the job queues.
+    fn delay_with_spread(&self, attempt: u64, spread: f64) -> Duration {
+        let delay = self.backoff(attempt).as_secs_f64() * (1.0 + self.jitter.clamp(0.0, 1.0) * spread);
+        let max_delay = self.max_delay.min(MAX_QUEUE_DELAY);
+        Duration::from_secs_f64(delay.clamp(0.0, max_delay.as_secs_f64()))
+    }
+}
+
+#[cfg(test)]
+mod retry_policy_test {
+    use rstest::rstest;
+
+    use super::*;
+
+    const POLICY: RetryPolicy = RetryPolicy {
+        base_delay: Duration::from_secs(10),
+        multiplier: 2.0,
+        max_delay: Duration::from_secs(100),
+        jitter: 0.5,
+    };
+
+    #[rstest]
+    #[case(0, 10)]
+    #[case(1, 20)]
+    #[case(3, 80)]
+    #[case(4, 100)]
+    #[case(u64::MAX, 100)]
+    fn test_backoff(#[case] attempt: u64, #[case] expected_secs: u64) {
+        assert_eq!(POLICY.backoff(attempt), Duration::from_secs(expected_secs));
+    }
+
+    #[rstest]
+    #[case(1, -1.0, 10)]
+    #[case(1, 1.0, 30)]
+    #[case(4, 1.0, 100)]
+    #[case(4, -1.0, 50)]
+    fn test_jitter(#[case] attempt: u64, #[case] spread: f64, #[case] expected_secs: u64) {
+        assert_eq!(POLICY.delay_with_spread(attempt, spread), Duration::from_secs(expected_secs));
+    }
+
+    #[rstest]
+    fn test_fixed() {
+        let policy = RetryPolicy::fixed(Duration::from_secs(60));
+        assert_eq!(policy.delay(0), Duration::from_secs(60));
+        assert_eq!(policy.delay(42), Duration::from_secs(60));
+    }
+}
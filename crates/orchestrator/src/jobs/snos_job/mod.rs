@@ -1,5 +1,6 @@
 use std::io::Read;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -9,7 +10,7 @@ use cairo_vm::Felt252;
 use chrono::{SubsecRound, Utc};
 use color_eyre::eyre::eyre;
 use color_eyre::Result;
-use prove_block::prove_block;
+use prove_block::{prove_block, ProveBlockError};
 use starknet_os::io::output::StarknetOsOutput;
 use tempfile::NamedTempFile;
 use thiserror::Error;
@@ -20,6 +21,7 @@ use crate::config::Config;
 use crate::data_storage::DataStorage;
 use crate::helpers;
 use crate::jobs::metadata::{JobMetadata, JobSpecificMetadata, SnosMetadata};
+use crate::jobs::retry_policy::RetryPolicy;
 use crate::jobs::snos_job::error::FactError;
 use crate::jobs::snos_job::fact_info::get_fact_info;
 use crate::jobs::types::{JobItem, JobStatus, JobType, JobVerificationStatus};
@@ -139,7 +141,15 @@ impl Job for SnosJob {
                 .await
                 .map_err(|e| {
                     tracing::error!(job_id = %job.internal_id, error = %e, "SNOS execution failed");
-                    SnosError::SnosExecutionError { internal_id: job.internal_id.clone(), message: e.to_string() }
+                    let execution_error =
+                        SnosError::SnosExecutionError { internal_id: job.internal_id.clone(), message: e.to_string() };
+                    match e {
+                        // the node serving the block may be unavailable for a while
+                        ProveBlockError::RpcError(_) => {
+                            JobError::Transient(OtherError::from(execution_error.to_string()))
+                        }
+                        _ => JobError::SnosJobError(execution_error),
+                    }
                 })?;
         tracing::debug!(job_id = %job.internal_id, "prove_block function completed successfully");
 
@@ -186,8 +196,12 @@ impl Job for SnosJob {
         1
     }
 
-    fn verification_polling_delay_seconds(&self) -> u64 {
-        1
+    fn process_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::fixed(Duration::from_secs(30))
+    }
+
+    fn verification_polling_policy(&self) -> RetryPolicy {
+        RetryPolicy::fixed(Duration::from_secs(1))
     }
 
     fn job_processing_lock(&self, config: Arc<Config>) -> std::option::Option<Arc<helpers::JobProcessingState>> {
@@ -1,6 +1,7 @@
 pub mod utils;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use ::utils::collections::{has_dup, is_sorted};
 use async_trait::async_trait;
@@ -16,8 +17,10 @@ use super::{JobError, OtherError};
 use crate::config::Config;
 use crate::helpers;
 use crate::jobs::metadata::{JobMetadata, JobSpecificMetadata, StateUpdateMetadata};
+use crate::jobs::retry_policy::RetryPolicy;
 use crate::jobs::state_update_job::utils::{
-    fetch_blob_data_for_block, fetch_program_output_for_block, fetch_snos_for_block,
+    compute_onchain_data_hash_and_size, fetch_blob_data_for_block, fetch_program_output_for_block,
+    fetch_snos_for_block, split_calldata_program_output,
 };
 use crate::jobs::types::{JobItem, JobStatus, JobType, JobVerificationStatus};
 use crate::jobs::Job;
@@ -158,16 +161,22 @@ impl Job for StateUpdateJob {
         let program_output_paths = state_metadata.program_output_paths.clone();
         let blob_data_paths = state_metadata.blob_data_paths.clone();
 
-        let mut nonce = config.settlement_client().get_nonce().await.map_err(|e| JobError::Other(OtherError(e)))?;
-
-        let mut sent_tx_hashes: Vec<String> = Vec::with_capacity(filtered_indices.len());
-
+        // fetched before the first transaction is sent, so that a transient storage error retries
+        // the whole job instead of leaving some blocks settled
+        let mut block_inputs = Vec::with_capacity(filtered_indices.len());
         for &i in &filtered_indices {
-            let block_no = state_metadata.blocks_to_settle[i];
-            tracing::debug!(job_id = %job.internal_id, block_no = %block_no, "Processing block");
             let snos = fetch_snos_for_block(internal_id.clone(), i, config.clone(), &snos_output_paths).await?;
             let program_output = fetch_program_output_for_block(i, config.clone(), &program_output_paths).await?;
             let blob_data = fetch_blob_data_for_block(i, config.clone(), &blob_data_paths).await?;
+            block_inputs.push((state_metadata.blocks_to_settle[i], snos, program_output, blob_data));
+        }
+
+        let mut nonce = config.settlement_client().get_nonce().await.map_err(|e| JobError::Transient(OtherError(e)))?;
+
+        let mut sent_tx_hashes: Vec<String> = Vec::with_capacity(filtered_indices.len());
+
+        for (block_no, snos, program_output, blob_data) in block_inputs {
+            tracing::debug!(job_id = %job.internal_id, block_no = %block_no, "Processing block");
             let txn_hash = match self
                 .update_state_for_block(config.clone(), block_no, snos, nonce, program_output, blob_data)
                 .await
@@ -244,7 +253,7 @@ impl Job for StateUpdateJob {
             );
 
             let tx_inclusion_status =
-                settlement_client.verify_tx_inclusion(tx_hash).await.map_err(|e| JobError::Other(OtherError(e)))?;
+                settlement_client.verify_tx_inclusion(tx_hash).await.map_err(|e| JobError::Transient(OtherError(e)))?;
 
             match tx_inclusion_status {
                 SettlementVerificationStatus::Rejected(_) => {
@@ -267,12 +276,12 @@ impl Job for StateUpdateJob {
                     settlement_client
                         .wait_for_tx_finality(tx_hash)
                         .await
-                        .map_err(|e| JobError::Other(OtherError(e)))?;
+                        .map_err(|e| JobError::Transient(OtherError(e)))?;
 
                     let new_status = settlement_client
                         .verify_tx_inclusion(tx_hash)
                         .await
-                        .map_err(|e| JobError::Other(OtherError(e)))?;
+                        .map_err(|e| JobError::Transient(OtherError(e)))?;
 
                     match new_status {
                         SettlementVerificationStatus::Rejected(_) => {
@@ -316,7 +325,7 @@ impl Job for StateUpdateJob {
         let expected_last_block_number = block_numbers.last().ok_or_else(|| StateUpdateError::EmptyBlockNumberList)?;
 
         let out_last_block_number =
-            settlement_client.get_last_settled_block().await.map_err(|e| JobError::Other(OtherError(e)))?;
+            settlement_client.get_last_settled_block().await.map_err(|e| JobError::Transient(OtherError(e)))?;
 
         let block_status = if out_last_block_number == *expected_last_block_number {
             tracing::info!(
@@ -356,8 +365,22 @@ impl Job for StateUpdateJob {
         10
     }
 
-    fn verification_polling_delay_seconds(&self) -> u64 {
-        60
+    fn process_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(600),
+            jitter: 0.2,
+        }
+    }
+
+    fn verification_polling_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_secs(60),
+            multiplier: 1.5,
+            max_delay: Duration::from_secs(300),
+            jitter: 0.1,
+        }
     }
 
     fn job_processing_lock(
@@ -381,8 +404,11 @@ impl StateUpdateJob {
             Err(StateUpdateError::UnsortedBlockNumbers)?;
         }
         // Check for gap between the last settled block and the first block to settle
-        let last_settled_block: u64 =
-            config.settlement_client().get_last_settled_block().await.map_err(|e| JobError::Other(OtherError(e)))?;
+        let last_settled_block: u64 = config
+            .settlement_client()
+            .get_last_settled_block()
+            .await
+            .map_err(|e| JobError::Transient(OtherError(e)))?;
         if last_settled_block + 1 != block_numbers[0] {
             Err(StateUpdateError::GapBetweenFirstAndLastBlock)?;
         }
@@ -402,7 +428,18 @@ impl StateUpdateJob {
     ) -> Result<String, JobError> {
         let settlement_client = config.settlement_client();
         let last_tx_hash_executed = if snos.use_kzg_da == Felt252::ZERO {
-            unimplemented!("update_state_for_block not implemented as of now for calldata DA.")
+            let (main_output, onchain_data) = split_calldata_program_output(&program_output)?;
+            let (onchain_data_hash, onchain_data_size) = compute_onchain_data_hash_and_size(&onchain_data);
+            tracing::debug!(
+                block_no = %block_no,
+                onchain_data_hash = %hex::encode(onchain_data_hash),
+                onchain_data_size = onchain_data.len(),
+                "Updating state with calldata DA"
+            );
+            settlement_client
+                .update_state_calldata(main_output, onchain_data_hash, onchain_data_size)
+                .await
+                .map_err(|e| JobError::Other(OtherError(e)))?
         } else if snos.use_kzg_da == Felt252::ONE {
             settlement_client
                 .update_state_with_blobs(program_output, blob_data, nonce)
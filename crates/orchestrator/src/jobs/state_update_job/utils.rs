@@ -3,7 +3,7 @@ use std::io::{BufRead, Cursor};
 use std::str::FromStr;
 use std::sync::Arc;
 
-use alloy::primitives::U256;
+use alloy::primitives::{keccak256, U256};
 use color_eyre::eyre::eyre;
 use num_bigint::BigUint;
 use starknet_os::io::output::StarknetOsOutput;
@@ -29,7 +29,7 @@ pub async fn fetch_blob_data_for_block(
     tracing::debug!("Retrieving blob data from path: {}", path);
     let blob_data = storage_client.get_data(path).await.map_err(|e| {
         tracing::error!("Failed to retrieve blob data from path {}: {}", path, e);
-        JobError::Other(OtherError(e))
+        JobError::Transient(OtherError(e))
     })?;
 
     tracing::debug!("Successfully retrieved blob data for block index {}", block_index);
@@ -55,7 +55,7 @@ pub async fn fetch_snos_for_block(
     tracing::debug!(job_id = %internal_id, "Retrieving SNOS output from path: {}", snos_path);
     let snos_output_bytes = storage_client.get_data(snos_path).await.map_err(|e| {
         tracing::error!(job_id = %internal_id, "Failed to retrieve SNOS data from path {}: {}", snos_path, e);
-        JobError::Other(OtherError(e))
+        JobError::Transient(OtherError(e))
     })?;
 
     tracing::debug!(job_id = %internal_id, "Deserializing SNOS output from path: {}", snos_path);
@@ -87,7 +87,7 @@ pub async fn fetch_program_output_for_block(
     tracing::debug!("Retrieving program output from path: {}", path);
     let program_output = storage_client.get_data(path).await.map_err(|e| {
         tracing::error!("Failed to retrieve program output from path {}: {}", path, e);
-        JobError::Other(OtherError(e))
+        JobError::Transient(OtherError(e))
     })?;
 
     tracing::debug!("Deserializing program output from path: {}", path);
@@ -97,6 +97,48 @@ pub async fn fetch_program_output_for_block(
     })
 }
 
+/// Length of the header of the Starknet OS output: the state roots, block numbers and hashes, the
+/// OS program and config hashes and the DA flags. With calldata DA, no KZG segment follows it.
+const OS_OUTPUT_HEADER_LEN: usize = 10;
+
+/// Splits the program output of a block settled with calldata DA into its main part and its
+/// on-chain data. With calldata DA, the OS outputs the state diff right after the messages
+/// segments: the core contract is given the main part along with the hash and size of this state
+/// diff, from which it rebuilds the state transition fact registered by the proof.
+pub fn split_calldata_program_output(program_output: &[[u8; 32]]) -> Result<(Vec<[u8; 32]>, Vec<[u8; 32]>), JobError> {
+    let mut onchain_data_offset = OS_OUTPUT_HEADER_LEN;
+    // the messages to L1 then to L2, each segment being prefixed by its size
+    for _ in 0..2 {
+        let segment_size = program_output.get(onchain_data_offset).ok_or_else(|| {
+            JobError::Other(OtherError(eyre!("Program output is too short ({} felts)", program_output.len())))
+        })?;
+        let segment_size: usize = U256::from_be_bytes(*segment_size)
+            .try_into()
+            .map_err(|e| JobError::Other(OtherError(eyre!("Invalid messages segment size: {}", e))))?;
+        onchain_data_offset = onchain_data_offset.saturating_add(1).saturating_add(segment_size);
+    }
+
+    if onchain_data_offset > program_output.len() {
+        return Err(JobError::Other(OtherError(eyre!(
+            "Messages segments end at felt {} past the program output ({} felts)",
+            onchain_data_offset,
+            program_output.len()
+        ))));
+    }
+
+    let (main_output, onchain_data) = program_output.split_at(onchain_data_offset);
+    Ok((main_output.to_vec(), onchain_data.to_vec()))
+}
+
+/// Computes the on-chain data hash and size the core contract expects for a block settled with
+/// calldata DA: the keccak of the state diff felts as 32 bytes big endian words, and the number of
+/// felts.
+pub fn compute_onchain_data_hash_and_size(onchain_data: &[[u8; 32]]) -> ([u8; 32], [u8; 32]) {
+    let onchain_data_hash: [u8; 32] = keccak256(onchain_data.concat()).into();
+    let onchain_data_size: [u8; 32] = U256::from(onchain_data.len()).to_be_bytes();
+    (onchain_data_hash, onchain_data_size)
+}
+
 // Util Functions
 // ===============
 
@@ -6,9 +6,10 @@ use da_client_interface::DaVerificationStatus;
 use mongodb::bson::serde_helpers::{chrono_datetime_as_bson_datetime, uuid_1_as_binary};
 use serde::{Deserialize, Serialize};
 use settlement_client_interface::SettlementVerificationStatus;
+use strum_macros::EnumIter;
 use uuid::Uuid;
 
-use crate::jobs::metadata::JobMetadata;
+use crate::jobs::metadata::{JobMetadata, JobSpecificMetadata};
 
 /// An external id.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -70,7 +71,7 @@ fn unwrap_external_id_failed(expected: &str, got: &ExternalId) -> color_eyre::ey
     eyre!("wrong ExternalId type: expected {}, got {:?}", expected, got)
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, EnumIter)]
 pub enum JobType {
     /// Running SNOS for a block
     SnosRun,
@@ -84,6 +85,19 @@ pub enum JobType {
     StateTransition,
 }
 
+impl JobType {
+    /// Job types out of which jobs of this type are created.
+    pub fn dependencies(&self) -> &'static [JobType] {
+        match self {
+            JobType::SnosRun => &[],
+            JobType::ProofCreation => &[JobType::SnosRun],
+            JobType::ProofRegistration => &[JobType::ProofCreation],
+            JobType::DataSubmission => &[JobType::ProofCreation],
+            JobType::StateTransition => &[JobType::SnosRun, JobType::DataSubmission],
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, PartialOrd, strum_macros::Display, Eq)]
 pub enum JobStatus {
     /// An acknowledgement that the job has been received by the
@@ -104,6 +118,8 @@ pub enum JobStatus {
     Failed,
     /// The job is being retried
     PendingRetry,
+    /// The job was stuck in processing or verification and is left for manual intervention
+    Stale,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -132,6 +148,31 @@ pub struct JobItem {
     pub updated_at: DateTime<Utc>,
 }
 
+impl JobItem {
+    /// When the job failed, its last update for jobs which failed before the failure time was
+    /// recorded.
+    pub fn failed_at(&self) -> DateTime<Utc> {
+        self.metadata.common.failed_at.unwrap_or(self.updated_at)
+    }
+
+    /// Whether the job handles any block between `from_block` and `to_block`, both included.
+    ///
+    /// Most jobs handle the block of their internal id, while state transition and proof
+    /// registration jobs cover several blocks.
+    pub fn covers_blocks(&self, from_block: u64, to_block: u64) -> bool {
+        let blocks = from_block..=to_block;
+        match &self.metadata.specific {
+            JobSpecificMetadata::StateUpdate(metadata) => {
+                metadata.blocks_to_settle.iter().any(|block| blocks.contains(block))
+            }
+            JobSpecificMetadata::ProofRegistration(metadata) => {
+                metadata.from_block <= to_block && metadata.to_block >= from_block
+            }
+            _ => self.internal_id.parse::<u64>().is_ok_and(|block| blocks.contains(&block)),
+        }
+    }
+}
+
 /// Defining a structure that contains the changes to be made in the job object,
 /// id and created at are not allowed to be changed
 // version and updated_at will always be updated when this object updates the job
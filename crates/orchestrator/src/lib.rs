@@ -26,6 +26,8 @@ pub mod queue;
 pub mod routes;
 /// Contains setup functions to set up db and cloud.
 pub mod setup;
+/// Coordinates the graceful shutdown of the service
+pub mod shutdown;
 /// Contains telemetry collection services. (Metrics/Logs/Traces)
 pub mod telemetry;
 #[cfg(test)]
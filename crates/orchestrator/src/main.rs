@@ -1,10 +1,15 @@
 use clap::Parser as _;
 use dotenvy::dotenv;
+use orchestrator::cli::cron::CronValidatedArgs;
 use orchestrator::cli::{Cli, Commands, RunCmd, SetupCmd};
 use orchestrator::config::init_config;
+use orchestrator::cron::in_process::InProcessCron;
+use orchestrator::cron::Cron;
+use orchestrator::metrics::backlog::start_backlog_metrics_poller;
 use orchestrator::queue::init_consumers;
 use orchestrator::routes::setup_server;
 use orchestrator::setup::setup_cloud;
+use orchestrator::shutdown::wait_for_shutdown_signal;
 use orchestrator::telemetry::{setup_analytics, shutdown_analytics};
 
 #[global_allocator]
@@ -27,6 +32,9 @@ async fn main() {
         Commands::Setup { setup_command } => {
             setup_orchestrator(setup_command).await.expect("Failed to setup orchestrator");
         }
+        Commands::DeadLetters { dead_letter_command } => {
+            dead_letter_command.execute().await.expect("Failed to run the dead letter command");
+        }
     }
 }
 
@@ -48,7 +56,7 @@ async fn run_orchestrator(run_cmd: &RunCmd) -> color_eyre::Result<()> {
     tracing::debug!(service = "orchestrator", "Application router initialized");
 
     // init consumer
-    match init_consumers(config).await {
+    match init_consumers(config.clone()).await {
         Ok(_) => tracing::info!(service = "orchestrator", "Consumers initialized successfully"),
         Err(e) => {
             tracing::error!(service = "orchestrator", error = %e, "Failed to initialize consumers");
@@ -56,11 +64,30 @@ async fn run_orchestrator(run_cmd: &RunCmd) -> color_eyre::Result<()> {
         }
     }
 
-    tokio::signal::ctrl_c().await.expect("Failed to listen for ctrl+c");
+    // start the in-process cron, if any. Otherwise the workers are triggered from outside
+    match run_cmd.validate_cron_params().expect("Invalid cron params") {
+        Some(CronValidatedArgs::InProcess(in_process_cron_params)) => {
+            InProcessCron::new_with_args(&in_process_cron_params).start(config.clone()).await?;
+            tracing::info!(service = "orchestrator", "In-process cron started successfully");
+        }
+        Some(CronValidatedArgs::AWSEventBridge(_)) | None => {}
+    }
+
+    start_backlog_metrics_poller(config.clone(), config.service_config().backlog_metrics_interval);
+
+    wait_for_shutdown_signal().await;
+    tracing::info!(service = "orchestrator", "Orchestrator service shutting down");
+
+    // stop pulling from the queues and let the in-flight jobs finish
+    if !config.shutdown().shutdown(config.service_config().shutdown_timeout).await {
+        tracing::warn!(service = "orchestrator", "Some jobs didn't finish before the shutdown deadline");
+    }
+
+    // let the other replicas lead the singleton workers right away
+    config.leaderships().release_all().await;
 
     // Analytics Shutdown
     shutdown_analytics(meter_provider, &instrumentation_params);
-    tracing::info!(service = "orchestrator", "Orchestrator service shutting down");
 
     Ok(())
 }
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use color_eyre::eyre::eyre;
+use opentelemetry::KeyValue;
+use starknet::core::types::{BlockId, MaybePendingBlockWithTxHashes};
+use starknet::providers::Provider;
+use tokio::task::JoinHandle;
+
+use crate::config::Config;
+use crate::jobs::metadata::StateUpdateMetadata;
+use crate::jobs::types::{JobStatus, JobType};
+use crate::metrics::ORCHESTRATOR_METRICS;
+use crate::queue::QUEUES;
+
+/// How often the job and queue backlog is polled, if not configured.
+pub const DEFAULT_BACKLOG_METRICS_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Records the backlog of the orchestrator: the number of jobs of each type and status, the
+/// depth of every queue and the settlement latency of the last settled block.
+///
+/// Every replica records the same values, so dashboards should aggregate them with `max`.
+#[derive(Default)]
+pub struct BacklogMetrics {
+    /// Type and status pairs recorded by the previous poll, a pair without jobs anymore is set
+    /// back to 0 instead of keeping its last count
+    recorded_job_counts: Vec<(JobType, JobStatus)>,
+    /// Last settled block seen by the previous poll, its settlement latency is already recorded
+    last_settled_block: Option<u64>,
+}
+
+impl BacklogMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Polls the database and the queues, and records their backlog.
+    pub async fn record(&mut self, config: &Config) -> color_eyre::Result<()> {
+        let job_counts = config.database().count_jobs_by_type_and_status().await?;
+        for (job_type, status) in &self.recorded_job_counts {
+            let has_jobs =
+                job_counts.iter().any(|(count_type, count_status, _)| (count_type, count_status) == (job_type, status));
+            if !has_jobs {
+                ORCHESTRATOR_METRICS.jobs_by_status.record(0.0, &job_count_attributes(job_type, status));
+            }
+        }
+        for (job_type, status, count) in &job_counts {
+            ORCHESTRATOR_METRICS.jobs_by_status.record(*count as f64, &job_count_attributes(job_type, status));
+        }
+        self.recorded_job_counts = job_counts.into_iter().map(|(job_type, status, _)| (job_type, status)).collect();
+
+        for queue_config in QUEUES.iter() {
+            let depth = config.queue().get_queue_depth(queue_config.name.clone()).await?;
+            let queue = queue_config.name.to_string();
+            for (state, messages) in
+                [("visible", depth.visible), ("in_flight", depth.in_flight), ("delayed", depth.delayed)]
+            {
+                let attributes = [KeyValue::new("queue", queue.clone()), KeyValue::new("state", state)];
+                ORCHESTRATOR_METRICS.queue_depth.record(messages as f64, &attributes);
+            }
+        }
+
+        self.record_settlement_latency(config).await
+    }
+
+    /// Records the time from the production of the last settled block to its settlement, once
+    /// per block. The settlement of the blocks before it in the same state transition, or in the
+    /// state transitions completed since the previous poll, isn't recorded.
+    async fn record_settlement_latency(&mut self, config: &Config) -> color_eyre::Result<()> {
+        let Some(job) =
+            config.database().get_latest_job_by_type_and_status(JobType::StateTransition, JobStatus::Completed).await?
+        else {
+            return Ok(());
+        };
+        let state_metadata: StateUpdateMetadata = job.metadata.specific.clone().try_into()?;
+        let Some(&block_no) = state_metadata.blocks_to_settle.last() else {
+            return Ok(());
+        };
+
+        // the first poll only remembers the block, its settlement may be recorded before a restart
+        let previous_block = self.last_settled_block.replace(block_no);
+        if previous_block.map_or(true, |previous_block| previous_block >= block_no) {
+            return Ok(());
+        }
+
+        let settled_at = job.metadata.common.verification_completed_at.unwrap_or(job.updated_at);
+        match config.starknet_client().get_block_with_tx_hashes(BlockId::Number(block_no)).await? {
+            MaybePendingBlockWithTxHashes::Block(block) => {
+                let settlement_latency = settled_at.timestamp() as f64 - block.timestamp as f64;
+                ORCHESTRATOR_METRICS.block_settlement_latency.record(settlement_latency.max(0.0), &[]);
+                Ok(())
+            }
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => Err(eyre!("Settled block {} is still pending", block_no)),
+        }
+    }
+}
+
+fn job_count_attributes(job_type: &JobType, status: &JobStatus) -> [KeyValue; 2] {
+    [
+        KeyValue::new("operation_job_type", format!("{:?}", job_type)),
+        KeyValue::new("operation_job_status", status.to_string()),
+    ]
+}
+
+/// Polls the backlog every `interval` in the background, until the shutdown starts.
+pub fn start_backlog_metrics_poller(config: Arc<Config>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut backlog_metrics = BacklogMetrics::new();
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = config.shutdown().draining() => break,
+            }
+            if let Err(e) = backlog_metrics.record(&config).await {
+                tracing::warn!(error = %e, "Failed to poll the backlog metrics");
+            }
+        }
+    })
+}
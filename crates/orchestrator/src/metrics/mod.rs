@@ -0,0 +1,177 @@
+use once_cell;
+use once_cell::sync::Lazy;
+use opentelemetry::metrics::{Counter, Gauge, Histogram};
+use opentelemetry::{global, KeyValue};
+use utils::metrics::lib::{
+    register_counter_metric_instrument, register_gauge_metric_instrument, register_histogram_metric_instrument, Metrics,
+};
+use utils::register_metric;
+
+pub mod backlog;
+
+register_metric!(ORCHESTRATOR_METRICS, OrchestratorMetrics);
+
+/// Bucket bounds of the histograms, in seconds. They span from the duration of a single job
+/// operation up to the end to end latency of a block, which can take hours.
+pub const LATENCY_BUCKETS_SECONDS: [f64; 20] = [
+    0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0, 3600.0, 7200.0, 14400.0,
+    28800.0, 43200.0, 86400.0,
+];
+
+pub struct OrchestratorMetrics {
+    pub block_gauge: Gauge<f64>,
+    pub successful_job_operations: Counter<f64>,
+    pub failed_job_operations: Counter<f64>,
+    pub failed_jobs: Counter<f64>,
+    /// Deprecated, in milliseconds, use `verification_time_seconds`
+    pub verification_time: Gauge<f64>,
+    pub verification_time_seconds: Histogram<f64>,
+    pub jobs_response_time: Histogram<f64>,
+    pub job_time_to_process: Histogram<f64>,
+    pub job_time_to_verify: Histogram<f64>,
+    pub block_settlement_latency: Histogram<f64>,
+    pub db_calls_response_time: Gauge<f64>,
+    pub halted_workers: Gauge<f64>,
+    pub reaped_stuck_jobs: Counter<f64>,
+    pub jobs_by_status: Gauge<f64>,
+    pub queue_depth: Gauge<f64>,
+}
+
+impl Metrics for OrchestratorMetrics {
+    fn register() -> Self {
+        // Register meter
+        let common_scope_attributes = vec![KeyValue::new("crate", "orchestrator")];
+        let orchestrator_meter = global::meter_with_version(
+            "crates.orchestrator.opentelemetry",
+            // TODO: Unsure of these settings, come back
+            Some("0.17"),
+            Some("https://opentelemetry.io/schemas/1.2.0"),
+            Some(common_scope_attributes.clone()),
+        );
+
+        // Register all instruments
+        let block_gauge = register_gauge_metric_instrument(
+            &orchestrator_meter,
+            "block_state".to_string(),
+            "A gauge to show block state at given time".to_string(),
+            "block".to_string(),
+        );
+
+        let successful_job_operations = register_counter_metric_instrument(
+            &orchestrator_meter,
+            "successful_job_operations".to_string(),
+            "A counter to show count of successful job operations over time".to_string(),
+            "jobs".to_string(),
+        );
+
+        let failed_job_operations = register_counter_metric_instrument(
+            &orchestrator_meter,
+            "failed_job_operations".to_string(),
+            "A counter to show count of failed job operations over time".to_string(),
+            "jobs".to_string(),
+        );
+
+        let failed_jobs = register_counter_metric_instrument(
+            &orchestrator_meter,
+            "failed_jobs".to_string(),
+            "A counter to show count of failed jobs over time".to_string(),
+            "jobs".to_string(),
+        );
+
+        let verification_time = register_gauge_metric_instrument(
+            &orchestrator_meter,
+            "verification_time".to_string(),
+            "Deprecated, use verification_time_seconds. A gauge to show the time taken for verification of tasks"
+                .to_string(),
+            "ms".to_string(),
+        );
+
+        let verification_time_seconds = register_histogram_metric_instrument(
+            &orchestrator_meter,
+            "verification_time_seconds".to_string(),
+            "A histogram to show the time taken by the last verification attempt of jobs".to_string(),
+            "s".to_string(),
+        );
+
+        let jobs_response_time = register_histogram_metric_instrument(
+            &orchestrator_meter,
+            "jobs_response_time".to_string(),
+            "A histogram to show response time of job operations".to_string(),
+            "s".to_string(),
+        );
+
+        let job_time_to_process = register_histogram_metric_instrument(
+            &orchestrator_meter,
+            "job_time_to_process".to_string(),
+            "A histogram to show the time from the creation of jobs to the end of their processing".to_string(),
+            "s".to_string(),
+        );
+
+        let job_time_to_verify = register_histogram_metric_instrument(
+            &orchestrator_meter,
+            "job_time_to_verify".to_string(),
+            "A histogram to show the time from the end of the processing of jobs to their verification".to_string(),
+            "s".to_string(),
+        );
+
+        let block_settlement_latency = register_histogram_metric_instrument(
+            &orchestrator_meter,
+            "block_settlement_latency".to_string(),
+            "A histogram to show the time from the production of L2 blocks to their settlement on L1".to_string(),
+            "s".to_string(),
+        );
+
+        let db_calls_response_time = register_gauge_metric_instrument(
+            &orchestrator_meter,
+            "db_calls_response_time".to_string(),
+            "A gauge to show response time of jobs over time".to_string(),
+            "s".to_string(),
+        );
+
+        let halted_workers = register_gauge_metric_instrument(
+            &orchestrator_meter,
+            "halted_workers".to_string(),
+            "A gauge to show whether a worker is halted by failed jobs".to_string(),
+            "worker".to_string(),
+        );
+
+        let reaped_stuck_jobs = register_counter_metric_instrument(
+            &orchestrator_meter,
+            "reaped_stuck_jobs".to_string(),
+            "A counter to show count of stuck jobs re-enqueued or marked as stale over time".to_string(),
+            "jobs".to_string(),
+        );
+
+        let jobs_by_status = register_gauge_metric_instrument(
+            &orchestrator_meter,
+            "jobs_by_status".to_string(),
+            "A gauge to show the number of jobs of each type and status".to_string(),
+            "jobs".to_string(),
+        );
+
+        let queue_depth = register_gauge_metric_instrument(
+            &orchestrator_meter,
+            "queue_depth".to_string(),
+            "A gauge to show the number of visible, in flight and delayed messages of each queue".to_string(),
+            "messages".to_string(),
+        );
+
+        Self {
+            block_gauge,
+            successful_job_operations,
+            failed_job_operations,
+            failed_jobs,
+            verification_time,
+            verification_time_seconds,
+            jobs_response_time,
+            job_time_to_process,
+            job_time_to_verify,
+            block_settlement_latency,
+            db_calls_response_time,
+            halted_workers,
+            reaped_stuck_jobs,
+            jobs_by_status,
+            queue_depth,
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use omniqueue::Delivery;
+use strum::IntoEnumIterator as _;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::sleep;
+
+use super::job_queue::ConsumptionError;
+use super::registry::job_queue_registration_of_queue;
+use super::{DeadLetterMessage, QueueType};
+use crate::config::Config;
+use crate::jobs::retry_policy::{RetryPolicy, MAX_QUEUE_DELAY};
+
+/// How many messages of a queue a replica handles at once, if not configured.
+pub const DEFAULT_QUEUE_CONCURRENCY: usize = 4;
+
+/// Most messages SQS returns from a single receive.
+pub const MAX_RECEIVE_BATCH_SIZE: usize = 10;
+
+/// How many messages are received at once, if not configured.
+pub const DEFAULT_RECEIVE_BATCH_SIZE: usize = MAX_RECEIVE_BATCH_SIZE;
+
+/// How long a receive waits for messages to arrive, if not configured.
+pub const DEFAULT_RECEIVE_WAIT: Duration = Duration::from_secs(10);
+
+/// Longest wait SQS accepts for a receive.
+pub const MAX_RECEIVE_WAIT: Duration = Duration::from_secs(20);
+
+/// How many times a job message is put back in its queue after a transient error, if not
+/// configured.
+pub const DEFAULT_TRANSIENT_RETRIES: usize = 3;
+
+/// How long a job message put back in its queue after a first transient error waits, if not
+/// configured. The following retries wait exponentially longer.
+pub const DEFAULT_TRANSIENT_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// How long to wait before receiving again from an empty queue. Queues with long polling already
+/// waited for messages by then.
+const EMPTY_QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How the queues are consumed.
+#[derive(Debug, Clone)]
+pub struct QueueConsumerParams {
+    /// Messages of a queue handled at once
+    pub concurrency: usize,
+    /// Per queue concurrency overriding the default one
+    pub concurrency_overrides: HashMap<QueueType, usize>,
+    /// Messages received at once
+    pub receive_batch_size: usize,
+    /// How long a receive waits for messages to arrive
+    pub receive_wait: Duration,
+    /// Times a job message is put back in its queue after a transient error
+    pub transient_retries: usize,
+    /// Per queue transient retries overriding the default ones
+    pub transient_retries_overrides: HashMap<QueueType, usize>,
+    /// Delay of the first retry after a transient error
+    pub transient_retry_delay: Duration,
+    /// Queues this replica doesn't consume, left to the other replicas
+    pub disabled_consumers: HashSet<QueueType>,
+}
+
+impl Default for QueueConsumerParams {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_QUEUE_CONCURRENCY,
+            concurrency_overrides: HashMap::new(),
+            receive_batch_size: DEFAULT_RECEIVE_BATCH_SIZE,
+            receive_wait: DEFAULT_RECEIVE_WAIT,
+            transient_retries: DEFAULT_TRANSIENT_RETRIES,
+            transient_retries_overrides: HashMap::new(),
+            transient_retry_delay: DEFAULT_TRANSIENT_RETRY_DELAY,
+            disabled_consumers: HashSet::new(),
+        }
+    }
+}
+
+impl QueueConsumerParams {
+    /// The number of messages of `queue` handled at once. Processing queues may have a
+    /// concurrency registered with their job type, used unless overridden.
+    pub fn concurrency(&self, queue: &QueueType) -> usize {
+        let registered = job_queue_registration_of_queue(queue)
+            .filter(|registration| &registration.process_queue == queue)
+            .and_then(|registration| registration.process_concurrency);
+        self.concurrency_overrides.get(queue).copied().or(registered).unwrap_or(self.concurrency)
+    }
+
+    /// Whether this replica consumes `queue`.
+    pub fn is_enabled(&self, queue: &QueueType) -> bool {
+        !self.disabled_consumers.contains(queue)
+    }
+
+    /// The number of times a job message of `queue` is put back in the queue after a transient
+    /// error.
+    pub fn transient_retries(&self, queue: &QueueType) -> usize {
+        self.transient_retries_overrides.get(queue).copied().unwrap_or(self.transient_retries)
+    }
+
+    /// How long a job message put back in its queue after a transient error waits.
+    pub fn transient_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy { base_delay: self.transient_retry_delay, multiplier: 2.0, max_delay: MAX_QUEUE_DELAY, jitter: 0.2 }
+    }
+}
+
+/// Parses per queue concurrencies, e.g. `update_state_job_processing=2,snos_job_verification=8`.
+pub fn parse_queue_concurrency_overrides(overrides: &str) -> Result<HashMap<QueueType, usize>, String> {
+    let overrides = parse_queue_overrides(overrides, "queue concurrency")?;
+    if let Some((queue, _)) = overrides.iter().find(|(_, concurrency)| **concurrency == 0) {
+        return Err(format!("Queue concurrency of {} must be at least 1", queue));
+    }
+    Ok(overrides)
+}
+
+/// Parses per queue transient retries, e.g. `update_state_job_processing=0,snos_job_processing=5`.
+pub fn parse_queue_transient_retries_overrides(overrides: &str) -> Result<HashMap<QueueType, usize>, String> {
+    parse_queue_overrides(overrides, "queue transient retries")
+}
+
+/// Parses a list of queues, e.g. `proving_job_processing,proving_job_verification`.
+pub fn parse_queue_names(queues: &str) -> Result<HashSet<QueueType>, String> {
+    queues
+        .split(',')
+        .map(str::trim)
+        .filter(|queue| !queue.is_empty())
+        .map(|queue| {
+            QueueType::iter()
+                .find(|queue_type| queue_type.to_string() == queue)
+                .ok_or_else(|| format!("Unknown queue {:?}", queue))
+        })
+        .collect()
+}
+
+fn parse_queue_overrides(overrides: &str, setting: &str) -> Result<HashMap<QueueType, usize>, String> {
+    overrides
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (queue, value) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid {} {:?}, expected queue_name=value", setting, entry))?;
+            let queue = QueueType::iter()
+                .find(|queue_type| queue_type.to_string() == queue.trim())
+                .ok_or_else(|| format!("Unknown queue in {} {:?}", setting, entry))?;
+            let value = value.trim().parse::<usize>().map_err(|e| format!("Invalid {} {:?}: {}", setting, entry, e))?;
+            Ok((queue, value))
+        })
+        .collect()
+}
+
+/// Receives the messages of `queue` with `receive` until the shutdown starts, and hands every
+/// message to `dispatch` along with a handler permit, which the handler of the message frees once
+/// done.
+///
+/// Only as many messages as there are free permits are received, so that a replica busy with
+/// long jobs leaves the other messages to the other replicas.
+pub async fn run_consumer<M, R, Fut, D>(queue: QueueType, config: Arc<Config>, receive: R, dispatch: D)
+where
+    R: Fn(Arc<Config>, QueueType, usize, Duration) -> Fut,
+    Fut: Future<Output = color_eyre::Result<Vec<M>>>,
+    D: Fn(M, OwnedSemaphorePermit) -> Result<(), ConsumptionError>,
+{
+    let params = config.service_config().queue_consumers.clone();
+    let handlers = Arc::new(Semaphore::new(params.concurrency(&queue)));
+    tracing::info!(queue = %queue, concurrency = params.concurrency(&queue), "Consuming from queue");
+
+    // no new message is pulled once the shutdown starts
+    while !config.shutdown().is_draining() {
+        let permit = tokio::select! {
+            permit = handlers.clone().acquire_owned() => permit.expect("Queue handler permits are never closed"),
+            _ = config.shutdown().draining() => break,
+        };
+        let mut permits = vec![permit];
+        while permits.len() < params.receive_batch_size {
+            match handlers.clone().try_acquire_owned() {
+                Ok(permit) => permits.push(permit),
+                Err(_) => break,
+            }
+        }
+
+        let received = tokio::select! {
+            received = receive(config.clone(), queue.clone(), permits.len(), params.receive_wait) => received,
+            _ = config.shutdown().draining() => break,
+        };
+        let messages = match received {
+            Ok(messages) => messages,
+            Err(e) => {
+                tracing::error!(queue = %queue, error = ?e, "Failed to consume from queue");
+                Vec::new()
+            }
+        };
+        if messages.is_empty() {
+            tokio::select! {
+                _ = sleep(EMPTY_QUEUE_POLL_INTERVAL) => {}
+                _ = config.shutdown().draining() => {}
+            }
+            continue;
+        }
+
+        tracing::debug!(queue = %queue, messages = messages.len(), "Messages received from queue");
+        for (message, permit) in messages.into_iter().zip(permits) {
+            if let Err(e) = dispatch(message, permit) {
+                tracing::error!(queue = %queue, error = ?e, "Failed to dispatch message");
+            }
+        }
+    }
+    tracing::info!("Stopped consuming from queue {:?}", queue);
+}
+
+/// Receives the messages of a job or worker trigger queue.
+pub async fn receive_deliveries(
+    config: Arc<Config>,
+    queue: QueueType,
+    max_messages: usize,
+    wait: Duration,
+) -> color_eyre::Result<Vec<Delivery>> {
+    Ok(config.queue().consume_messages_from_queue(queue, max_messages, wait).await?)
+}
+
+/// Receives the messages of a dead letter queue, along with where they come from.
+pub async fn receive_dead_letters(
+    config: Arc<Config>,
+    queue: QueueType,
+    max_messages: usize,
+    wait: Duration,
+) -> color_eyre::Result<Vec<DeadLetterMessage>> {
+    config.queue().consume_dead_letter_messages(queue, max_messages, wait).await
+}
+
+#[cfg(test)]
+mod consumer_test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_parse_queue_concurrency_overrides() {
+        let overrides =
+            parse_queue_concurrency_overrides("update_state_job_processing=1, snos_job_verification=8").unwrap();
+        assert_eq!(overrides.get(&QueueType::UpdateStateJobProcessing), Some(&1));
+        assert_eq!(overrides.get(&QueueType::SnosJobVerification), Some(&8));
+
+        let params = QueueConsumerParams { concurrency_overrides: overrides, ..QueueConsumerParams::default() };
+        assert_eq!(params.concurrency(&QueueType::UpdateStateJobProcessing), 1);
+        assert_eq!(params.concurrency(&QueueType::ProvingJobProcessing), DEFAULT_QUEUE_CONCURRENCY);
+    }
+
+    #[rstest]
+    #[case("unknown_queue=1")]
+    #[case("snos_job_processing")]
+    #[case("snos_job_processing=0")]
+    #[case("snos_job_processing=many")]
+    fn test_parse_invalid_queue_concurrency_overrides(#[case] overrides: &str) {
+        assert!(parse_queue_concurrency_overrides(overrides).is_err());
+    }
+
+    #[rstest]
+    fn test_parse_queue_transient_retries_overrides() {
+        let overrides = parse_queue_transient_retries_overrides("update_state_job_processing=0").unwrap();
+        let params = QueueConsumerParams { transient_retries_overrides: overrides, ..QueueConsumerParams::default() };
+        assert_eq!(params.transient_retries(&QueueType::UpdateStateJobProcessing), 0);
+        assert_eq!(params.transient_retries(&QueueType::SnosJobProcessing), DEFAULT_TRANSIENT_RETRIES);
+
+        assert!(parse_queue_transient_retries_overrides("snos_job_processing=-1").is_err());
+    }
+
+    #[rstest]
+    fn test_registered_queue_concurrency() {
+        let params = QueueConsumerParams::default();
+        assert_eq!(params.concurrency(&QueueType::UpdateStateJobProcessing), 1);
+        assert_eq!(params.concurrency(&QueueType::UpdateStateJobVerification), DEFAULT_QUEUE_CONCURRENCY);
+
+        let overrides = parse_queue_concurrency_overrides("update_state_job_processing=2").unwrap();
+        let params = QueueConsumerParams { concurrency_overrides: overrides, ..QueueConsumerParams::default() };
+        assert_eq!(params.concurrency(&QueueType::UpdateStateJobProcessing), 2);
+    }
+
+    #[rstest]
+    fn test_parse_disabled_consumers() {
+        let disabled = parse_queue_names("proving_job_processing, proving_job_verification,").unwrap();
+        let params = QueueConsumerParams { disabled_consumers: disabled, ..QueueConsumerParams::default() };
+        assert!(!params.is_enabled(&QueueType::ProvingJobProcessing));
+        assert!(!params.is_enabled(&QueueType::ProvingJobVerification));
+        assert!(params.is_enabled(&QueueType::UpdateStateJobProcessing));
+
+        assert!(parse_queue_names("proving_job").is_err());
+    }
+}
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use uuid::Uuid;
+
+use super::job_queue::{add_job_to_process_queue, add_job_to_verification_queue, QueueNameForJobType};
+use super::{max_receive_count, DeadLetterSource, QueueType};
+use crate::config::Config;
+use crate::database::JobFilter;
+use crate::jobs::metadata::DeadLetter;
+use crate::jobs::types::{JobItem, JobItemUpdates, JobStatus};
+use crate::jobs::JobError;
+
+/// Number of failed jobs fetched at once while listing the dead letters.
+const DEAD_LETTER_PAGE_SIZE: i64 = 100;
+
+/// What an operator did with a dead letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DeadLetterAction {
+    /// The job was sent back to its source queue
+    Replayed,
+    /// The dead letter was dropped, the job stays failed
+    Discarded,
+}
+
+/// An entry of the dead letter audit log, written for every replayed or discarded dead letter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterAuditEntry {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub action: DeadLetterAction,
+    /// Who took the action, as given with the request
+    pub actor: String,
+    pub source_queue: QueueType,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The dead letters an action applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeadLetterSelection {
+    All,
+    Jobs(Vec<Uuid>),
+}
+
+/// A dead letter the action couldn't be applied to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterActionFailure {
+    pub job_id: Uuid,
+    pub error: String,
+}
+
+/// Outcome of an action applied to several dead letters. A failure doesn't stop the action on the
+/// other dead letters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterActionReport {
+    pub succeeded: Vec<Uuid>,
+    pub failed: Vec<DeadLetterActionFailure>,
+}
+
+/// The queue the message of `job` was dead lettered from. A job waiting on its verification was
+/// dead lettered from its verification queue, any other job from its processing queue.
+pub fn source_queue(job: &JobItem) -> QueueType {
+    match job.status {
+        JobStatus::PendingVerification | JobStatus::VerificationTimeout => job.job_type.verify_queue_name(),
+        _ => job.job_type.process_queue_name(),
+    }
+}
+
+/// The dead letter recorded for `job` when its message is received from the dead letter queue.
+/// What the broker didn't tell about the message is inferred from the job: the source queue from
+/// its status and the receive count from the redrive policy of the source queue.
+pub fn dead_letter_of(job: &JobItem, source: DeadLetterSource) -> DeadLetter {
+    let source_queue = source.queue.unwrap_or_else(|| source_queue(job));
+    DeadLetter {
+        receive_count: source.receive_count.or_else(|| max_receive_count(&source_queue)).unwrap_or(0),
+        source_queue,
+        job_status: job.status.clone(),
+        dead_lettered_at: Utc::now(),
+    }
+}
+
+/// Lists the failed jobs whose message is in the dead letter queue, by creation time.
+pub async fn list_dead_letters(config: Arc<Config>) -> color_eyre::Result<Vec<JobItem>> {
+    let filter = JobFilter { status: Some(JobStatus::Failed), ..Default::default() };
+    let mut dead_letters = Vec::new();
+    let mut cursor = None;
+    loop {
+        let jobs = config.database().get_jobs(filter.clone(), cursor, DEAD_LETTER_PAGE_SIZE).await?;
+        cursor = jobs.last().map(Into::into);
+        let last_page = (jobs.len() as i64) < DEAD_LETTER_PAGE_SIZE;
+        dead_letters.extend(jobs.into_iter().filter(|job| job.metadata.common.dead_letter.is_some()));
+        if last_page {
+            return Ok(dead_letters);
+        }
+    }
+}
+
+/// Sends the job of a dead letter back to the queue it was dead lettered from, with its attempts
+/// reset, and records it in the audit log. The job is failed again with its dead letter if it
+/// can't be sent.
+///
+/// # State Transitions
+/// * `Failed` -> `PendingRetry`, for jobs dead lettered from their processing queue
+/// * `Failed` -> `PendingVerification`, for jobs dead lettered from their verification queue
+pub async fn replay_dead_letter(id: Uuid, actor: &str, config: Arc<Config>) -> Result<(), JobError> {
+    let (mut job, dead_letter) = get_dead_letter(id, &config).await?;
+    let failed_metadata = job.metadata.clone();
+
+    job.metadata.common.dead_letter = None;
+    let verification = dead_letter.source_queue == job.job_type.verify_queue_name();
+    let status = if verification {
+        job.metadata.common.verification_attempt_no = 0;
+        job.metadata.common.verification_retry_attempt_no += 1;
+        JobStatus::PendingVerification
+    } else {
+        job.metadata.common.process_attempt_no = 0;
+        job.metadata.common.process_retry_attempt_no += 1;
+        JobStatus::PendingRetry
+    };
+    // updated before the job is sent, its consumer would skip a failed job
+    let replayed_job = config
+        .database()
+        .update_job(&job, JobItemUpdates::new().update_status(status).update_metadata(job.metadata.clone()).build())
+        .await?;
+
+    let queued = if verification {
+        add_job_to_verification_queue(id, &job.job_type, Duration::ZERO, config.clone()).await
+    } else {
+        add_job_to_process_queue(id, &job.job_type, config.clone()).await
+    };
+    if let Err(e) = queued {
+        // nothing would pick the job up otherwise
+        config
+            .database()
+            .update_job(
+                &replayed_job,
+                JobItemUpdates::new().update_status(JobStatus::Failed).update_metadata(failed_metadata).build(),
+            )
+            .await?;
+        return Err(e.into());
+    }
+
+    audit(&job, DeadLetterAction::Replayed, actor, dead_letter.source_queue, &config).await
+}
+
+/// Drops a dead letter, leaving its job failed, and records it in the audit log.
+pub async fn discard_dead_letter(id: Uuid, actor: &str, config: Arc<Config>) -> Result<(), JobError> {
+    let (mut job, dead_letter) = get_dead_letter(id, &config).await?;
+
+    job.metadata.common.dead_letter = None;
+    config.database().update_job(&job, JobItemUpdates::new().update_metadata(job.metadata.clone()).build()).await?;
+
+    audit(&job, DeadLetterAction::Discarded, actor, dead_letter.source_queue, &config).await
+}
+
+/// Replays or discards the selected dead letters, one after the other.
+pub async fn apply_dead_letter_action(
+    action: DeadLetterAction,
+    selection: DeadLetterSelection,
+    actor: &str,
+    config: Arc<Config>,
+) -> Result<DeadLetterActionReport, JobError> {
+    let ids = match selection {
+        DeadLetterSelection::Jobs(ids) => ids,
+        DeadLetterSelection::All => list_dead_letters(config.clone()).await?.into_iter().map(|job| job.id).collect(),
+    };
+
+    let mut report = DeadLetterActionReport::default();
+    for id in ids {
+        let result = match action {
+            DeadLetterAction::Replayed => replay_dead_letter(id, actor, config.clone()).await,
+            DeadLetterAction::Discarded => discard_dead_letter(id, actor, config.clone()).await,
+        };
+        match result {
+            Ok(()) => report.succeeded.push(id),
+            Err(e) => {
+                tracing::error!(job_id = ?id, action = %action, error = %e, "Failed to apply the dead letter action");
+                report.failed.push(DeadLetterActionFailure { job_id: id, error: e.to_string() });
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Fetches a failed job along with its dead letter.
+async fn get_dead_letter(id: Uuid, config: &Config) -> Result<(JobItem, DeadLetter), JobError> {
+    let job = config.database().get_job_by_id(id).await?.ok_or(JobError::JobNotFound { id })?;
+    if job.status != JobStatus::Failed {
+        return Err(JobError::InvalidStatus { id, job_status: job.status });
+    }
+    match job.metadata.common.dead_letter.clone() {
+        Some(dead_letter) => Ok((job, dead_letter)),
+        None => Err(JobError::NotDeadLettered { id }),
+    }
+}
+
+async fn audit(
+    job: &JobItem,
+    action: DeadLetterAction,
+    actor: &str,
+    source_queue: QueueType,
+    config: &Config,
+) -> Result<(), JobError> {
+    let entry = DeadLetterAuditEntry {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        action,
+        actor: actor.to_string(),
+        source_queue,
+        // stored with a millisecond precision by the databases
+        created_at: Utc::now().round_subsecs(3),
+    };
+    tracing::info!(
+        log_type = "audit",
+        category = "dead_letter",
+        job_id = ?job.id,
+        block_no = %job.internal_id,
+        action = %entry.action,
+        actor = %entry.actor,
+        source_queue = %entry.source_queue,
+        "Dead letter {}",
+        entry.action
+    );
+    Ok(config.database().create_dead_letter_audit_entry(entry).await?)
+}
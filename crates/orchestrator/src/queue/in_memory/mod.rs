@@ -0,0 +1,280 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use omniqueue::backends::{InMemoryBackend, InMemoryConsumer};
+use omniqueue::{Delivery, QueueError};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::QueueType;
+use crate::queue::{DeadLetterMessage, DeadLetterSource, QueueConfig, QueueDepth, QueueProvider, QUEUES};
+
+/// A message waiting in the queue.
+struct QueuedMessage {
+    payload: Vec<u8>,
+    receive_count: i32,
+    visible_at: Instant,
+    /// Where the message comes from, if it was dead lettered
+    source: DeadLetterSource,
+}
+
+/// A message handed to a consumer which hasn't been settled yet.
+///
+/// Every delivery is backed by its own omniqueue in memory channel, so a `nack` on the delivery
+/// sends the payload back to `nack_receiver` and we can redeliver it. The omniqueue acks can't be
+/// observed, so the message is only forgotten once acked through `QueueProvider::ack_message`.
+struct InFlightMessage {
+    payload: Vec<u8>,
+    receive_count: i32,
+    invisible_until: Instant,
+    source: DeadLetterSource,
+    nack_receiver: InMemoryConsumer,
+}
+
+struct InMemoryQueueState {
+    config: QueueConfig,
+    messages: VecDeque<QueuedMessage>,
+    in_flight: Vec<InFlightMessage>,
+    /// Dead letters received but not settled yet, by receipt
+    unsettled_dead_letters: HashMap<String, QueuedMessage>,
+}
+
+impl InMemoryQueueState {
+    fn new(config: QueueConfig) -> Self {
+        Self { config, messages: VecDeque::new(), in_flight: Vec::new(), unsettled_dead_letters: HashMap::new() }
+    }
+
+    /// Puts the nack-ed messages and the ones whose visibility timeout expired back in the queue.
+    async fn reclaim_in_flight_messages(&mut self) {
+        let now = Instant::now();
+        let mut still_in_flight = Vec::with_capacity(self.in_flight.len());
+        for mut message in self.in_flight.drain(..) {
+            // `timeout` polls the receive once before checking the deadline, so this doesn't wait
+            let nacked =
+                matches!(tokio::time::timeout(Duration::ZERO, message.nack_receiver.receive()).await, Ok(Ok(_)));
+            if nacked || message.invisible_until <= now {
+                self.messages.push_back(QueuedMessage {
+                    payload: message.payload,
+                    receive_count: message.receive_count,
+                    visible_at: now,
+                    source: message.source,
+                });
+            } else {
+                still_in_flight.push(message);
+            }
+        }
+        self.in_flight = still_in_flight;
+    }
+
+    /// The in flight message delivered as `message`. Deliveries are told apart by their payload,
+    /// which is unique for the orchestrator messages in flight.
+    fn in_flight_message(&self, message: &Delivery) -> Option<usize> {
+        let payload = message.borrow_payload()?;
+        self.in_flight.iter().position(|in_flight| in_flight.payload == payload)
+    }
+
+    fn pop_visible_message(&mut self) -> Option<QueuedMessage> {
+        let now = Instant::now();
+        let index = self.messages.iter().position(|message| message.visible_at <= now)?;
+        self.messages.remove(index)
+    }
+}
+
+/// InMemoryQueue implements the queues in the process memory. It's meant for local runs and tests,
+/// everything is lost when the orchestrator stops.
+///
+/// It only follows part of the SQS semantics the orchestrator relies on:
+/// - delayed delivery is supported
+/// - a nack-ed message is received again, and moved to its dead letter queue once it has been
+///   received more than `DlqConfig::max_receive_count` times
+/// - a message neither acked nor nack-ed by the end of its visibility timeout, e.g. held by a stuck
+///   consumer, is received again. Messages must be acked with `QueueProvider::ack_message`, an ack
+///   on the delivery alone leaves it to be received again.
+pub struct InMemoryQueue {
+    queues: Mutex<HashMap<String, InMemoryQueueState>>,
+}
+
+impl Default for InMemoryQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryQueue {
+    /// Creates all the orchestrator queues, there's no separate setup step for in memory queues.
+    pub fn new() -> Self {
+        let queues =
+            QUEUES.iter().map(|config| (config.name.to_string(), InMemoryQueueState::new(config.clone()))).collect();
+        Self { queues: Mutex::new(queues) }
+    }
+}
+
+#[async_trait]
+impl QueueProvider for InMemoryQueue {
+    async fn send_message_to_queue(&self, queue: QueueType, payload: String, delay: Option<Duration>) -> Result<()> {
+        let mut queues = self.queues.lock().await;
+        let state = queues.get_mut(&queue.to_string()).ok_or_else(|| eyre!("Queue {} does not exist", queue))?;
+        state.messages.push_back(QueuedMessage {
+            payload: payload.into_bytes(),
+            receive_count: 0,
+            visible_at: Instant::now() + delay.unwrap_or_default(),
+            source: DeadLetterSource::default(),
+        });
+        Ok(())
+    }
+
+    async fn consume_message_from_queue(&self, queue: QueueType) -> std::result::Result<Delivery, QueueError> {
+        let mut queues = self.queues.lock().await;
+        let queue_name = queue.to_string();
+
+        loop {
+            let state = queues.get_mut(&queue_name).ok_or(QueueError::NoData)?;
+            state.reclaim_in_flight_messages().await;
+            let mut message = state.pop_visible_message().ok_or(QueueError::NoData)?;
+            message.receive_count += 1;
+
+            if let Some(dlq_config) = state.config.dlq_config.clone() {
+                if message.receive_count > dlq_config.max_receive_count {
+                    tracing::warn!(
+                        queue = %queue,
+                        dlq = %dlq_config.dlq_name,
+                        receive_count = message.receive_count,
+                        "Moving message to the dead letter queue"
+                    );
+                    if let Some(dlq_state) = queues.get_mut(&dlq_config.dlq_name.to_string()) {
+                        dlq_state.messages.push_back(QueuedMessage {
+                            payload: message.payload,
+                            receive_count: 0,
+                            visible_at: Instant::now(),
+                            // this receive only moved the message
+                            source: DeadLetterSource {
+                                queue: Some(queue.clone()),
+                                receive_count: Some(message.receive_count - 1),
+                            },
+                        });
+                    }
+                    continue;
+                }
+            }
+
+            let (producer, mut nack_receiver) = InMemoryBackend::builder().build_pair().await?;
+            producer.send_raw(&message.payload).await?;
+            let delivery = nack_receiver.receive().await?;
+
+            state.in_flight.push(InFlightMessage {
+                payload: message.payload,
+                receive_count: message.receive_count,
+                invisible_until: Instant::now() + Duration::from_secs(state.config.visibility_timeout as u64),
+                source: message.source,
+                nack_receiver,
+            });
+            return Ok(delivery);
+        }
+    }
+
+    async fn consume_messages_from_queue(
+        &self,
+        queue: QueueType,
+        max_messages: usize,
+        _wait: Duration,
+    ) -> std::result::Result<Vec<Delivery>, QueueError> {
+        let mut deliveries = Vec::with_capacity(max_messages);
+        while deliveries.len() < max_messages {
+            match self.consume_message_from_queue(queue.clone()).await {
+                Ok(delivery) => deliveries.push(delivery),
+                Err(QueueError::NoData) => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(deliveries)
+    }
+
+    async fn ack_message(&self, queue: QueueType, message: Delivery) -> Result<()> {
+        let mut queues = self.queues.lock().await;
+        let state = queues.get_mut(&queue.to_string()).ok_or_else(|| eyre!("Queue {} does not exist", queue))?;
+        // a message acked after its visibility timeout has already been put back in the queue
+        if let Some(index) = state.in_flight_message(&message) {
+            state.in_flight.remove(index);
+        }
+        message.ack().await.map_err(|(e, _)| e)?;
+        Ok(())
+    }
+
+    /// The omniqueue in memory deliveries don't support ack deadlines, the in flight message is
+    /// extended instead.
+    async fn extend_message_visibility(&self, message: &mut Delivery, timeout: Duration) -> Result<()> {
+        let mut queues = self.queues.lock().await;
+        for state in queues.values_mut() {
+            if let Some(index) = state.in_flight_message(message) {
+                state.in_flight[index].invisible_until = Instant::now() + timeout;
+                return Ok(());
+            }
+        }
+        Err(eyre!("The message isn't in flight anymore, its visibility timeout expired"))
+    }
+
+    async fn consume_dead_letter_messages(
+        &self,
+        queue: QueueType,
+        max_messages: usize,
+        _wait: Duration,
+    ) -> Result<Vec<DeadLetterMessage>> {
+        let mut queues = self.queues.lock().await;
+        let state = queues.get_mut(&queue.to_string()).ok_or_else(|| eyre!("Queue {} does not exist", queue))?;
+        state.reclaim_in_flight_messages().await;
+
+        let mut messages = Vec::new();
+        while messages.len() < max_messages.max(1) {
+            let Some(message) = state.pop_visible_message() else {
+                break;
+            };
+            let receipt = Uuid::new_v4().to_string();
+            messages.push(DeadLetterMessage {
+                payload: String::from_utf8(message.payload.clone())?,
+                source: message.source.clone(),
+                receipt: receipt.clone(),
+            });
+            state.unsettled_dead_letters.insert(receipt, message);
+        }
+        Ok(messages)
+    }
+
+    /// A message which wasn't handled is received again once its visibility timeout expires.
+    async fn settle_dead_letter_message(&self, queue: QueueType, receipt: &str, handled: bool) -> Result<()> {
+        let mut queues = self.queues.lock().await;
+        let state = queues.get_mut(&queue.to_string()).ok_or_else(|| eyre!("Queue {} does not exist", queue))?;
+        let mut message =
+            state.unsettled_dead_letters.remove(receipt).ok_or_else(|| eyre!("Unknown receipt {}", receipt))?;
+        if !handled {
+            message.visible_at = Instant::now() + Duration::from_secs(state.config.visibility_timeout as u64);
+            state.messages.push_back(message);
+        }
+        Ok(())
+    }
+
+    async fn create_queue(&self, queue_config: &QueueConfig) -> Result<()> {
+        let mut queues = self.queues.lock().await;
+        queues
+            .entry(queue_config.name.to_string())
+            .and_modify(|state| state.config = queue_config.clone())
+            .or_insert_with(|| InMemoryQueueState::new(queue_config.clone()));
+        Ok(())
+    }
+
+    async fn get_queue_depth(&self, queue: QueueType) -> Result<QueueDepth> {
+        let mut queues = self.queues.lock().await;
+        let state = queues.get_mut(&queue.to_string()).ok_or_else(|| eyre!("Queue {} does not exist", queue))?;
+        state.reclaim_in_flight_messages().await;
+
+        let now = Instant::now();
+        let visible = state.messages.iter().filter(|message| message.visible_at <= now).count();
+        Ok(QueueDepth {
+            visible: visible as u64,
+            in_flight: (state.in_flight.len() + state.unsettled_dead_letters.len()) as u64,
+            delayed: (state.messages.len() - visible) as u64,
+        })
+    }
+}
@@ -5,21 +5,30 @@ use std::time::Duration;
 
 use color_eyre::eyre::Context;
 use color_eyre::Result as EyreResult;
-use omniqueue::{Delivery, QueueError};
+use omniqueue::Delivery;
 use serde::{Deserialize, Deserializer, Serialize};
 use strum::Display;
 use thiserror::Error;
-use tokio::time::sleep;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::time::Instant;
+use tracing::Instrument as _;
 use uuid::Uuid;
 
-use super::QueueType;
+use super::consumer::{receive_dead_letters, receive_deliveries, run_consumer};
+use super::registry::{consumers, job_queue_registration, job_queue_registration_of_queue, ConsumerHandler};
+use super::{visibility_timeout, DeadLetterMessage, DeadLetterSource, QueueType};
+use crate::alerts::types::{Alert, AlertSeverity};
 use crate::config::Config;
 use crate::jobs::types::JobType;
-use crate::jobs::{handle_job_failure, process_job, verify_job, JobError, OtherError};
+use crate::jobs::{
+    fail_job_after_transient_retries, handle_job_failure, process_job, verify_job, JobError, OtherError,
+};
+use crate::telemetry::propagation::{continue_trace, current_trace_context, TraceContext};
 use crate::workers::data_submission_worker::DataSubmissionWorker;
 use crate::workers::proof_registration::ProofRegistrationWorker;
 use crate::workers::proving::ProvingWorker;
 use crate::workers::snos::SnosWorker;
+use crate::workers::stuck_job_reaper::StuckJobReaperWorker;
 use crate::workers::update_state::UpdateStateWorker;
 use crate::workers::Worker;
 
@@ -41,6 +50,12 @@ pub enum ConsumptionError {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JobQueueMessage {
     pub id: Uuid,
+    /// Trace context of the span which queued the job, continued by the consumer
+    #[serde(default, skip_serializing_if = "TraceContext::is_empty")]
+    pub trace_context: TraceContext,
+    /// Times the message was already put back in its queue after a transient error
+    #[serde(default)]
+    pub transient_retries: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Display)]
@@ -51,6 +66,7 @@ pub enum WorkerTriggerType {
     ProofRegistration,
     DataSubmission,
     UpdateState,
+    StuckJobReaper,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -74,6 +90,7 @@ impl FromStr for WorkerTriggerType {
             "ProofRegistration" => Ok(WorkerTriggerType::ProofRegistration),
             "DataSubmission" => Ok(WorkerTriggerType::DataSubmission),
             "UpdateState" => Ok(WorkerTriggerType::UpdateState),
+            "StuckJobReaper" => Ok(WorkerTriggerType::StuckJobReaper),
             _ => Err(WorkerTriggerTypeError::UnknownType(s.to_string())),
         }
     }
@@ -99,12 +116,6 @@ impl<'de> Deserialize<'de> for WorkerTriggerMessage {
     }
 }
 
-#[derive(Debug)]
-enum DeliveryReturnType {
-    Message(Delivery),
-    NoMessage,
-}
-
 pub trait QueueNameForJobType {
     fn process_queue_name(&self) -> QueueType;
     fn verify_queue_name(&self) -> QueueType;
@@ -112,22 +123,10 @@ pub trait QueueNameForJobType {
 
 impl QueueNameForJobType for JobType {
     fn process_queue_name(&self) -> QueueType {
-        match self {
-            JobType::SnosRun => QueueType::SnosJobProcessing,
-            JobType::ProofCreation => QueueType::ProvingJobProcessing,
-            JobType::ProofRegistration => QueueType::ProofRegistrationJobProcessing,
-            JobType::DataSubmission => QueueType::DataSubmissionJobProcessing,
-            JobType::StateTransition => QueueType::UpdateStateJobProcessing,
-        }
+        job_queue_registration(self).process_queue.clone()
     }
     fn verify_queue_name(&self) -> QueueType {
-        match self {
-            JobType::SnosRun => QueueType::SnosJobVerification,
-            JobType::ProofCreation => QueueType::ProvingJobVerification,
-            JobType::ProofRegistration => QueueType::ProofRegistrationJobVerification,
-            JobType::DataSubmission => QueueType::DataSubmissionJobVerification,
-            JobType::StateTransition => QueueType::UpdateStateJobVerification,
-        }
+        job_queue_registration(self).verify_queue.clone()
     }
 }
 
@@ -136,6 +135,16 @@ pub async fn add_job_to_process_queue(id: Uuid, job_type: &JobType, config: Arc<
     add_job_to_queue(id, job_type.process_queue_name(), None, config).await
 }
 
+pub async fn add_job_to_process_queue_with_delay(
+    id: Uuid,
+    job_type: &JobType,
+    delay: Duration,
+    config: Arc<Config>,
+) -> EyreResult<()> {
+    tracing::info!("Adding job with id {:?} to processing queue in {:?}", id, delay);
+    add_job_to_queue(id, job_type.process_queue_name(), Some(delay), config).await
+}
+
 pub async fn add_job_to_verification_queue(
     id: Uuid,
     job_type: &JobType,
@@ -146,74 +155,101 @@ pub async fn add_job_to_verification_queue(
     add_job_to_queue(id, job_type.verify_queue_name(), Some(delay), config).await
 }
 
-pub async fn consume_job_from_queue<F, Fut>(
+/// Spawns the handling of a job message received from `queue`. The handling keeps `permit` until
+/// it's done, so that the queue consumer doesn't receive more messages than it can handle.
+pub fn dispatch_job_message<F, Fut>(
     queue: QueueType,
+    message: Delivery,
     handler: F,
     config: Arc<Config>,
+    permit: OwnedSemaphorePermit,
 ) -> Result<(), ConsumptionError>
 where
     F: FnOnce(Uuid, Arc<Config>) -> Fut,
     F: Send + 'static,
     Fut: Future<Output = Result<(), JobError>> + Send,
 {
-    tracing::trace!(queue = %queue, "Attempting to consume job from queue");
-
-    let delivery = get_delivery_from_queue(queue.clone(), config.clone()).await?;
-
-    let message = match delivery {
-        DeliveryReturnType::Message(message) => {
-            tracing::debug!(queue = %queue, "Message received from queue");
-            message
-        }
-        DeliveryReturnType::NoMessage => {
-            tracing::debug!(queue = %queue, "No message in queue");
-            return Ok(());
-        }
-    };
-
     let job_message = parse_job_message(&message)?;
 
     if let Some(job_message) = job_message {
         tracing::info!(queue = %queue, job_id = %job_message.id, "Processing job message");
-        tokio::spawn(async move {
-            match handle_job_message(job_message, message, handler, config).await {
-                Ok(_) => {}
-                Err(e) => log::error!("Failed to handle job message. Error: {:?}", e),
+        // the job continues the trace of the span which queued it
+        let span = tracing::info_span!("consume_job_message", queue = %queue, job_id = %job_message.id);
+        continue_trace(&span, &job_message.trace_context);
+        // tracked so that the shutdown waits for the job
+        let shutdown = config.shutdown().clone();
+        shutdown.spawn(
+            async move {
+                let _permit = permit;
+                match handle_job_message(queue, job_message, message, handler, config).await {
+                    Ok(_) => {}
+                    Err(e) => log::error!("Failed to handle job message. Error: {:?}", e),
+                }
             }
-        });
+            .instrument(span),
+        );
     } else {
         tracing::warn!(queue = %queue, "Received empty job message");
     }
 
-    tracing::info!(queue = %queue, "Job consumption completed successfully");
     Ok(())
 }
 
-/// Function to consume the message from the worker trigger queues and spawn the worker
-/// for respective message received.
-pub async fn consume_worker_trigger_messages_from_queue<F, Fut>(
+/// Spawns the handling of a message received from the dead letter queue `queue`. The handling
+/// keeps `permit` until it's done.
+pub fn dispatch_dead_letter_message<F, Fut>(
     queue: QueueType,
+    message: DeadLetterMessage,
     handler: F,
     config: Arc<Config>,
+    permit: OwnedSemaphorePermit,
 ) -> Result<(), ConsumptionError>
 where
-    F: FnOnce(Box<dyn Worker>, Arc<Config>) -> Fut,
+    F: FnOnce(Uuid, DeadLetterSource, Arc<Config>) -> Fut,
     F: Send + 'static,
-    Fut: Future<Output = color_eyre::Result<()>> + Send,
+    Fut: Future<Output = Result<(), JobError>> + Send,
 {
-    tracing::debug!("Consuming from queue {:?}", queue);
-    let delivery = get_delivery_from_queue(queue, Arc::clone(&config)).await?;
+    let job_message: JobQueueMessage = serde_json::from_str(&message.payload)
+        .wrap_err("Payload Serde Error")
+        .map_err(|e| ConsumptionError::Other(OtherError::from(e)))?;
 
-    let message = match delivery {
-        DeliveryReturnType::Message(message) => message,
-        DeliveryReturnType::NoMessage => return Ok(()),
-    };
+    tracing::info!(queue = %queue, job_id = %job_message.id, source = ?message.source, "Processing dead letter");
+    let span = tracing::info_span!("consume_dead_letter", queue = %queue, job_id = %job_message.id);
+    continue_trace(&span, &job_message.trace_context);
+    let shutdown = config.shutdown().clone();
+    shutdown.spawn(
+        async move {
+            let _permit = permit;
+            if let Err(e) = handle_dead_letter_message(queue, job_message, message, handler, config).await {
+                tracing::error!("Failed to handle dead letter. Error: {:?}", e);
+            }
+        }
+        .instrument(span),
+    );
+    Ok(())
+}
 
+/// Spawns the worker of a message received from the worker trigger queue. The worker keeps
+/// `permit` until it's done.
+pub fn dispatch_worker_trigger_message<F, Fut>(
+    queue: QueueType,
+    message: Delivery,
+    handler: F,
+    config: Arc<Config>,
+    permit: OwnedSemaphorePermit,
+) -> Result<(), ConsumptionError>
+where
+    F: FnOnce(Box<dyn Worker>, Arc<Config>) -> Fut,
+    F: Send + 'static,
+    Fut: Future<Output = color_eyre::Result<()>> + Send,
+{
     let job_message = parse_worker_message(&message)?;
 
     if let Some(job_message) = job_message {
-        tokio::spawn(async move {
-            match handle_worker_message(job_message, message, handler, config).await {
+        let shutdown = config.shutdown().clone();
+        shutdown.spawn(async move {
+            let _permit = permit;
+            match handle_worker_message(queue, job_message, message, handler, config).await {
                 Ok(_) => {}
                 Err(e) => tracing::error!("Failed to handle worker message. Error: {:?}", e),
             }
@@ -244,8 +280,9 @@ fn parse_worker_message(message: &Delivery) -> Result<Option<WorkerTriggerMessag
 }
 
 async fn handle_job_message<F, Fut>(
+    queue: QueueType,
     job_message: JobQueueMessage,
-    message: Delivery,
+    mut message: Delivery,
     handler: F,
     config: Arc<Config>,
 ) -> Result<(), ConsumptionError>
@@ -255,42 +292,155 @@ where
 {
     tracing::info!("Handling job with id {:?}", job_message.id);
 
-    match handler(job_message.id, config.clone()).await {
-        Ok(_) => {
-            message
-                .ack()
-                .await
-                .map_err(|(e, _)| e)
-                .wrap_err("Queue Error")
-                .map_err(|e| ConsumptionError::Other(OtherError::from(e)))?;
-            Ok(())
+    let result = {
+        let keep_invisible = keep_message_invisible(&queue, &mut message, &config);
+        tokio::select! {
+            result = handler(job_message.id, config.clone()) => Some(result),
+            _ = keep_invisible => None,
+            _ = config.shutdown().deadline_reached() => None,
+        }
+    };
+    let Some(result) = result else {
+        // a job cut off while processing stays locked until the stuck job reaper picks it up
+        tracing::warn!(job_id = %job_message.id, "Shutdown deadline reached, giving the job message back to the queue");
+        return give_back_message(message).await;
+    };
+
+    match result {
+        Ok(_) => ack_message(queue, message, &config).await,
+        // the job message was put back in the queue already, the job waits for a processing slot
+        Err(JobError::MaxCapacityReached) => {
+            tracing::info!(job_id = %job_message.id, "Max capacity reached, the job is processed later");
+            ack_message(queue, message, &config).await
+        }
+        Err(e @ JobError::LockError(_)) => retry_job_message(queue, job_message, message, e, false, config).await,
+        Err(e) if e.is_transient() && job_message.transient_retries < transient_retries(&queue, &config) => {
+            retry_job_message(queue, job_message, message, e, true, config).await
         }
         Err(e) => {
             tracing::error!("Failed to handle job with id {:?}. Error: {:?}", job_message.id, e);
-            config
-                .alerts()
-                .send_alert_message(e.to_string())
-                .await
-                .map_err(|e| ConsumptionError::Other(OtherError::from(e)))?;
+            let mut alerted = false;
+            if e.is_transient() && is_processing_queue(&queue) {
+                match fail_job_after_transient_retries(job_message.id, &e, config.clone()).await {
+                    // failing the job already alerted on it
+                    Ok(failed) => alerted = failed,
+                    Err(fail_error) => {
+                        tracing::error!(job_id = %job_message.id, error = ?fail_error, "Failed to fail the job")
+                    }
+                }
+            }
+            if !alerted {
+                let alert = Alert { job_id: Some(job_message.id), ..Alert::new(AlertSeverity::Error, e.to_string()) };
+                config.alerts().send_alert(&alert).await.map_err(|e| ConsumptionError::Other(OtherError::from(e)))?;
+            }
 
-            // not using `nack` as we dont' want retries in case of failures
-            match message.ack().await {
+            // not using `nack` as permanent errors won't go away with retries
+            match ack_message(queue, message, &config).await {
                 Ok(_) => Err(ConsumptionError::FailedToHandleJob {
                     job_id: job_message.id,
                     error_msg: "Job handling failed, message nack-ed".to_string(),
                 }),
                 Err(delivery_nack_error) => Err(ConsumptionError::FailedToHandleJob {
                     job_id: job_message.id,
-                    error_msg: delivery_nack_error.0.to_string(),
+                    error_msg: delivery_nack_error.to_string(),
                 }),
             }
         }
     }
 }
 
+/// The number of times a job message of `queue` is put back in the queue after a transient error.
+fn transient_retries(queue: &QueueType, config: &Config) -> usize {
+    config.service_config().queue_consumers.transient_retries(queue)
+}
+
+/// Whether `queue` is the processing queue of a job type.
+fn is_processing_queue(queue: &QueueType) -> bool {
+    job_queue_registration_of_queue(queue).is_some_and(|registration| &registration.process_queue == queue)
+}
+
+/// Puts a job message back in its queue after the transient error `error`, with a delay growing
+/// with its retries, and acks the received one. The received message is nacked instead if it
+/// can't be put back, so that it's received again after its visibility timeout.
+///
+/// The retry counts against the transient retries of the message only if `counted`.
+async fn retry_job_message(
+    queue: QueueType,
+    job_message: JobQueueMessage,
+    message: Delivery,
+    error: JobError,
+    counted: bool,
+    config: Arc<Config>,
+) -> Result<(), ConsumptionError> {
+    let retry_policy = config.service_config().queue_consumers.transient_retry_policy();
+    let delay = retry_policy.delay(job_message.transient_retries as u64);
+    tracing::warn!(
+        queue = %queue,
+        job_id = %job_message.id,
+        transient_retries = job_message.transient_retries,
+        error = %error,
+        "Transient error while handling the job, retrying in {:?}",
+        delay
+    );
+
+    let retry = JobQueueMessage {
+        id: job_message.id,
+        trace_context: current_trace_context(),
+        transient_retries: job_message.transient_retries + usize::from(counted),
+    };
+    if let Err(e) = send_job_message(&retry, queue.clone(), Some(delay), &config).await {
+        tracing::error!(queue = %queue, job_id = %job_message.id, error = ?e, "Failed to put the job message back");
+        return give_back_message(message).await;
+    }
+    ack_message(queue, message, &config).await
+}
+
+/// Hands a dead letter to `handler` along with where it comes from. The dead letter is received
+/// again after a transient error, or if the shutdown cuts its handling off.
+async fn handle_dead_letter_message<F, Fut>(
+    queue: QueueType,
+    job_message: JobQueueMessage,
+    message: DeadLetterMessage,
+    handler: F,
+    config: Arc<Config>,
+) -> Result<(), ConsumptionError>
+where
+    F: FnOnce(Uuid, DeadLetterSource, Arc<Config>) -> Fut,
+    Fut: Future<Output = Result<(), JobError>>,
+{
+    let result = tokio::select! {
+        result = handler(job_message.id, message.source, config.clone()) => Some(result),
+        _ = config.shutdown().deadline_reached() => None,
+    };
+    let handled = match result {
+        Some(Ok(_)) => true,
+        Some(Err(e)) if e.is_transient() => {
+            tracing::warn!(job_id = %job_message.id, error = %e, "Transient error while handling the dead letter");
+            false
+        }
+        Some(Err(e)) => {
+            tracing::error!("Failed to handle dead letter of job with id {:?}. Error: {:?}", job_message.id, e);
+            let alert = Alert { job_id: Some(job_message.id), ..Alert::new(AlertSeverity::Error, e.to_string()) };
+            config.alerts().send_alert(&alert).await.map_err(|e| ConsumptionError::Other(OtherError::from(e)))?;
+            // not kept as permanent errors won't go away with retries
+            true
+        }
+        None => {
+            tracing::warn!(job_id = %job_message.id, "Shutdown deadline reached, giving the dead letter back");
+            false
+        }
+    };
+    config
+        .queue()
+        .settle_dead_letter_message(queue, &message.receipt, handled)
+        .await
+        .map_err(|e| ConsumptionError::Other(OtherError::from(e)))
+}
+
 async fn handle_worker_message<F, Fut>(
+    queue: QueueType,
     job_message: WorkerTriggerMessage,
-    message: Delivery,
+    mut message: Delivery,
     handler: F,
     config: Arc<Config>,
 ) -> Result<(), ConsumptionError>
@@ -300,16 +450,24 @@ where
 {
     let worker_handler = get_worker_handler_from_worker_trigger_type(job_message.worker.clone());
 
-    match handler(worker_handler, config.clone()).await {
-        Ok(_) => {
-            message
-                .ack()
-                .await
-                .map_err(|(e, _)| e)
-                .wrap_err("Queue Error")
-                .map_err(|e| ConsumptionError::Other(OtherError::from(e)))?;
-            Ok(())
+    let result = {
+        let keep_invisible = keep_message_invisible(&queue, &mut message, &config);
+        tokio::select! {
+            result = handler(worker_handler, config.clone()) => Some(result),
+            _ = keep_invisible => None,
+            _ = config.shutdown().deadline_reached() => None,
         }
+    };
+    let Some(result) = result else {
+        tracing::warn!(
+            worker = ?job_message.worker,
+            "Shutdown deadline reached, giving the worker trigger back to the queue"
+        );
+        return give_back_message(message).await;
+    };
+
+    match result {
+        Ok(_) => ack_message(queue, message, &config).await,
         Err(e) => {
             tracing::error!("Failed to handle worker trigger {:?}. Error: {:?}", job_message.worker, e);
             config
@@ -319,7 +477,7 @@ where
                 .map_err(|e| ConsumptionError::Other(OtherError::from(e)))?;
 
             // not using `nack` as we dont' want retries in case of failures
-            message.ack().await.map_err(|(e, _)| ConsumptionError::Other(OtherError::from(e.to_string())))?;
+            ack_message(queue, message, &config).await?;
             Err(ConsumptionError::FailedToSpawnWorker {
                 worker_trigger_type: job_message.worker,
                 error_msg: "Worker handling failed, message nack-ed".to_string(),
@@ -328,60 +486,93 @@ where
     }
 }
 
+/// Extends the visibility timeout of `message` every half timeout for as long as it's handled, so
+/// that a long job, e.g. a state update waiting for its L1 transaction, isn't received again by
+/// another consumer in the middle of it. Never resolves.
+async fn keep_message_invisible(queue: &QueueType, message: &mut Delivery, config: &Config) {
+    let visibility_timeout = visibility_timeout(queue);
+    let period = (visibility_timeout / 2).max(Duration::from_secs(1));
+    let mut ticker = tokio::time::interval_at(Instant::now() + period, period);
+    loop {
+        ticker.tick().await;
+        if let Err(e) = config.queue().extend_message_visibility(message, visibility_timeout).await {
+            tracing::warn!(queue = %queue, error = ?e, "Failed to extend the visibility timeout of the message");
+        }
+    }
+}
+
+/// Acks a message of `queue` through the queue provider, which may track the messages in flight.
+async fn ack_message(queue: QueueType, message: Delivery, config: &Config) -> Result<(), ConsumptionError> {
+    config
+        .queue()
+        .ack_message(queue, message)
+        .await
+        .wrap_err("Queue Error")
+        .map_err(|e| ConsumptionError::Other(OtherError::from(e)))
+}
+
+/// Nacks a message which couldn't be handled, e.g. before the shutdown, so that it's received
+/// again.
+async fn give_back_message(message: Delivery) -> Result<(), ConsumptionError> {
+    message.nack().await.map_err(|(e, _)| ConsumptionError::Other(OtherError::from(e.to_string())))
+}
+
 /// To get Box<dyn Worker> handler from `WorkerTriggerType`.
-fn get_worker_handler_from_worker_trigger_type(worker_trigger_type: WorkerTriggerType) -> Box<dyn Worker> {
+pub(crate) fn get_worker_handler_from_worker_trigger_type(worker_trigger_type: WorkerTriggerType) -> Box<dyn Worker> {
     match worker_trigger_type {
         WorkerTriggerType::Snos => Box::new(SnosWorker),
         WorkerTriggerType::Proving => Box::new(ProvingWorker),
         WorkerTriggerType::DataSubmission => Box::new(DataSubmissionWorker),
         WorkerTriggerType::ProofRegistration => Box::new(ProofRegistrationWorker),
         WorkerTriggerType::UpdateState => Box::new(UpdateStateWorker),
-    }
-}
-
-/// To get the delivery from the message queue using the queue name
-async fn get_delivery_from_queue(
-    queue: QueueType,
-    config: Arc<Config>,
-) -> Result<DeliveryReturnType, ConsumptionError> {
-    match config.queue().consume_message_from_queue(queue).await {
-        Ok(d) => Ok(DeliveryReturnType::Message(d)),
-        Err(QueueError::NoData) => Ok(DeliveryReturnType::NoMessage),
-        Err(e) => Err(ConsumptionError::FailedToConsumeFromQueue { error_msg: e.to_string() }),
+        WorkerTriggerType::StuckJobReaper => Box::new(StuckJobReaperWorker),
     }
 }
 
 macro_rules! spawn_consumer {
-    ($queue_type:expr, $handler:expr, $consume_function:expr, $config:expr) => {
+    ($queue_type:expr, $handler:expr, $dispatch_function:expr, $receive_function:expr, $config:expr) => {
+        let queue_type = $queue_type;
         let config_clone = $config.clone();
-        tokio::spawn(async move {
-            loop {
-                match $consume_function($queue_type, $handler, config_clone.clone()).await {
-                    Ok(_) => {}
-                    Err(e) => tracing::error!("Failed to consume from queue {:?}. Error: {:?}", $queue_type, e),
-                }
-                sleep(Duration::from_millis(500)).await;
-            }
-        });
+        tokio::spawn(run_consumer(queue_type.clone(), $config.clone(), $receive_function, move |message, permit| {
+            $dispatch_function(queue_type.clone(), message, $handler, config_clone.clone(), permit)
+        }));
     };
 }
 
+/// Spawns a consumer for every registered queue, except the ones disabled on this replica.
 pub async fn init_consumers(config: Arc<Config>) -> Result<(), JobError> {
-    spawn_consumer!(QueueType::SnosJobProcessing, process_job, consume_job_from_queue, config.clone());
-    spawn_consumer!(QueueType::SnosJobVerification, verify_job, consume_job_from_queue, config.clone());
-
-    spawn_consumer!(QueueType::ProvingJobProcessing, process_job, consume_job_from_queue, config.clone());
-    spawn_consumer!(QueueType::ProvingJobVerification, verify_job, consume_job_from_queue, config.clone());
-
-    spawn_consumer!(QueueType::DataSubmissionJobProcessing, process_job, consume_job_from_queue, config.clone());
-    spawn_consumer!(QueueType::DataSubmissionJobVerification, verify_job, consume_job_from_queue, config.clone());
-
-    spawn_consumer!(QueueType::UpdateStateJobProcessing, process_job, consume_job_from_queue, config.clone());
-    spawn_consumer!(QueueType::UpdateStateJobVerification, verify_job, consume_job_from_queue, config.clone());
-
-    spawn_consumer!(QueueType::JobHandleFailure, handle_job_failure, consume_job_from_queue, config.clone());
-
-    spawn_consumer!(QueueType::WorkerTrigger, spawn_worker, consume_worker_trigger_messages_from_queue, config);
+    for consumer in consumers() {
+        if !config.service_config().queue_consumers.is_enabled(&consumer.queue) {
+            tracing::info!(queue = %consumer.queue, "Consumer disabled on this replica");
+            continue;
+        }
+        match consumer.handler {
+            ConsumerHandler::ProcessJob => {
+                spawn_consumer!(consumer.queue, process_job, dispatch_job_message, receive_deliveries, config);
+            }
+            ConsumerHandler::VerifyJob => {
+                spawn_consumer!(consumer.queue, verify_job, dispatch_job_message, receive_deliveries, config);
+            }
+            ConsumerHandler::HandleJobFailure => {
+                spawn_consumer!(
+                    consumer.queue,
+                    handle_job_failure,
+                    dispatch_dead_letter_message,
+                    receive_dead_letters,
+                    config
+                );
+            }
+            ConsumerHandler::Worker => {
+                spawn_consumer!(
+                    consumer.queue,
+                    spawn_worker,
+                    dispatch_worker_trigger_message,
+                    receive_deliveries,
+                    config
+                );
+            }
+        }
+    }
     Ok(())
 }
 
@@ -394,8 +585,8 @@ async fn spawn_worker(worker: Box<dyn Worker>, config: Arc<Config>) -> color_eyr
     Ok(())
 }
 async fn add_job_to_queue(id: Uuid, queue: QueueType, delay: Option<Duration>, config: Arc<Config>) -> EyreResult<()> {
-    let message = JobQueueMessage { id };
-    config.queue().send_message_to_queue(queue.clone(), serde_json::to_string(&message)?, delay).await?;
+    let message = JobQueueMessage { id, trace_context: current_trace_context(), transient_retries: 0 };
+    send_job_message(&message, queue.clone(), delay, &config).await?;
     tracing::info!(
         log_type = "JobQueue",
         category = "add_job_to_queue",
@@ -406,3 +597,13 @@ async fn add_job_to_queue(id: Uuid, queue: QueueType, delay: Option<Duration>, c
     );
     Ok(())
 }
+
+async fn send_job_message(
+    message: &JobQueueMessage,
+    queue: QueueType,
+    delay: Option<Duration>,
+    config: &Config,
+) -> EyreResult<()> {
+    config.queue().send_message_to_queue(queue, serde_json::to_string(message)?, delay).await?;
+    Ok(())
+}
@@ -1,4 +1,9 @@
+pub mod amqp;
+pub mod consumer;
+pub mod dead_letter;
+pub mod in_memory;
 pub mod job_queue;
+pub mod registry;
 pub mod sqs;
 
 use std::sync::Arc;
@@ -9,12 +14,15 @@ use color_eyre::Result as EyreResult;
 use lazy_static::lazy_static;
 use mockall::automock;
 use omniqueue::{Delivery, QueueError};
+use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumIter};
 
+use self::registry::JOB_QUEUES;
 use crate::config::Config;
 use crate::jobs::JobError;
 
-#[derive(Display, Debug, Clone, PartialEq, Eq, EnumIter)]
+#[derive(Display, Debug, Clone, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum QueueType {
     #[strum(serialize = "snos_job_processing")]
     SnosJobProcessing,
@@ -55,52 +63,77 @@ pub struct QueueConfig {
     pub dlq_config: Option<DlqConfig>,
 }
 
-// TODO: use QueueType::iter() or format!
+/// How many times a job message can be received before being moved to the failure handling queue.
+const JOB_QUEUE_MAX_RECEIVE_COUNT: i32 = 5;
+
 lazy_static! {
-    pub static ref QUEUES: Vec<QueueConfig> = vec![
-        QueueConfig { name: QueueType::JobHandleFailure, visibility_timeout: 300, dlq_config: None },
-        QueueConfig {
-            name: QueueType::SnosJobProcessing,
-            visibility_timeout: 300,
-            dlq_config: Some(DlqConfig { max_receive_count: 5, dlq_name: QueueType::JobHandleFailure })
-        },
-        QueueConfig {
-            name: QueueType::SnosJobVerification,
-            visibility_timeout: 300,
-            dlq_config: Some(DlqConfig { max_receive_count: 5, dlq_name: QueueType::JobHandleFailure })
-        },
-        QueueConfig {
-            name: QueueType::ProvingJobProcessing,
-            visibility_timeout: 300,
-            dlq_config: Some(DlqConfig { max_receive_count: 5, dlq_name: QueueType::JobHandleFailure })
-        },
-        QueueConfig {
-            name: QueueType::ProvingJobVerification,
-            visibility_timeout: 300,
-            dlq_config: Some(DlqConfig { max_receive_count: 5, dlq_name: QueueType::JobHandleFailure })
-        },
-        QueueConfig {
-            name: QueueType::DataSubmissionJobProcessing,
-            visibility_timeout: 300,
-            dlq_config: Some(DlqConfig { max_receive_count: 5, dlq_name: QueueType::JobHandleFailure })
-        },
-        QueueConfig {
-            name: QueueType::DataSubmissionJobVerification,
-            visibility_timeout: 300,
-            dlq_config: Some(DlqConfig { max_receive_count: 5, dlq_name: QueueType::JobHandleFailure })
-        },
-        QueueConfig {
-            name: QueueType::UpdateStateJobProcessing,
-            visibility_timeout: 900,
-            dlq_config: Some(DlqConfig { max_receive_count: 5, dlq_name: QueueType::JobHandleFailure })
-        },
-        QueueConfig {
-            name: QueueType::UpdateStateJobVerification,
-            visibility_timeout: 300,
-            dlq_config: Some(DlqConfig { max_receive_count: 5, dlq_name: QueueType::JobHandleFailure })
-        },
-        QueueConfig { name: QueueType::WorkerTrigger, visibility_timeout: 300, dlq_config: None },
-    ];
+    /// The queues created by the `setup` command. The failure handling queue comes first, as the
+    /// job queues move their dead letters to it.
+    pub static ref QUEUES: Vec<QueueConfig> = {
+        let job_queue = |name: &QueueType, visibility_timeout: i32| QueueConfig {
+            name: name.clone(),
+            visibility_timeout,
+            dlq_config: Some(DlqConfig {
+                max_receive_count: JOB_QUEUE_MAX_RECEIVE_COUNT,
+                dlq_name: QueueType::JobHandleFailure,
+            }),
+        };
+        let mut queues =
+            vec![QueueConfig { name: QueueType::JobHandleFailure, visibility_timeout: 300, dlq_config: None }];
+        for registration in JOB_QUEUES.iter() {
+            queues.push(job_queue(&registration.process_queue, registration.process_visibility_timeout));
+            queues.push(job_queue(&registration.verify_queue, registration.verify_visibility_timeout));
+        }
+        queues.push(QueueConfig { name: QueueType::WorkerTrigger, visibility_timeout: 300, dlq_config: None });
+        queues
+    };
+}
+
+/// How long a message received from `queue` stays hidden from the other consumers.
+pub fn visibility_timeout(queue: &QueueType) -> Duration {
+    let visibility_timeout =
+        QUEUES.iter().find(|config| &config.name == queue).map_or(300, |config| config.visibility_timeout);
+    Duration::from_secs(visibility_timeout.max(0) as u64)
+}
+
+/// How many times a message can be received from `queue` before being moved to its dead letter
+/// queue, if it has one.
+pub fn max_receive_count(queue: &QueueType) -> Option<i32> {
+    QUEUES
+        .iter()
+        .find(|config| &config.name == queue)
+        .and_then(|config| config.dlq_config.as_ref())
+        .map(|dlq_config| dlq_config.max_receive_count)
+}
+
+/// Number of messages in a queue, by state. SQS only gives approximate numbers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueDepth {
+    /// Messages ready to be consumed
+    pub visible: u64,
+    /// Messages consumed but not acknowledged yet
+    pub in_flight: u64,
+    /// Messages sent with a delay which didn't pass yet
+    pub delayed: u64,
+}
+
+/// What the broker tells about the queue a message was dead lettered from, `None` where it
+/// doesn't tell.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeadLetterSource {
+    /// The queue the message was moved from
+    pub queue: Option<QueueType>,
+    /// Times the message was received from that queue before being moved
+    pub receive_count: Option<i32>,
+}
+
+/// A message received from a dead letter queue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetterMessage {
+    pub payload: String,
+    pub source: DeadLetterSource,
+    /// Identifies the message when settling it
+    pub receipt: String,
 }
 
 /// Queue Provider Trait
@@ -114,7 +147,45 @@ pub trait QueueProvider: Send + Sync {
     async fn send_message_to_queue(&self, queue: QueueType, payload: String, delay: Option<Duration>)
     -> EyreResult<()>;
     async fn consume_message_from_queue(&self, queue: QueueType) -> std::result::Result<Delivery, QueueError>;
+    /// Receives up to `max_messages` messages at once, waiting up to `wait` for the first one to
+    /// arrive. Returns no message if the queue stayed empty.
+    async fn consume_messages_from_queue(
+        &self,
+        queue: QueueType,
+        _max_messages: usize,
+        _wait: Duration,
+    ) -> std::result::Result<Vec<Delivery>, QueueError> {
+        // one message at a time without waiting, unless the queue receives batches
+        match self.consume_message_from_queue(queue).await {
+            Ok(delivery) => Ok(vec![delivery]),
+            Err(QueueError::NoData) => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+    /// Acknowledges `message`, received from `queue`, so that it's never received again.
+    async fn ack_message(&self, _queue: QueueType, message: Delivery) -> EyreResult<()> {
+        message.ack().await.map_err(|(e, _)| e)?;
+        Ok(())
+    }
+    /// Keeps `message` hidden from the other consumers for `timeout` from now on, while it's
+    /// still being handled.
+    async fn extend_message_visibility(&self, message: &mut Delivery, timeout: Duration) -> EyreResult<()> {
+        message.set_ack_deadline(timeout).await?;
+        Ok(())
+    }
+    /// Receives up to `max_messages` messages of the dead letter queue `queue`, along with where
+    /// they were dead lettered from. Returns no message if the queue stayed empty.
+    async fn consume_dead_letter_messages(
+        &self,
+        queue: QueueType,
+        max_messages: usize,
+        wait: Duration,
+    ) -> EyreResult<Vec<DeadLetterMessage>>;
+    /// Settles a message received with `consume_dead_letter_messages`. It's deleted if `handled`,
+    /// and received again later otherwise.
+    async fn settle_dead_letter_message(&self, queue: QueueType, receipt: &str, handled: bool) -> EyreResult<()>;
     async fn create_queue(&self, queue_config: &QueueConfig) -> EyreResult<()>;
+    async fn get_queue_depth(&self, queue: QueueType) -> EyreResult<QueueDepth>;
     async fn setup(&self) -> EyreResult<()> {
         // Creating the queues :
         for queue in QUEUES.iter() {
@@ -0,0 +1,144 @@
+use lazy_static::lazy_static;
+
+use super::job_queue::WorkerTriggerType;
+use super::QueueType;
+use crate::jobs::types::JobType;
+
+/// What a job type needs from the queues. Every registered job type gets its processing and
+/// verification queues created and consumed, and its worker triggered.
+#[derive(Debug, Clone)]
+pub struct JobQueueRegistration {
+    pub job_type: JobType,
+    pub process_queue: QueueType,
+    pub verify_queue: QueueType,
+    /// Seconds a message of the processing queue stays hidden from the other consumers
+    pub process_visibility_timeout: i32,
+    /// Seconds a message of the verification queue stays hidden from the other consumers
+    pub verify_visibility_timeout: i32,
+    /// Messages of the processing queue handled at once by a replica, instead of the configured
+    /// default concurrency. The concurrency overrides still take precedence.
+    pub process_concurrency: Option<usize>,
+    /// The worker creating the jobs of this type
+    pub worker_trigger: WorkerTriggerType,
+}
+
+/// The handler a consumer hands the messages of its queue to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsumerHandler {
+    ProcessJob,
+    VerifyJob,
+    HandleJobFailure,
+    Worker,
+}
+
+/// A queue consumed by the orchestrator, along with the handler of its messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerRegistration {
+    pub queue: QueueType,
+    pub handler: ConsumerHandler,
+}
+
+lazy_static! {
+    pub static ref JOB_QUEUES: Vec<JobQueueRegistration> = vec![
+        JobQueueRegistration {
+            job_type: JobType::SnosRun,
+            process_queue: QueueType::SnosJobProcessing,
+            verify_queue: QueueType::SnosJobVerification,
+            process_visibility_timeout: 300,
+            verify_visibility_timeout: 300,
+            process_concurrency: None,
+            worker_trigger: WorkerTriggerType::Snos,
+        },
+        JobQueueRegistration {
+            job_type: JobType::ProofCreation,
+            process_queue: QueueType::ProvingJobProcessing,
+            verify_queue: QueueType::ProvingJobVerification,
+            process_visibility_timeout: 300,
+            verify_visibility_timeout: 300,
+            process_concurrency: None,
+            worker_trigger: WorkerTriggerType::Proving,
+        },
+        JobQueueRegistration {
+            job_type: JobType::ProofRegistration,
+            process_queue: QueueType::ProofRegistrationJobProcessing,
+            verify_queue: QueueType::ProofRegistrationJobVerification,
+            process_visibility_timeout: 300,
+            verify_visibility_timeout: 300,
+            process_concurrency: None,
+            worker_trigger: WorkerTriggerType::ProofRegistration,
+        },
+        JobQueueRegistration {
+            job_type: JobType::DataSubmission,
+            process_queue: QueueType::DataSubmissionJobProcessing,
+            verify_queue: QueueType::DataSubmissionJobVerification,
+            process_visibility_timeout: 300,
+            verify_visibility_timeout: 300,
+            process_concurrency: None,
+            worker_trigger: WorkerTriggerType::DataSubmission,
+        },
+        JobQueueRegistration {
+            job_type: JobType::StateTransition,
+            process_queue: QueueType::UpdateStateJobProcessing,
+            verify_queue: QueueType::UpdateStateJobVerification,
+            process_visibility_timeout: 900,
+            verify_visibility_timeout: 300,
+            // parallel state updates can cause nonce issues on the settlement layer
+            process_concurrency: Some(1),
+            worker_trigger: WorkerTriggerType::UpdateState,
+        },
+    ];
+}
+
+/// The registration of `job_type`.
+pub fn job_queue_registration(job_type: &JobType) -> &'static JobQueueRegistration {
+    JOB_QUEUES
+        .iter()
+        .find(|registration| &registration.job_type == job_type)
+        .unwrap_or_else(|| panic!("No queues registered for the {:?} job type", job_type))
+}
+
+/// The registration of the job type `queue` is the processing or verification queue of.
+pub fn job_queue_registration_of_queue(queue: &QueueType) -> Option<&'static JobQueueRegistration> {
+    JOB_QUEUES.iter().find(|registration| &registration.process_queue == queue || &registration.verify_queue == queue)
+}
+
+/// Every queue consumed by the orchestrator: the queues of the registered job types, the failure
+/// handling queue and the worker trigger queue.
+pub fn consumers() -> Vec<ConsumerRegistration> {
+    let consumer = |queue: &QueueType, handler| ConsumerRegistration { queue: queue.clone(), handler };
+    let mut consumers = Vec::new();
+    for registration in JOB_QUEUES.iter() {
+        consumers.push(consumer(&registration.process_queue, ConsumerHandler::ProcessJob));
+        consumers.push(consumer(&registration.verify_queue, ConsumerHandler::VerifyJob));
+    }
+    consumers.push(consumer(&QueueType::JobHandleFailure, ConsumerHandler::HandleJobFailure));
+    consumers.push(consumer(&QueueType::WorkerTrigger, ConsumerHandler::Worker));
+    consumers
+}
+
+#[cfg(test)]
+mod registry_test {
+    use std::collections::HashSet;
+
+    use strum::IntoEnumIterator as _;
+
+    use super::*;
+    use crate::queue::QUEUES;
+
+    #[test]
+    fn test_every_job_type_is_registered() {
+        for job_type in JobType::iter() {
+            assert_eq!(job_queue_registration(&job_type).job_type, job_type);
+        }
+    }
+
+    #[test]
+    fn test_every_queue_is_created_and_consumed_once() {
+        let created: Vec<_> = QUEUES.iter().map(|queue| queue.name.clone()).collect();
+        let consumed: Vec<_> = consumers().into_iter().map(|consumer| consumer.queue).collect();
+        for queues in [created, consumed] {
+            assert_eq!(queues.len(), QueueType::iter().count());
+            assert_eq!(queues.into_iter().collect::<HashSet<_>>(), QueueType::iter().collect());
+        }
+    }
+}
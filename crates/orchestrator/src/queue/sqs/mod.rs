@@ -1,19 +1,23 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use aws_config::SdkConfig;
-use aws_sdk_sqs::types::QueueAttributeName;
+use aws_sdk_sqs::types::{MessageSystemAttributeName, QueueAttributeName};
 use aws_sdk_sqs::Client;
 use color_eyre::eyre::eyre;
 use color_eyre::Result;
 use omniqueue::backends::{SqsBackend, SqsConfig, SqsConsumer, SqsProducer};
 use omniqueue::{Delivery, QueueError};
 use serde::Serialize;
+use strum::IntoEnumIterator as _;
+use tokio::sync::Mutex;
 use url::Url;
 
+use super::consumer::{MAX_RECEIVE_BATCH_SIZE, MAX_RECEIVE_WAIT};
 use super::QueueType;
-use crate::queue::{QueueConfig, QueueProvider};
+use crate::queue::{DeadLetterMessage, DeadLetterSource, QueueConfig, QueueDepth, QueueProvider};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct AWSSQSValidatedArgs {
@@ -27,6 +31,10 @@ pub struct SqsQueue {
     queue_base_url: Url,
     sqs_prefix: String,
     sqs_suffix: String,
+    /// Producers and consumers are built once per queue and kept for the lifetime of the
+    /// orchestrator, they hold their own SQS client
+    producers: Mutex<HashMap<QueueType, Arc<SqsProducer>>>,
+    consumers: Mutex<HashMap<QueueType, Arc<Mutex<SqsConsumer>>>>,
 }
 
 impl SqsQueue {
@@ -38,6 +46,8 @@ impl SqsQueue {
             queue_base_url: params.queue_base_url,
             sqs_prefix: params.sqs_prefix,
             sqs_suffix: params.sqs_suffix,
+            producers: Mutex::new(HashMap::new()),
+            consumers: Mutex::new(HashMap::new()),
         }
     }
 
@@ -49,14 +59,58 @@ impl SqsQueue {
     pub fn get_queue_name(&self, queue_type: QueueType) -> String {
         format!("{}_{}_{}", self.sqs_prefix, queue_type, self.sqs_suffix)
     }
+
+    /// The queue named `queue_name`, if it's one of the orchestrator queues.
+    fn get_queue_type(&self, queue_name: &str) -> Option<QueueType> {
+        QueueType::iter().find(|queue_type| self.get_queue_name(queue_type.clone()) == queue_name)
+    }
+
+    /// The source of a message out of its `DeadLetterQueueSourceArn` and `ApproximateReceiveCount`
+    /// attributes.
+    fn get_dead_letter_source(&self, attributes: &HashMap<MessageSystemAttributeName, String>) -> DeadLetterSource {
+        DeadLetterSource {
+            // the ARN ends with the queue name
+            queue: attributes
+                .get(&MessageSystemAttributeName::DeadLetterQueueSourceArn)
+                .and_then(|arn| arn.rsplit(':').next())
+                .and_then(|queue_name| self.get_queue_type(queue_name)),
+            receive_count: attributes
+                .get(&MessageSystemAttributeName::ApproximateReceiveCount)
+                .and_then(|receive_count| receive_count.parse().ok()),
+        }
+    }
+
+    fn get_sqs_config(&self, queue_type: QueueType) -> SqsConfig {
+        SqsConfig { queue_dsn: self.get_queue_url(queue_type), override_endpoint: true }
+    }
+
+    async fn get_producer(&self, queue: QueueType) -> Result<Arc<SqsProducer>> {
+        let mut producers = self.producers.lock().await;
+        if let Some(producer) = producers.get(&queue) {
+            return Ok(producer.clone());
+        }
+        let producer = Arc::new(SqsBackend::builder(self.get_sqs_config(queue.clone())).build_producer().await?);
+        producers.insert(queue, producer.clone());
+        Ok(producer)
+    }
+
+    async fn get_consumer(&self, queue: QueueType) -> std::result::Result<Arc<Mutex<SqsConsumer>>, QueueError> {
+        let mut consumers = self.consumers.lock().await;
+        if let Some(consumer) = consumers.get(&queue) {
+            return Ok(consumer.clone());
+        }
+        let consumer =
+            Arc::new(Mutex::new(SqsBackend::builder(self.get_sqs_config(queue.clone())).build_consumer().await?));
+        consumers.insert(queue, consumer.clone());
+        Ok(consumer)
+    }
 }
 
 #[allow(unreachable_patterns)]
 #[async_trait]
 impl QueueProvider for SqsQueue {
     async fn send_message_to_queue(&self, queue: QueueType, payload: String, delay: Option<Duration>) -> Result<()> {
-        let queue_url = self.get_queue_url(queue);
-        let producer = get_producer(queue_url).await?;
+        let producer = self.get_producer(queue).await?;
 
         match delay {
             Some(d) => producer.send_raw_scheduled(payload.as_str(), d).await?,
@@ -67,11 +121,67 @@ impl QueueProvider for SqsQueue {
     }
 
     async fn consume_message_from_queue(&self, queue: QueueType) -> std::result::Result<Delivery, QueueError> {
-        let queue_url = self.get_queue_url(queue);
-        let mut consumer = get_consumer(queue_url).await?;
+        let consumer = self.get_consumer(queue).await?;
+        let mut consumer = consumer.lock().await;
         consumer.receive().await
     }
 
+    /// Long polls the queue, SQS returns at most 10 messages and waits at most 20 seconds.
+    async fn consume_messages_from_queue(
+        &self,
+        queue: QueueType,
+        max_messages: usize,
+        wait: Duration,
+    ) -> std::result::Result<Vec<Delivery>, QueueError> {
+        let consumer = self.get_consumer(queue).await?;
+        let mut consumer = consumer.lock().await;
+        consumer.receive_all(max_messages.clamp(1, MAX_RECEIVE_BATCH_SIZE), wait.min(MAX_RECEIVE_WAIT)).await
+    }
+
+    /// Receives the messages along with the attributes telling where they come from, which the
+    /// omniqueue consumers leave out.
+    async fn consume_dead_letter_messages(
+        &self,
+        queue: QueueType,
+        max_messages: usize,
+        wait: Duration,
+    ) -> Result<Vec<DeadLetterMessage>> {
+        let output = self
+            .client
+            .receive_message()
+            .queue_url(self.get_queue_url(queue))
+            .max_number_of_messages(max_messages.clamp(1, MAX_RECEIVE_BATCH_SIZE) as i32)
+            .wait_time_seconds(wait.min(MAX_RECEIVE_WAIT).as_secs() as i32)
+            .message_system_attribute_names(MessageSystemAttributeName::DeadLetterQueueSourceArn)
+            .message_system_attribute_names(MessageSystemAttributeName::ApproximateReceiveCount)
+            .send()
+            .await?;
+
+        let mut messages = Vec::new();
+        for message in output.messages() {
+            messages.push(DeadLetterMessage {
+                payload: message.body().unwrap_or_default().to_string(),
+                source: message
+                    .attributes()
+                    .map(|attributes| self.get_dead_letter_source(attributes))
+                    .unwrap_or_default(),
+                receipt: message
+                    .receipt_handle()
+                    .ok_or_else(|| eyre!("Message received without a receipt handle"))?
+                    .to_string(),
+            });
+        }
+        Ok(messages)
+    }
+
+    /// A message which wasn't handled is received again once its visibility timeout expires.
+    async fn settle_dead_letter_message(&self, queue: QueueType, receipt: &str, handled: bool) -> Result<()> {
+        if handled {
+            self.client.delete_message().queue_url(self.get_queue_url(queue)).receipt_handle(receipt).send().await?;
+        }
+        Ok(())
+    }
+
     async fn create_queue(&self, queue_config: &QueueConfig) -> Result<()> {
         let res = self.client.create_queue().queue_name(self.get_queue_name(queue_config.name.clone())).send().await?;
         let queue_url = res.queue_url().ok_or_else(|| eyre!("Not able to get queue url from result"))?;
@@ -97,6 +207,30 @@ impl QueueProvider for SqsQueue {
 
         Ok(())
     }
+
+    async fn get_queue_depth(&self, queue: QueueType) -> Result<QueueDepth> {
+        let output = self
+            .client
+            .get_queue_attributes()
+            .queue_url(self.get_queue_url(queue))
+            .attribute_names(QueueAttributeName::ApproximateNumberOfMessages)
+            .attribute_names(QueueAttributeName::ApproximateNumberOfMessagesNotVisible)
+            .attribute_names(QueueAttributeName::ApproximateNumberOfMessagesDelayed)
+            .send()
+            .await?;
+
+        let attribute = |name: QueueAttributeName| -> Result<u64> {
+            match output.attributes().and_then(|attributes| attributes.get(&name)) {
+                Some(value) => Ok(value.parse()?),
+                None => Err(eyre!("Queue attribute {} missing", name)),
+            }
+        };
+        Ok(QueueDepth {
+            visible: attribute(QueueAttributeName::ApproximateNumberOfMessages)?,
+            in_flight: attribute(QueueAttributeName::ApproximateNumberOfMessagesNotVisible)?,
+            delayed: attribute(QueueAttributeName::ApproximateNumberOfMessagesDelayed)?,
+        })
+    }
 }
 
 impl SqsQueue {
@@ -123,16 +257,3 @@ impl SqsQueue {
         Ok(attributes.attributes().unwrap().get(&QueueAttributeName::QueueArn).unwrap().to_string())
     }
 }
-
-// TODO: store the producer and consumer in memory to avoid creating a new one every time
-async fn get_producer(queue: String) -> Result<SqsProducer> {
-    let (producer, _) =
-        SqsBackend::builder(SqsConfig { queue_dsn: queue, override_endpoint: true }).build_pair().await?;
-    Ok(producer)
-}
-
-async fn get_consumer(queue: String) -> std::result::Result<SqsConsumer, QueueError> {
-    let (_, consumer) =
-        SqsBackend::builder(SqsConfig { queue_dsn: queue, override_endpoint: true }).build_pair().await?;
-    Ok(consumer)
-}
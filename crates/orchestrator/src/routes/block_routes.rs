@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use tracing::{error, instrument};
+
+use super::error::JobRouteError;
+use super::types::{
+    BlockNumber, BlockRangeQuery, BlockRangeStatusResponse, BlockStatusResponse, BlockStatusSummary, JobRouteResult,
+};
+use crate::config::Config;
+use crate::jobs::types::{JobItem, JobType};
+
+/// Maximum number of blocks in a block range status request.
+const MAX_BLOCK_RANGE: u64 = 100;
+
+/// Builds the status of a block out of the jobs handling it.
+///
+/// If several jobs of the same type cover the block, e.g. a state transition job was created again
+/// for a range overlapping a previous one, the most recent one is reported.
+fn block_status(block_number: u64, jobs: &[JobItem]) -> BlockStatusResponse {
+    let latest_job = |job_type: JobType| {
+        jobs.iter()
+            .filter(|job| job.job_type == job_type && job.covers_blocks(block_number, block_number))
+            .max_by_key(|job| job.created_at)
+            .map(Into::into)
+    };
+
+    BlockStatusResponse {
+        block_number,
+        snos: latest_job(JobType::SnosRun),
+        proving: latest_job(JobType::ProofCreation),
+        data_submission: latest_job(JobType::DataSubmission),
+        proof_registration: latest_job(JobType::ProofRegistration),
+        state_transition: latest_job(JobType::StateTransition),
+    }
+}
+
+/// Handles HTTP requests for the pipeline status of a block.
+///
+/// Returns the SNOS, proving, data submission, proof registration and state transition jobs of
+/// the block, with their status, attempts, timestamps and failure reason.
+///
+/// # Arguments
+/// * `Path(BlockNumber { number })` - The block number extracted from the URL path
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - The block status or error details
+///
+/// # Errors
+/// * `JobRouteError::InvalidQuery` - If the block number is not a number
+/// * `JobRouteError::DatabaseError` - If the jobs couldn't be fetched
+#[instrument(skip(config), fields(block_number = %number))]
+async fn handle_block_status_request(
+    Path(BlockNumber { number }): Path<BlockNumber>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    let block_number =
+        number.parse::<u64>().map_err(|_| JobRouteError::InvalidQuery(format!("Invalid block number: {}", number)))?;
+
+    let jobs = config.database().get_jobs_by_block_range(block_number, block_number).await.map_err(|e| {
+        error!(error = %e, "Failed to fetch the jobs of the block");
+        JobRouteError::DatabaseError
+    })?;
+
+    Ok(Json(block_status(block_number, &jobs)).into_response())
+}
+
+/// Handles HTTP requests for the pipeline status of a range of blocks.
+///
+/// Returns the status of each pipeline step for every block of the range.
+///
+/// # Arguments
+/// * `Query(BlockRangeQuery { from, to })` - The block range, both bounds included
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - The block statuses or error details
+///
+/// # Errors
+/// * `JobRouteError::InvalidQuery` - If the range is empty or larger than `MAX_BLOCK_RANGE` blocks
+/// * `JobRouteError::DatabaseError` - If the jobs couldn't be fetched
+#[instrument(skip(config))]
+async fn handle_block_range_status_request(
+    Query(BlockRangeQuery { from, to }): Query<BlockRangeQuery>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    if from > to {
+        return Err(JobRouteError::InvalidQuery(format!("from ({}) is greater than to ({})", from, to)));
+    }
+    if to - from >= MAX_BLOCK_RANGE {
+        return Err(JobRouteError::InvalidQuery(format!("range can't be larger than {} blocks", MAX_BLOCK_RANGE)));
+    }
+
+    let jobs = config.database().get_jobs_by_block_range(from, to).await.map_err(|e| {
+        error!(error = %e, "Failed to fetch the jobs of the block range");
+        JobRouteError::DatabaseError
+    })?;
+
+    let blocks = (from..=to).map(|block_number| BlockStatusSummary::from(block_status(block_number, &jobs))).collect();
+    Ok(Json(BlockRangeStatusResponse { blocks }).into_response())
+}
+
+/// Creates a router for block-related endpoints.
+///
+/// Sets up the read only routes reporting where blocks are in the pipeline,
+/// nested under the "/blocks" path.
+///
+/// # Arguments
+/// * `config` - Shared application configuration
+///
+/// # Returns
+/// * `Router` - Configured router with all block endpoints
+pub fn block_router(config: Arc<Config>) -> Router {
+    Router::new().nest(
+        "/blocks",
+        Router::new()
+            .route("/status", get(handle_block_range_status_request))
+            .route("/:number/status", get(handle_block_status_request))
+            .with_state(config),
+    )
+}
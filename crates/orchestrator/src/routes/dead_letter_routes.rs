@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tracing::{error, info, instrument};
+
+use super::error::JobRouteError;
+use super::types::{
+    DeadLetterActionRequest, DeadLetterAuditQuery, DeadLetterAuditResponse, DeadLetterListResponse, DeadLetterResponse,
+    JobRouteResult,
+};
+use crate::config::Config;
+use crate::queue::dead_letter::{apply_dead_letter_action, list_dead_letters, DeadLetterAction, DeadLetterSelection};
+
+/// Number of audit log entries returned when no limit is given.
+const DEFAULT_AUDIT_LIST_LIMIT: i64 = 50;
+/// Maximum number of audit log entries returned at once.
+const MAX_AUDIT_LIST_LIMIT: i64 = 500;
+
+/// Handles HTTP requests to list the dead letters.
+///
+/// Returns every failed job whose message is in the dead letter queue, along with the queue it
+/// was moved from and how many times it was received from it.
+///
+/// # Arguments
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - The dead letters or error details
+///
+/// # Errors
+/// * `JobRouteError::DatabaseError` - If the dead letters couldn't be fetched
+#[instrument(skip(config))]
+async fn handle_list_dead_letters_request(State(config): State<Arc<Config>>) -> JobRouteResult {
+    let jobs = list_dead_letters(config).await.map_err(|e| {
+        error!(error = %e, "Failed to list dead letters");
+        JobRouteError::DatabaseError
+    })?;
+
+    let dead_letters = jobs.into_iter().filter_map(DeadLetterResponse::from_job).collect();
+    Ok(Json(DeadLetterListResponse { dead_letters }).into_response())
+}
+
+/// Handles HTTP requests to replay dead letters onto the queue they were moved from.
+///
+/// # Arguments
+/// * `State(config)` - Shared application configuration
+/// * `Json(request)` - The selected dead letters and who replays them
+///
+/// # Returns
+/// * `JobRouteResult` - The replayed dead letters and the ones which failed
+///
+/// # Errors
+/// * `JobRouteError::InvalidQuery` - If the dead letters aren't selected by exactly one of
+///   `job_ids` and `all`, or the actor is missing
+/// * `JobRouteError::ProcessingError` - If the dead letters couldn't be listed
+#[instrument(skip(config))]
+async fn handle_replay_dead_letters_request(
+    State(config): State<Arc<Config>>,
+    Json(request): Json<DeadLetterActionRequest>,
+) -> JobRouteResult {
+    handle_dead_letter_action(DeadLetterAction::Replayed, request, config).await
+}
+
+/// Handles HTTP requests to discard dead letters, leaving their jobs failed.
+///
+/// # Arguments
+/// * `State(config)` - Shared application configuration
+/// * `Json(request)` - The selected dead letters and who discards them
+///
+/// # Returns
+/// * `JobRouteResult` - The discarded dead letters and the ones which failed
+///
+/// # Errors
+/// * `JobRouteError::InvalidQuery` - If the dead letters aren't selected by exactly one of
+///   `job_ids` and `all`, or the actor is missing
+/// * `JobRouteError::ProcessingError` - If the dead letters couldn't be listed
+#[instrument(skip(config))]
+async fn handle_discard_dead_letters_request(
+    State(config): State<Arc<Config>>,
+    Json(request): Json<DeadLetterActionRequest>,
+) -> JobRouteResult {
+    handle_dead_letter_action(DeadLetterAction::Discarded, request, config).await
+}
+
+async fn handle_dead_letter_action(
+    action: DeadLetterAction,
+    request: DeadLetterActionRequest,
+    config: Arc<Config>,
+) -> JobRouteResult {
+    let selection = match (request.job_ids, request.all) {
+        (Some(job_ids), false) if !job_ids.is_empty() => DeadLetterSelection::Jobs(job_ids),
+        (None, true) => DeadLetterSelection::All,
+        _ => return Err(JobRouteError::InvalidQuery("either job_ids or all must be given".to_string())),
+    };
+    if request.actor.trim().is_empty() {
+        return Err(JobRouteError::InvalidQuery("actor must be given".to_string()));
+    }
+
+    let report = apply_dead_letter_action(action, selection, &request.actor, config).await.map_err(|e| {
+        error!(error = %e, action = %action, "Failed to apply the dead letter action");
+        JobRouteError::ProcessingError(e.to_string())
+    })?;
+    info!(
+        action = %action,
+        actor = %request.actor,
+        succeeded = report.succeeded.len(),
+        failed = report.failed.len(),
+        "Dead letter action applied"
+    );
+    Ok(Json(report).into_response())
+}
+
+/// Handles HTTP requests for the dead letter audit log.
+///
+/// # Arguments
+/// * `Query(query)` - Page size
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - The latest audit log entries, newest first
+///
+/// # Errors
+/// * `JobRouteError::InvalidQuery` - If the limit is invalid
+/// * `JobRouteError::DatabaseError` - If the entries couldn't be fetched
+#[instrument(skip(config))]
+async fn handle_dead_letter_audit_request(
+    Query(query): Query<DeadLetterAuditQuery>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    let limit = query.limit.unwrap_or(DEFAULT_AUDIT_LIST_LIMIT);
+    if !(1..=MAX_AUDIT_LIST_LIMIT).contains(&limit) {
+        return Err(JobRouteError::InvalidQuery(format!("limit must be between 1 and {}", MAX_AUDIT_LIST_LIMIT)));
+    }
+
+    let entries = config.database().get_dead_letter_audit_entries(limit).await.map_err(|e| {
+        error!(error = %e, "Failed to fetch the dead letter audit log");
+        JobRouteError::DatabaseError
+    })?;
+    Ok(Json(DeadLetterAuditResponse { entries }).into_response())
+}
+
+/// Creates a router for dead letter endpoints.
+///
+/// Sets up the routes inspecting, replaying and discarding the dead letters, and reading the
+/// audit log of these actions, nested under the "/dead_letters" path.
+///
+/// # Arguments
+/// * `config` - Shared application configuration
+///
+/// # Returns
+/// * `Router` - Configured router with all dead letter endpoints
+pub fn dead_letter_router(config: Arc<Config>) -> Router {
+    Router::new().nest(
+        "/dead_letters",
+        Router::new()
+            .route("/", get(handle_list_dead_letters_request))
+            .route("/replay", post(handle_replay_dead_letters_request))
+            .route("/discard", post(handle_discard_dead_letters_request))
+            .route("/audit", get(handle_dead_letter_audit_request))
+            .with_state(config),
+    )
+}
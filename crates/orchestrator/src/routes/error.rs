@@ -17,6 +17,7 @@ use super::types::ApiResponse;
 /// * `InvalidJobState` - 409 Conflict
 /// * `DatabaseError` - 500 Internal Server Error
 /// * `InvalidStatus` - 400 Bad Request
+/// * `InvalidQuery` - 400 Bad Request
 ///
 /// # Examples
 /// ```
@@ -54,6 +55,10 @@ pub enum JobRouteError {
     /// Contains both the job ID and the current status
     #[error("Invalid status: {id}: {job_status}")]
     InvalidStatus { id: String, job_status: String },
+
+    /// Indicates that the query parameters of a listing request are invalid
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
 }
 
 /// Implementation of axum's `IntoResponse` trait for converting errors into HTTP responses.
@@ -77,6 +82,7 @@ pub enum JobRouteError {
 /// * `InvalidJobState` -> 409 Conflict
 /// * `DatabaseError` -> 500 Internal Server Error
 /// * `InvalidStatus` -> 400 Bad Request
+/// * `InvalidQuery` -> 400 Bad Request
 ///
 /// # Examples
 /// This implementation is used automatically when returning errors from route handlers:
@@ -113,6 +119,9 @@ impl IntoResponse for JobRouteError {
                 Json(ApiResponse::error(format!("Cannot retry job {id}: invalid status {job_status}"))),
             )
                 .into_response(),
+            JobRouteError::InvalidQuery(msg) => {
+                (StatusCode::BAD_REQUEST, Json(ApiResponse::error(format!("Invalid query: {}", msg)))).into_response()
+            }
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::{Json, Router};
@@ -9,11 +9,17 @@ use tracing::{error, info, instrument};
 use uuid::Uuid;
 
 use super::error::JobRouteError;
-use super::types::{ApiResponse, JobId, JobRouteResult};
+use super::types::{ApiResponse, JobId, JobListQuery, JobListResponse, JobResponse, JobRouteResult};
 use crate::config::Config;
+use crate::database::{JobCursor, JobFilter};
 use crate::jobs::{queue_job_for_processing, queue_job_for_verification, retry_job};
 use crate::metrics::ORCHESTRATOR_METRICS;
 
+/// Number of jobs returned by the job listing when no limit is given.
+const DEFAULT_JOB_LIST_LIMIT: i64 = 50;
+/// Maximum number of jobs returned by the job listing in one page.
+const MAX_JOB_LIST_LIMIT: i64 = 500;
+
 /// Handles HTTP requests to process a job.
 ///
 /// This endpoint initiates the processing of a job identified by its UUID. It performs the
@@ -142,10 +148,94 @@ async fn handle_retry_job_request(
     }
 }
 
+/// Handles HTTP requests to fetch a job.
+///
+/// Returns the full job, including its common and job specific metadata.
+///
+/// # Arguments
+/// * `Path(JobId { id })` - The job ID extracted from the URL path
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - The job or error details
+///
+/// # Errors
+/// * `JobRouteError::InvalidId` - If the provided ID is not a valid UUID
+/// * `JobRouteError::NotFound` - If there's no job with this ID
+/// * `JobRouteError::DatabaseError` - If the job couldn't be fetched
+#[instrument(skip(config), fields(job_id = %id))]
+async fn handle_get_job_request(Path(JobId { id }): Path<JobId>, State(config): State<Arc<Config>>) -> JobRouteResult {
+    let job_id = Uuid::parse_str(&id).map_err(|_| JobRouteError::InvalidId(id.clone()))?;
+
+    match config.database().get_job_by_id(job_id).await {
+        Ok(Some(job)) => Ok(Json(JobResponse::from(job)).into_response()),
+        Ok(None) => Err(JobRouteError::NotFound(id)),
+        Err(e) => {
+            error!(error = %e, "Failed to fetch job");
+            Err(JobRouteError::DatabaseError)
+        }
+    }
+}
+
+/// Handles HTTP requests to list jobs.
+///
+/// Jobs are filtered by the query parameters and returned by creation time, one page at a time.
+/// The `next_cursor` of the response is passed as `cursor` to fetch the next page.
+///
+/// # Arguments
+/// * `Query(query)` - Filters, cursor and page size
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - A page of jobs or error details
+///
+/// # Errors
+/// * `JobRouteError::InvalidQuery` - If the cursor or the limit is invalid
+/// * `JobRouteError::DatabaseError` - If the jobs couldn't be fetched
+#[instrument(skip(config))]
+async fn handle_list_jobs_request(
+    Query(query): Query<JobListQuery>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    let limit = query.limit.unwrap_or(DEFAULT_JOB_LIST_LIMIT);
+    if !(1..=MAX_JOB_LIST_LIMIT).contains(&limit) {
+        return Err(JobRouteError::InvalidQuery(format!("limit must be between 1 and {}", MAX_JOB_LIST_LIMIT)));
+    }
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(str::parse::<JobCursor>)
+        .transpose()
+        .map_err(|e| JobRouteError::InvalidQuery(e.to_string()))?;
+    let filter = JobFilter {
+        job_type: query.job_type,
+        status: query.status,
+        external_id: None,
+        internal_id_from: query.internal_id_from,
+        internal_id_to: query.internal_id_to,
+        created_after: query.created_after,
+        created_before: query.created_before,
+        updated_after: None,
+        failed_after: None,
+    };
+
+    // one extra job tells whether there's a next page
+    let mut jobs = config.database().get_jobs(filter, cursor, limit + 1).await.map_err(|e| {
+        error!(error = %e, "Failed to list jobs");
+        JobRouteError::DatabaseError
+    })?;
+    let has_next_page = jobs.len() > limit as usize;
+    jobs.truncate(limit as usize);
+    let next_cursor = if has_next_page { jobs.last().map(|job| JobCursor::from(job).to_string()) } else { None };
+
+    let jobs = jobs.into_iter().map(JobResponse::from).collect();
+    Ok(Json(JobListResponse { jobs, next_cursor }).into_response())
+}
+
 /// Creates a router for job-related endpoints.
 ///
 /// This function sets up the main router for all job-related operations,
-/// nesting the specific job trigger endpoints under the "/jobs" path.
+/// nesting the job trigger and inspection endpoints under the "/jobs" path.
 ///
 /// # Arguments
 /// * `config` - Shared application configuration
@@ -153,7 +243,7 @@ async fn handle_retry_job_request(
 /// # Returns
 /// * `Router` - Configured router with all job endpoints
 pub fn job_router(config: Arc<Config>) -> Router {
-    Router::new().nest("/jobs", trigger_router(config.clone()))
+    Router::new().nest("/jobs", trigger_router(config.clone()).merge(inspection_router(config)))
 }
 
 /// Creates the nested router for job trigger endpoints.
@@ -173,3 +263,19 @@ fn trigger_router(config: Arc<Config>) -> Router {
         .route("/:id/retry", get(handle_retry_job_request))
         .with_state(config)
 }
+
+/// Creates the nested router for job inspection endpoints.
+///
+/// Sets up the read only routes listing jobs and fetching a single job.
+///
+/// # Arguments
+/// * `config` - Shared application configuration
+///
+/// # Returns
+/// * `Router` - Configured router with inspection endpoints
+fn inspection_router(config: Arc<Config>) -> Router {
+    Router::new()
+        .route("/", get(handle_list_jobs_request))
+        .route("/:id", get(handle_get_job_request))
+        .with_state(config)
+}
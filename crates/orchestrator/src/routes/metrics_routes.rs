@@ -0,0 +1,38 @@
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use prometheus::{Registry, TextEncoder, TEXT_FORMAT};
+use tracing::error;
+
+/// Handles Prometheus scrapes.
+///
+/// Collects the current value of every metric of the orchestrator and renders them in the
+/// Prometheus text exposition format.
+///
+/// # Arguments
+/// * `State(registry)` - The registry the exporter of the global meter provider collects into
+///
+/// # Returns
+/// * `impl IntoResponse` - The rendered metrics, or a 500 status code if they couldn't be encoded
+async fn handle_metrics_request(State(registry): State<Registry>) -> impl IntoResponse {
+    match TextEncoder::new().encode_to_string(&registry.gather()) {
+        Ok(metrics) => (StatusCode::OK, [(header::CONTENT_TYPE, TEXT_FORMAT)], metrics).into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to encode the metrics");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to encode the metrics").into_response()
+        }
+    }
+}
+
+/// Creates a router for the Prometheus scrape endpoint at `/metrics`.
+///
+/// # Arguments
+/// * `registry` - The registry the exporter of the global meter provider collects into
+///
+/// # Returns
+/// * `Router` - Configured router with the metrics endpoint
+pub fn metrics_router(registry: Registry) -> Router {
+    Router::new().route("/metrics", get(handle_metrics_request)).with_state(registry)
+}
@@ -3,22 +3,35 @@ use std::sync::Arc;
 
 use app_routes::{app_router, handler_404};
 use axum::Router;
+use block_routes::block_router;
+use dead_letter_routes::dead_letter_router;
 use job_routes::job_router;
+use metrics_routes::metrics_router;
+use worker_routes::worker_router;
 
 use crate::config::Config;
+use crate::telemetry::prometheus::prometheus_registry;
 
 /// Routes module for the orchestrator service.
 ///
 /// This module provides the core routing and server setup functionality, organizing
 /// different route handlers into submodules:
 /// - `app_routes`: General application routes (e.g., health checks)
+/// - `block_routes`: Block pipeline status routes
+/// - `dead_letter_routes`: Dead letter inspection, replay and discard routes
 /// - `job_routes`: Job processing and management routes
+/// - `metrics_routes`: Prometheus scrape route
+/// - `worker_routes`: Worker circuit state routes
 /// - `error`: Error handling and HTTP response mapping
 /// - `types`: Shared type definitions for route handlers
 pub mod app_routes;
+pub mod block_routes;
+pub mod dead_letter_routes;
 pub mod error;
 pub mod job_routes;
+pub mod metrics_routes;
 pub mod types;
+pub mod worker_routes;
 
 pub use error::JobRouteError;
 
@@ -42,7 +55,8 @@ pub struct ServerParams {
 ///
 /// This function:
 /// 1. Initializes the server with the provided configuration
-/// 2. Sets up all route handlers (both app and job routes)
+/// 2. Sets up all route handlers (app, job, block, dead letter and worker routes, and the metrics
+///    route if the Prometheus metrics are enabled)
 /// 3. Starts the server in a separate tokio task
 ///
 /// # Arguments
@@ -65,8 +79,20 @@ pub async fn setup_server(config: Arc<Config>) -> SocketAddr {
     let (api_server_url, listener) = get_server_url(config.server_config()).await;
 
     let job_routes = job_router(config.clone());
+    let block_routes = block_router(config.clone());
+    let dead_letter_routes = dead_letter_router(config.clone());
+    let worker_routes = worker_router(config.clone());
     let app_routes = app_router();
-    let app = Router::new().merge(app_routes).merge(job_routes).fallback(handler_404);
+    let mut app = Router::new()
+        .merge(app_routes)
+        .merge(job_routes)
+        .merge(block_routes)
+        .merge(dead_letter_routes)
+        .merge(worker_routes);
+    if let Some(registry) = prometheus_registry() {
+        app = app.merge(metrics_router(registry.clone()));
+    }
+    let app = app.fallback(handler_404);
 
     tokio::spawn(async move {
         axum::serve(listener, app).await.expect("Failed to start axum server");
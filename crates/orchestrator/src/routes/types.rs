@@ -1,7 +1,14 @@
 use axum::response::Response;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use super::error::JobRouteError;
+use crate::jobs::metadata::JobMetadata;
+use crate::jobs::types::{ExternalId, JobItem, JobStatus, JobType};
+use crate::queue::dead_letter::DeadLetterAuditEntry;
+use crate::queue::QueueType;
+use crate::workers::circuit_breaker::{CircuitState, HaltScope};
 
 /// Represents a job identifier in API requests.
 ///
@@ -18,6 +25,263 @@ pub struct JobId {
     pub id: String,
 }
 
+/// Query parameters of the job listing endpoint.
+///
+/// Every filter is optional. The internal id and creation time bounds are inclusive.
+/// `cursor` is the `next_cursor` returned with the previous page.
+///
+/// # Examples
+/// ```text
+/// GET /jobs?job_type=SnosRun&status=Failed&internal_id_from=100&limit=20
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct JobListQuery {
+    /// Only return jobs of this type
+    pub job_type: Option<JobType>,
+    /// Only return jobs in this status
+    pub status: Option<JobStatus>,
+    /// Lowest internal id (block number) to return
+    pub internal_id_from: Option<u64>,
+    /// Highest internal id (block number) to return
+    pub internal_id_to: Option<u64>,
+    /// Only return jobs created at or after this time (RFC 3339)
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only return jobs created at or before this time (RFC 3339)
+    pub created_before: Option<DateTime<Utc>>,
+    /// Cursor of the page to fetch, the first page is returned if absent
+    pub cursor: Option<String>,
+    /// Maximum number of jobs to return
+    pub limit: Option<i64>,
+}
+
+/// A job as returned by the job inspection endpoints.
+///
+/// Mirrors `JobItem`, with the ids and timestamps in their plain JSON representation rather than
+/// the one used to store them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobResponse {
+    pub id: Uuid,
+    pub internal_id: String,
+    pub job_type: JobType,
+    pub status: JobStatus,
+    pub external_id: ExternalId,
+    /// Common and job specific metadata
+    pub metadata: JobMetadata,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<JobItem> for JobResponse {
+    fn from(job: JobItem) -> Self {
+        Self {
+            id: job.id,
+            internal_id: job.internal_id,
+            job_type: job.job_type,
+            status: job.status,
+            external_id: job.external_id,
+            metadata: job.metadata,
+            version: job.version,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
+}
+
+/// A page of the job listing.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobListResponse {
+    /// Jobs ordered by creation time
+    pub jobs: Vec<JobResponse>,
+    /// Cursor of the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Represents a block number in API requests, parsed by the handler.
+#[derive(Deserialize)]
+pub struct BlockNumber {
+    /// The block number as found in the path
+    pub number: String,
+}
+
+/// Query parameters of the block range status endpoint, both bounds are inclusive.
+#[derive(Debug, Deserialize)]
+pub struct BlockRangeQuery {
+    pub from: u64,
+    pub to: u64,
+}
+
+/// Status of one job of the block pipeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockJobStatus {
+    pub job_id: Uuid,
+    pub internal_id: String,
+    pub status: JobStatus,
+    pub process_attempt_no: u64,
+    pub process_retry_attempt_no: u64,
+    pub verification_attempt_no: u64,
+    pub verification_retry_attempt_no: u64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub process_started_at: Option<DateTime<Utc>>,
+    pub process_completed_at: Option<DateTime<Utc>>,
+    pub verification_started_at: Option<DateTime<Utc>>,
+    pub verification_completed_at: Option<DateTime<Utc>>,
+    pub failure_reason: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+impl From<&JobItem> for BlockJobStatus {
+    fn from(job: &JobItem) -> Self {
+        let common = &job.metadata.common;
+        Self {
+            job_id: job.id,
+            internal_id: job.internal_id.clone(),
+            status: job.status.clone(),
+            process_attempt_no: common.process_attempt_no,
+            process_retry_attempt_no: common.process_retry_attempt_no,
+            verification_attempt_no: common.verification_attempt_no,
+            verification_retry_attempt_no: common.verification_retry_attempt_no,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            process_started_at: common.process_started_at,
+            process_completed_at: common.process_completed_at,
+            verification_started_at: common.verification_started_at,
+            verification_completed_at: common.verification_completed_at,
+            failure_reason: common.failure_reason.clone(),
+            next_attempt_at: common.next_attempt_at,
+        }
+    }
+}
+
+/// Jobs handling a block, one per pipeline step. A step is `None` until its job is created.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockStatusResponse {
+    pub block_number: u64,
+    pub snos: Option<BlockJobStatus>,
+    pub proving: Option<BlockJobStatus>,
+    pub data_submission: Option<BlockJobStatus>,
+    pub proof_registration: Option<BlockJobStatus>,
+    pub state_transition: Option<BlockJobStatus>,
+}
+
+/// Status of each pipeline step of a block, as found in `BlockStatusResponse`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockStatusSummary {
+    pub block_number: u64,
+    pub snos: Option<JobStatus>,
+    pub proving: Option<JobStatus>,
+    pub data_submission: Option<JobStatus>,
+    pub proof_registration: Option<JobStatus>,
+    pub state_transition: Option<JobStatus>,
+}
+
+impl From<BlockStatusResponse> for BlockStatusSummary {
+    fn from(block: BlockStatusResponse) -> Self {
+        Self {
+            block_number: block.block_number,
+            snos: block.snos.map(|job| job.status),
+            proving: block.proving.map(|job| job.status),
+            data_submission: block.data_submission.map(|job| job.status),
+            proof_registration: block.proof_registration.map(|job| job.status),
+            state_transition: block.state_transition.map(|job| job.status),
+        }
+    }
+}
+
+/// Summary of the blocks of a range, in ascending block order.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlockRangeStatusResponse {
+    pub blocks: Vec<BlockStatusSummary>,
+}
+
+/// Whether the worker creating jobs of `job_type` is halted by failed jobs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkerStatus {
+    pub job_type: JobType,
+    pub circuit: CircuitState,
+}
+
+/// Circuit state of every worker under the configured halt scope.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkerStatusResponse {
+    pub halt_scope: HaltScope,
+    pub workers: Vec<WorkerStatus>,
+}
+
+/// A job whose message is in the dead letter queue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetterResponse {
+    pub job_id: Uuid,
+    pub internal_id: String,
+    pub job_type: JobType,
+    /// Queue the message was moved from, which it's replayed to
+    pub source_queue: QueueType,
+    /// Times the message was received from its queue before being moved
+    pub receive_count: i32,
+    /// Status the job was in when its message was moved
+    pub job_status: JobStatus,
+    pub dead_lettered_at: DateTime<Utc>,
+    pub failure_reason: Option<String>,
+}
+
+impl DeadLetterResponse {
+    /// The dead letter of `job`, `None` if its message isn't in the dead letter queue.
+    pub fn from_job(job: JobItem) -> Option<Self> {
+        let dead_letter = job.metadata.common.dead_letter?;
+        Some(Self {
+            job_id: job.id,
+            internal_id: job.internal_id,
+            job_type: job.job_type,
+            source_queue: dead_letter.source_queue,
+            receive_count: dead_letter.receive_count,
+            job_status: dead_letter.job_status,
+            dead_lettered_at: dead_letter.dead_lettered_at,
+            failure_reason: job.metadata.common.failure_reason,
+        })
+    }
+}
+
+/// The dead letters in the dead letter queue, by job creation time.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetterListResponse {
+    pub dead_letters: Vec<DeadLetterResponse>,
+}
+
+/// Body of the dead letter replay and discard endpoints.
+///
+/// Exactly one of `job_ids` and `all` selects the dead letters.
+///
+/// # Examples
+/// ```text
+/// POST /dead_letters/replay
+/// {"job_ids": ["123e4567-e89b-12d3-a456-426614174000"], "actor": "alice"}
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetterActionRequest {
+    /// Jobs whose dead letter the action applies to
+    #[serde(default)]
+    pub job_ids: Option<Vec<Uuid>>,
+    /// Apply the action to every dead letter
+    #[serde(default)]
+    pub all: bool,
+    /// Who takes the action, written to the audit log
+    pub actor: String,
+}
+
+/// Query parameters of the dead letter audit log endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct DeadLetterAuditQuery {
+    /// Maximum number of entries to return
+    pub limit: Option<i64>,
+}
+
+/// The latest entries of the dead letter audit log, newest first.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetterAuditResponse {
+    pub entries: Vec<DeadLetterAuditEntry>,
+}
+
 /// Represents a standardized API response structure.
 ///
 /// This struct provides a consistent format for all API responses, including
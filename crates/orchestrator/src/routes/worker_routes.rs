@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use tracing::{error, instrument};
+
+use super::error::JobRouteError;
+use super::types::{JobRouteResult, WorkerStatus, WorkerStatusResponse};
+use crate::config::Config;
+use crate::jobs::types::JobType;
+use crate::workers::circuit_breaker::get_circuit_state;
+
+/// Job types created by the workers, in pipeline order.
+const WORKER_JOB_TYPES: [JobType; 5] = [
+    JobType::SnosRun,
+    JobType::ProofCreation,
+    JobType::ProofRegistration,
+    JobType::DataSubmission,
+    JobType::StateTransition,
+];
+
+/// Handles HTTP requests for the circuit state of the workers.
+///
+/// Reports, for each worker, whether the failed jobs currently halt its job creation and why.
+///
+/// # Arguments
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - The worker statuses or error details
+///
+/// # Errors
+/// * `JobRouteError::DatabaseError` - If the failed jobs couldn't be fetched
+#[instrument(skip(config))]
+async fn handle_worker_status_request(State(config): State<Arc<Config>>) -> JobRouteResult {
+    let mut workers = Vec::with_capacity(WORKER_JOB_TYPES.len());
+    for job_type in WORKER_JOB_TYPES {
+        let circuit = get_circuit_state(&config, &job_type).await.map_err(|e| {
+            error!(error = %e, "Failed to fetch the failed jobs");
+            JobRouteError::DatabaseError
+        })?;
+        workers.push(WorkerStatus { job_type, circuit });
+    }
+
+    let params = &config.service_config().circuit_breaker;
+    Ok(Json(WorkerStatusResponse { halt_scope: params.scope, workers }).into_response())
+}
+
+/// Creates a router for worker-related endpoints.
+///
+/// Sets up the read only routes reporting whether the workers are halted,
+/// nested under the "/workers" path.
+///
+/// # Arguments
+/// * `config` - Shared application configuration
+///
+/// # Returns
+/// * `Router` - Configured router with all worker endpoints
+pub fn worker_router(config: Arc<Config>) -> Router {
+    Router::new().nest("/workers", Router::new().route("/status", get(handle_worker_status_request)).with_state(config))
+}
@@ -11,11 +11,13 @@ use crate::cli::cron::CronValidatedArgs;
 use crate::cli::queue::QueueValidatedArgs;
 use crate::cli::storage::StorageValidatedArgs;
 use crate::cli::SetupCmd;
-use crate::config::build_provider_config;
+use crate::config::{build_alert_client, build_provider_config};
 use crate::cron::event_bridge::AWSEventBridge;
 use crate::cron::Cron;
 use crate::data_storage::aws_s3::AWSS3;
+use crate::data_storage::local_fs::LocalFsStorage;
 use crate::data_storage::DataStorage;
+use crate::queue::amqp::AmqpQueue;
 use crate::queue::sqs::SqsQueue;
 use crate::queue::QueueProvider as _;
 
@@ -30,58 +32,75 @@ pub async fn setup_cloud(setup_cmd: &SetupCmd) -> color_eyre::Result<()> {
     // AWS
     println!("Setting up cloud. ⏳");
     let provider_params = setup_cmd.validate_provider_params().expect("Failed to validate provider params");
-    let provider_config = build_provider_config(&provider_params).await;
-    let aws_config = provider_config.get_aws_client_or_panic();
+    let provider_config = match &provider_params {
+        Some(provider_params) => Some(build_provider_config(provider_params).await),
+        None => None,
+    };
+    // the AWS resources are only validated along with the AWS provider
+    let aws_config = || provider_config.as_deref().expect("AWS provider is required").get_aws_client_or_panic();
 
     // Queues
     println!("Setting up queues. ⏳");
     let queue_params = setup_cmd.validate_queue_params().expect("Failed to validate queue params");
     match queue_params {
         QueueValidatedArgs::AWSSQS(aws_sqs_params) => {
-            let sqs = Box::new(SqsQueue::new_with_args(aws_sqs_params, aws_config));
-            sqs.setup().await?
+            let sqs = Box::new(SqsQueue::new_with_args(aws_sqs_params, aws_config()));
+            sqs.setup().await?;
+            // Waiting for few seconds to let AWS index the queues
+            sleep(Duration::from_secs(20)).await;
         }
+        QueueValidatedArgs::AMQP(amqp_params) => AmqpQueue::new_with_args(amqp_params).setup().await?,
+        QueueValidatedArgs::InMemory => println!("In memory queues don't need any setup"),
     }
     println!("Queues setup completed ✅");
 
-    // Waiting for few seconds to let AWS index the queues
-    sleep(Duration::from_secs(20)).await;
-
     // Data Storage
     println!("Setting up data storage. ⏳");
     let data_storage_params = setup_cmd.validate_storage_params().expect("Failed to validate storage params");
 
     match data_storage_params {
         StorageValidatedArgs::AWSS3(aws_s3_params) => {
-            let s3 = Box::new(AWSS3::new_with_args(&aws_s3_params, aws_config).await);
+            let s3 = Box::new(AWSS3::new_with_args(&aws_s3_params, aws_config()).await);
             s3.setup(&StorageValidatedArgs::AWSS3(aws_s3_params.clone())).await?
         }
+        StorageValidatedArgs::LocalFs(local_fs_params) => {
+            let local_fs = Box::new(LocalFsStorage::new_with_args(&local_fs_params));
+            local_fs.setup(&StorageValidatedArgs::LocalFs(local_fs_params.clone())).await?
+        }
+        StorageValidatedArgs::InMemory => println!("In memory data storage doesn't need any setup"),
     }
     println!("Data storage setup completed ✅");
 
     // Cron
     println!("Setting up cron. ⏳");
-    // Sleeping for few seconds to let AWS index the newly created queues to be used for setting up cron
-    sleep(Duration::from_secs(60)).await;
     let cron_params = setup_cmd.validate_cron_params().expect("Failed to validate cron params");
     match cron_params {
-        CronValidatedArgs::AWSEventBridge(aws_event_bridge_params) => {
-            let aws_config = provider_config.get_aws_client_or_panic();
-            let event_bridge = Box::new(AWSEventBridge::new_with_args(&aws_event_bridge_params, aws_config));
+        Some(CronValidatedArgs::AWSEventBridge(aws_event_bridge_params)) => {
+            // Sleeping for few seconds to let AWS index the newly created queues to be used for setting up cron
+            sleep(Duration::from_secs(60)).await;
+            let event_bridge = Box::new(AWSEventBridge::new_with_args(&aws_event_bridge_params, aws_config()));
             event_bridge.setup().await?
         }
+        Some(CronValidatedArgs::InProcess(_)) => println!("In-process cron doesn't need any setup"),
+        None => println!("No cron to set up"),
     }
     println!("Cron setup completed ✅");
 
     // Alerts
     println!("Setting up alerts. ⏳");
     let alert_params = setup_cmd.validate_alert_params().expect("Failed to validate alert params");
-    match alert_params {
-        AlertValidatedArgs::AWSSNS(aws_sns_params) => {
-            let aws_config = provider_config.get_aws_client_or_panic();
-            let sns = Box::new(AWSSNS::new_with_args(&aws_sns_params, aws_config).await);
+    match &alert_params {
+        Some(AlertValidatedArgs::AWSSNS(aws_sns_params)) => {
+            let sns = Box::new(AWSSNS::new_with_args(aws_sns_params, aws_config()).await);
             sns.setup().await?
         }
+        Some(AlertValidatedArgs::Webhook(_)) => println!("Webhook alerts don't need any setup"),
+        Some(AlertValidatedArgs::InMemory) => println!("In memory alerts don't need any setup"),
+        Some(alert_params @ AlertValidatedArgs::FanOut(_)) => {
+            let provider_config = provider_config.clone().expect("AWS provider is required");
+            build_alert_client(alert_params, provider_config).await.setup().await?
+        }
+        None => println!("No alerts to set up"),
     }
     println!("Alerts setup completed ✅");
 
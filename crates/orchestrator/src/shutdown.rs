@@ -0,0 +1,154 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+/// How long the in-flight jobs are given to finish once the shutdown starts, if not configured.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long the in-flight jobs are given to give their message back to the queue once the
+/// shutdown deadline is reached.
+const GIVE_BACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Coordinates the graceful shutdown of the orchestrator.
+///
+/// Once the shutdown starts, the queue consumers and the in-process cron stop pulling work and
+/// the jobs and workers in flight are given until the shutdown deadline to finish. The ones still
+/// running at the deadline give their message back to the queue, so that another replica picks
+/// it up.
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownCoordinator {
+    /// Cancelled once the shutdown starts
+    draining: CancellationToken,
+    /// Cancelled once the in-flight work ran out of time
+    deadline_reached: CancellationToken,
+    in_flight: TaskTracker,
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the shutdown started, in which case no new work should be pulled.
+    pub fn is_draining(&self) -> bool {
+        self.draining.is_cancelled()
+    }
+
+    /// Resolves once the shutdown starts.
+    pub async fn draining(&self) {
+        self.draining.cancelled().await
+    }
+
+    /// Resolves once the in-flight work ran out of time to finish.
+    pub async fn deadline_reached(&self) {
+        self.deadline_reached.cancelled().await
+    }
+
+    /// Spawns in-flight work, which the shutdown waits for.
+    pub fn spawn<F>(&self, task: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.in_flight.spawn(task)
+    }
+
+    /// Number of in-flight tasks still running.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Stops pulling new work and waits up to `timeout` for the in-flight work to finish.
+    /// Returns whether all of it finished in time.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        tracing::info!(in_flight = self.in_flight(), ?timeout, "Draining the in-flight jobs");
+        self.draining.cancel();
+        self.in_flight.close();
+
+        if tokio::time::timeout(timeout, self.in_flight.wait()).await.is_ok() {
+            tracing::info!("All the in-flight jobs finished");
+            return true;
+        }
+
+        tracing::warn!(
+            in_flight = self.in_flight(),
+            "Shutdown deadline reached, giving the remaining jobs back to the queues"
+        );
+        self.deadline_reached.cancel();
+        if tokio::time::timeout(GIVE_BACK_TIMEOUT, self.in_flight.wait()).await.is_err() {
+            tracing::error!(in_flight = self.in_flight(), "Failed to give some jobs back to the queues in time");
+        }
+        false
+    }
+}
+
+/// Resolves once the orchestrator is asked to stop, through ctrl+c or SIGTERM as sent by
+/// Kubernetes before killing a pod.
+pub async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("Failed to listen for ctrl+c");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received ctrl+c"),
+        _ = terminate => tracing::info!("Received SIGTERM"),
+    }
+}
+
+#[cfg(test)]
+mod shutdown_test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_work() {
+        let coordinator = ShutdownCoordinator::new();
+        let finished = Arc::new(AtomicBool::new(false));
+        let task_finished = finished.clone();
+        coordinator.spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            task_finished.store(true, Ordering::Release);
+        });
+
+        assert!(coordinator.shutdown(Duration::from_secs(5)).await);
+        assert!(coordinator.is_draining());
+        assert!(finished.load(Ordering::Acquire));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_shutdown_deadline_stops_in_flight_work() {
+        let coordinator = ShutdownCoordinator::new();
+        let task_coordinator = coordinator.clone();
+        let given_back = Arc::new(AtomicBool::new(false));
+        let task_given_back = given_back.clone();
+        coordinator.spawn(async move {
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(60)) => {}
+                _ = task_coordinator.deadline_reached() => task_given_back.store(true, Ordering::Release),
+            }
+        });
+
+        assert!(!coordinator.shutdown(Duration::from_millis(100)).await);
+        assert!(given_back.load(Ordering::Acquire));
+        assert_eq!(coordinator.in_flight(), 0);
+    }
+}
@@ -6,7 +6,8 @@ use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_otlp::{ExportConfig, WithExportConfig};
 use opentelemetry_sdk::logs::LoggerProvider;
 use opentelemetry_sdk::metrics::reader::{DefaultAggregationSelector, DefaultTemporalitySelector};
-use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::metrics::{Aggregation, Instrument, InstrumentKind, PeriodicReader, SdkMeterProvider, Stream};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::trace::{BatchConfigBuilder, Config, Tracer};
 use opentelemetry_sdk::{runtime, Resource};
 use tracing_opentelemetry::OpenTelemetryLayer;
@@ -15,6 +16,12 @@ use tracing_subscriber::util::SubscriberInitExt as _;
 use tracing_subscriber::EnvFilter;
 use url::Url;
 
+use crate::metrics::LATENCY_BUCKETS_SECONDS;
+use crate::telemetry::prometheus::init_prometheus_exporter;
+
+pub mod prometheus;
+pub mod propagation;
+
 pub struct OTELConfig {
     endpoint: Url,
     service_name: String,
@@ -24,6 +31,8 @@ pub struct OTELConfig {
 pub struct InstrumentationParams {
     pub otel_service_name: String,
     pub otel_collector_endpoint: Option<Url>,
+    /// Expose the metrics on the `/metrics` route of the server, for Prometheus to scrape them
+    pub prometheus_metrics: bool,
 }
 
 pub fn setup_analytics(instrumentation: &InstrumentationParams) -> Option<SdkMeterProvider> {
@@ -32,8 +41,9 @@ pub fn setup_analytics(instrumentation: &InstrumentationParams) -> Option<SdkMet
     let tracing_subscriber =
         tracing_subscriber::registry().with(tracing_subscriber::fmt::layer()).with(EnvFilter::from_default_env());
 
+    let meter_provider = init_meter_provider(otel_config.as_ref(), instrumentation);
+
     if let Some(otel_config) = otel_config {
-        let meter_provider = init_metric_provider(&otel_config);
         let tracer = init_tracer_provider(&otel_config);
 
         // Opentelemetry will not provide a global API to manage the logger
@@ -45,11 +55,49 @@ pub fn setup_analytics(instrumentation: &InstrumentationParams) -> Option<SdkMet
         let layer = OpenTelemetryTracingBridge::new(&logger_provider);
 
         tracing_subscriber.with(OpenTelemetryLayer::new(tracer)).with(layer).init();
-        Some(meter_provider)
     } else {
         tracing_subscriber.init();
-        None
     }
+    meter_provider
+}
+
+/// Sets up the global meter provider, exporting the metrics over OTLP and to Prometheus when
+/// enabled. Returns `None` if neither is.
+fn init_meter_provider(
+    otel_config: Option<&OTELConfig>,
+    instrumentation: &InstrumentationParams,
+) -> Option<SdkMeterProvider> {
+    if otel_config.is_none() && !instrumentation.prometheus_metrics {
+        return None;
+    }
+
+    let mut builder = SdkMeterProvider::builder().with_view(latency_buckets_view);
+    if let Some(otel_config) = otel_config {
+        builder = builder.with_reader(init_otlp_metric_reader(otel_config)).with_resource(Resource::new(vec![
+            KeyValue::new(
+                opentelemetry_semantic_conventions::resource::SERVICE_NAME,
+                format!("{}{}", otel_config.service_name, "_meter_service"),
+            ),
+        ]));
+    }
+    if instrumentation.prometheus_metrics {
+        builder = builder.with_reader(init_prometheus_exporter().expect("Failed to build the Prometheus exporter"));
+    }
+
+    let provider = builder.build();
+    global::set_meter_provider(provider.clone());
+    Some(provider)
+}
+
+/// Uses the latency buckets of the orchestrator for all the histograms, as the default ones are
+/// meant for milliseconds while the orchestrator records seconds.
+fn latency_buckets_view(instrument: &Instrument) -> Option<Stream> {
+    (instrument.kind == Some(InstrumentKind::Histogram)).then(|| {
+        Stream::new().aggregation(Aggregation::ExplicitBucketHistogram {
+            boundaries: LATENCY_BUCKETS_SECONDS.to_vec(),
+            record_min_max: true,
+        })
+    })
 }
 
 fn get_otel_config(instrumentation: &InstrumentationParams) -> Option<OTELConfig> {
@@ -66,15 +114,11 @@ fn get_otel_config(instrumentation: &InstrumentationParams) -> Option<OTELConfig
 }
 
 pub fn shutdown_analytics(meter_provider: Option<SdkMeterProvider>, instrumentation: &InstrumentationParams) {
-    let otel_config = get_otel_config(instrumentation);
-
-    // guard clause if otel is disabled
-    if otel_config.is_none() {
-        return;
+    if get_otel_config(instrumentation).is_some() {
+        global::shutdown_tracer_provider();
     }
 
     if let Some(meter_provider) = meter_provider {
-        global::shutdown_tracer_provider();
         let _ = meter_provider.shutdown();
     }
 }
@@ -96,11 +140,28 @@ pub fn init_tracer_provider(otel_config: &OTELConfig) -> Tracer {
         .expect("Failed to install tracer provider");
 
     global::set_tracer_provider(provider.clone());
+    // carries the trace context along the queue messages, see `propagation`
+    global::set_text_map_propagator(TraceContextPropagator::new());
 
     provider.tracer(format!("{}{}", otel_config.service_name, "_subscriber"))
 }
 
 pub fn init_metric_provider(otel_config: &OTELConfig) -> SdkMeterProvider {
+    // Builds a meter provider with the periodic reader
+    let provider = SdkMeterProvider::builder()
+        .with_view(latency_buckets_view)
+        .with_reader(init_otlp_metric_reader(otel_config))
+        .with_resource(Resource::new(vec![KeyValue::new(
+            opentelemetry_semantic_conventions::resource::SERVICE_NAME,
+            format!("{}{}", otel_config.service_name, "_meter_service"),
+        )]))
+        .build();
+    global::set_meter_provider(provider.clone());
+    provider
+}
+
+/// Creates a reader pushing the metrics to the OTLP collector.
+fn init_otlp_metric_reader(otel_config: &OTELConfig) -> PeriodicReader {
     let export_config = ExportConfig { endpoint: otel_config.endpoint.to_string(), ..ExportConfig::default() };
 
     // Creates and builds the OTLP exporter
@@ -112,20 +173,9 @@ pub fn init_metric_provider(otel_config: &OTELConfig) -> SdkMeterProvider {
     );
 
     // Creates a periodic reader that exports every 5 seconds
-    let reader = PeriodicReader::builder(exporter.expect("Failed to build metrics exporter"), runtime::Tokio)
+    PeriodicReader::builder(exporter.expect("Failed to build metrics exporter"), runtime::Tokio)
         .with_interval(Duration::from_secs(5))
-        .build();
-
-    // Builds a meter provider with the periodic reader
-    let provider = SdkMeterProvider::builder()
-        .with_reader(reader)
-        .with_resource(Resource::new(vec![KeyValue::new(
-            opentelemetry_semantic_conventions::resource::SERVICE_NAME,
-            format!("{}{}", otel_config.service_name, "_meter_service"),
-        )]))
-        .build();
-    global::set_meter_provider(provider.clone());
-    provider
+        .build()
 }
 
 fn init_logs(otel_config: &OTELConfig) -> Result<LoggerProvider, opentelemetry::logs::LogError> {
@@ -155,6 +205,7 @@ mod tests {
         let instrumentation_params = InstrumentationParams {
             otel_collector_endpoint: Some(Url::parse("http://localhost:4317").unwrap()),
             otel_service_name: "test_service".to_string(),
+            prometheus_metrics: false,
         };
 
         let otel_config = get_otel_config(&instrumentation_params).unwrap();
@@ -176,6 +227,7 @@ mod tests {
         let instrumentation_params = InstrumentationParams {
             otel_collector_endpoint: Some(Url::parse("http://localhost:4317").unwrap()),
             otel_service_name: "test_service".to_string(),
+            prometheus_metrics: false,
         };
 
         let otel_config = get_otel_config(&instrumentation_params).unwrap();
@@ -195,6 +247,7 @@ mod tests {
         let instrumentation_params = InstrumentationParams {
             otel_collector_endpoint: Some(Url::parse("http://localhost:4317").unwrap()),
             otel_service_name: "test_service".to_string(),
+            prometheus_metrics: false,
         };
 
         let analytics = setup_analytics(&instrumentation_params);
@@ -202,6 +255,20 @@ mod tests {
         assert!(analytics.is_some(), " Unable to set analytics")
     }
 
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_init_meter_provider_without_exporters() {
+        let instrumentation_params = InstrumentationParams {
+            otel_collector_endpoint: None,
+            otel_service_name: "test_service".to_string(),
+            prometheus_metrics: false,
+        };
+
+        let meter_provider = init_meter_provider(None, &instrumentation_params);
+
+        assert!(meter_provider.is_none(), "Meter provider set up without any exporter");
+    }
+
     #[tokio::test]
     #[allow(clippy::needless_return)]
     async fn test_gauge_setter() {
@@ -210,6 +277,7 @@ mod tests {
         let instrumentation_params = InstrumentationParams {
             otel_collector_endpoint: Some(Url::parse("http://localhost:4317").unwrap()),
             otel_service_name: "test_service".to_string(),
+            prometheus_metrics: false,
         };
 
         setup_analytics(&instrumentation_params);
@@ -0,0 +1,56 @@
+use once_cell::sync::OnceCell;
+use opentelemetry::metrics::Result as MetricsResult;
+use opentelemetry_prometheus::PrometheusExporter;
+use prometheus::Registry;
+
+/// The registry the exporter of the global meter provider, set up by `setup_analytics`, writes
+/// the metrics to.
+static PROMETHEUS_REGISTRY: OnceCell<Registry> = OnceCell::new();
+
+/// Returns the registry served by the `/metrics` route, if the Prometheus metrics are enabled.
+pub fn prometheus_registry() -> Option<&'static Registry> {
+    PROMETHEUS_REGISTRY.get()
+}
+
+/// Creates the OpenTelemetry Prometheus exporter, collecting the metrics of its meter provider
+/// into the registry served by the `/metrics` route when Prometheus scrapes them.
+///
+/// It reads every instrument of the meter provider, so the instruments added to
+/// `ORCHESTRATOR_METRICS` are exposed without further setup.
+pub(crate) fn init_prometheus_exporter() -> MetricsResult<PrometheusExporter> {
+    let registry = PROMETHEUS_REGISTRY.get_or_init(Registry::new).clone();
+    // the scope is always the orchestrator, no need to add it to every sample
+    opentelemetry_prometheus::exporter().with_registry(registry).without_scope_info().build()
+}
+
+#[cfg(test)]
+mod prometheus_test {
+    use opentelemetry::metrics::MeterProvider as _;
+    use opentelemetry::KeyValue;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+    use prometheus::{Encoder as _, TextEncoder};
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_render_metrics() {
+        let registry = prometheus::Registry::new();
+        let exporter =
+            opentelemetry_prometheus::exporter().with_registry(registry.clone()).without_scope_info().build().unwrap();
+        let provider = SdkMeterProvider::builder().with_reader(exporter).build();
+        let meter = provider.meter("orchestrator");
+
+        let counter = meter.f64_counter("successful_job_operations").with_description("Jobs done").init();
+        counter.add(2.0, &[KeyValue::new("operation_job_type", "SnosRun")]);
+        let histogram = meter.f64_histogram("jobs_response_time").with_unit("s").init();
+        histogram.record(0.5, &[]);
+
+        let mut rendered = vec![];
+        TextEncoder::new().encode(&registry.gather(), &mut rendered).unwrap();
+        let rendered = String::from_utf8(rendered).unwrap();
+        assert!(rendered.contains("# HELP successful_job_operations_total Jobs done"));
+        assert!(rendered.contains("# TYPE successful_job_operations_total counter"));
+        assert!(rendered.contains("successful_job_operations_total{operation_job_type=\"SnosRun\"} 2"));
+        assert!(rendered.contains("# TYPE jobs_response_time_seconds histogram"));
+        assert!(rendered.contains("jobs_response_time_seconds_count 1"));
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use opentelemetry::global;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+
+/// The OpenTelemetry context of a span in the W3C trace context format, i.e. its `traceparent`
+/// and `tracestate` headers. It's carried along the queue messages and the jobs so that the
+/// journey of a block through the orchestrator shows up as a single trace.
+pub type TraceContext = HashMap<String, String>;
+
+/// Returns the context of the current span. It's empty unless the traces are exported.
+pub fn current_trace_context() -> TraceContext {
+    let mut trace_context = TraceContext::new();
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut trace_context));
+    trace_context
+}
+
+/// Makes `span` continue the trace of `trace_context`, if any. The span must not be entered yet.
+pub fn continue_trace(span: &Span, trace_context: &TraceContext) {
+    if trace_context.is_empty() {
+        return;
+    }
+    let parent = global::get_text_map_propagator(|propagator| propagator.extract(trace_context));
+    span.set_parent(parent);
+}
+
+#[cfg(test)]
+mod propagation_test {
+    use opentelemetry::trace::{TraceContextExt as _, TracerProvider as _};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use rstest::rstest;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    use super::*;
+
+    #[rstest]
+    fn test_trace_context_round_trip() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let tracer = TracerProvider::builder().build().tracer("test");
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let producer = tracing::info_span!("add_job_to_queue");
+            let trace_context = producer.in_scope(current_trace_context);
+            assert!(trace_context.contains_key("traceparent"));
+
+            let consumer = tracing::info_span!("consume_job_message");
+            continue_trace(&consumer, &trace_context);
+            assert_eq!(
+                consumer.context().span().span_context().trace_id(),
+                producer.context().span().span_context().trace_id()
+            );
+        });
+    }
+
+    #[rstest]
+    fn test_continue_trace_without_context() {
+        let span = tracing::info_span!("consume_job_message");
+        continue_trace(&span, &TraceContext::new());
+        assert!(!span.context().span().span_context().is_valid());
+    }
+}
@@ -21,11 +21,13 @@ use crate::config::ProviderConfig;
 use crate::data_storage::aws_s3::{AWSS3ValidatedArgs, AWSS3};
 use crate::data_storage::DataStorage;
 use crate::database::mongodb::MongoDb;
+use crate::database::postgres::PostgresDb;
 use crate::jobs::metadata::{CommonMetadata, DaMetadata, JobMetadata, JobSpecificMetadata};
 use crate::jobs::types::JobStatus::Created;
 use crate::jobs::types::JobType::DataSubmission;
 use crate::jobs::types::{ExternalId, JobItem};
-use crate::queue::QueueType;
+use crate::queue::amqp::AmqpQueue;
+use crate::queue::{QueueProvider as _, QueueType};
 
 #[fixture]
 pub fn default_job_item() -> JobItem {
@@ -64,10 +66,19 @@ pub async fn create_sns_arn(
     provider_config: Arc<ProviderConfig>,
     alert_params: &AlertValidatedArgs,
 ) -> Result<(), SdkError<CreateTopicError>> {
-    let AlertValidatedArgs::AWSSNS(aws_sns_params) = alert_params;
-    let topic_name = aws_sns_params.topic_arn.split(":").last().unwrap();
-    let sns_client = get_sns_client(provider_config.get_aws_client_or_panic()).await;
-    sns_client.create_topic().name(topic_name).send().await?;
+    match alert_params {
+        AlertValidatedArgs::AWSSNS(aws_sns_params) => {
+            let topic_name = aws_sns_params.topic_arn.split(":").last().unwrap();
+            let sns_client = get_sns_client(provider_config.get_aws_client_or_panic()).await;
+            sns_client.create_topic().name(topic_name).send().await?;
+        }
+        AlertValidatedArgs::FanOut(alert_params) => {
+            for sink_params in alert_params {
+                Box::pin(create_sns_arn(provider_config.clone(), sink_params)).await?;
+            }
+        }
+        AlertValidatedArgs::Webhook(_) | AlertValidatedArgs::InMemory => {}
+    }
     Ok(())
 }
 
@@ -84,6 +95,15 @@ pub async fn drop_database(database_params: &DatabaseValidatedArgs) -> color_eyr
             // if only particular collection is to be dropped
             db_client.database(&mongodb_params.database_name).drop(None).await?;
         }
+        DatabaseValidatedArgs::Postgres(postgres_params) => {
+            let pool = PostgresDb::new_with_args(postgres_params).await.pool();
+            // emptying the tables, the schema is kept as it's managed by the migrations
+            sqlx::query("TRUNCATE TABLE jobs, leases, leaders, dead_letter_audit_log RESTART IDENTITY")
+                .execute(&pool)
+                .await?;
+        }
+        // every config gets a fresh in memory database
+        DatabaseValidatedArgs::InMemory => {}
     }
     Ok(())
 }
@@ -137,6 +157,13 @@ pub async fn delete_storage(
 
             Ok(())
         }
+        StorageValidatedArgs::LocalFs(local_fs_params) => {
+            if local_fs_params.root_dir.exists() {
+                tokio::fs::remove_dir_all(&local_fs_params.root_dir).await?;
+            }
+            Ok(())
+        }
+        StorageValidatedArgs::InMemory => Ok(()),
     }
 }
 
@@ -166,6 +193,10 @@ pub async fn create_queues(
                 sqs_client.create_queue().queue_name(queue_name).send().await?;
             }
         }
+        // declaring the queues is idempotent, the existing ones are kept
+        QueueValidatedArgs::AMQP(amqp_params) => AmqpQueue::new_with_args(amqp_params.clone()).setup().await?,
+        // the in memory queues are created along with the client
+        QueueValidatedArgs::InMemory => {}
     }
     Ok(())
 }
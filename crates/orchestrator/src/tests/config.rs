@@ -1,10 +1,13 @@
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::str::FromStr as _;
 use std::sync::Arc;
+use std::time::Duration;
 
 use alloy::primitives::Address;
 use axum::Router;
 use cairo_vm::types::layout_name::LayoutName;
+use clap::ValueEnum as _;
 use da_client_interface::{DaClient, MockDaClient};
 use ethereum_da_client::EthereumDaValidatedArgs;
 use ethereum_settlement_client::EthereumSettlementValidatedArgs;
@@ -33,13 +36,25 @@ use crate::config::{get_aws_config, Config, OrchestratorParams, ProviderConfig,
 use crate::data_storage::aws_s3::AWSS3ValidatedArgs;
 use crate::data_storage::{DataStorage, MockDataStorage};
 use crate::database::mongodb::MongoDBValidatedArgs;
+use crate::database::postgres::PostgresValidatedArgs;
 use crate::database::{Database, MockDatabase};
-use crate::helpers::{JobProcessingState, ProcessingLocks};
+use crate::helpers::{JobProcessingState, ProcessingLocks, DEFAULT_JOB_PROCESSING_LEASE_TTL, SNOS_JOB_PROCESSING_LOCK};
+use crate::jobs::types::JobType;
+use crate::metrics::backlog::DEFAULT_BACKLOG_METRICS_INTERVAL;
+use crate::queue::consumer::{
+    parse_queue_concurrency_overrides, parse_queue_names, parse_queue_transient_retries_overrides, QueueConsumerParams,
+    DEFAULT_QUEUE_CONCURRENCY, DEFAULT_RECEIVE_BATCH_SIZE, DEFAULT_RECEIVE_WAIT, DEFAULT_TRANSIENT_RETRIES,
+    DEFAULT_TRANSIENT_RETRY_DELAY,
+};
 use crate::queue::sqs::AWSSQSValidatedArgs;
-use crate::queue::{MockQueueProvider, QueueProvider};
+use crate::queue::{MockQueueProvider, QueueProvider, QueueType};
 use crate::routes::{get_server_url, setup_server, ServerParams};
+use crate::shutdown::DEFAULT_SHUTDOWN_TIMEOUT;
 use crate::telemetry::InstrumentationParams;
 use crate::tests::common::{create_queues, create_sns_arn, drop_database};
+use crate::workers::circuit_breaker::{CircuitBreakerParams, HaltScope};
+use crate::workers::leader_election::DEFAULT_WORKER_LEADER_LEASE_TTL;
+use crate::workers::stuck_job_reaper::{parse_stuck_job_deadlines, StuckJobReaperParams, DEFAULT_STUCK_JOB_DEADLINE};
 
 // Inspiration : https://rust-unofficial.github.io/patterns/patterns/creational/builder.html
 // TestConfigBuilder allows to heavily customise the global configs based on the test's requirement.
@@ -118,6 +133,8 @@ pub struct TestConfigBuilder {
     storage_type: ConfigType,
     /// API Service
     api_server_type: ConfigType,
+    /// Overrides the proof registration service param
+    register_proofs: Option<bool>,
 }
 
 impl Default for TestConfigBuilder {
@@ -147,6 +164,7 @@ impl TestConfigBuilder {
             storage_type: ConfigType::default(),
             alerts_type: ConfigType::default(),
             api_server_type: ConfigType::default(),
+            register_proofs: None,
         }
     }
 
@@ -199,10 +217,15 @@ impl TestConfigBuilder {
         self
     }
 
+    pub fn configure_register_proofs(mut self, register_proofs: bool) -> TestConfigBuilder {
+        self.register_proofs = Some(register_proofs);
+        self
+    }
+
     pub async fn build(self) -> TestConfigBuilderReturns {
         dotenvy::from_filename("../.env.test").expect("Failed to load the .env.test file");
 
-        let params = get_env_params();
+        let mut params = get_env_params();
 
         let provider_config = Arc::new(ProviderConfig::AWS(Box::new(get_aws_config(&params.aws_params).await)));
 
@@ -217,8 +240,13 @@ impl TestConfigBuilder {
             queue_type,
             storage_type,
             api_server_type,
+            register_proofs,
         } = self;
 
+        if let Some(register_proofs) = register_proofs {
+            params.orchestrator_params.service_config.register_proofs = register_proofs;
+        }
+
         let (_starknet_rpc_url, starknet_client, starknet_server) =
             implement_client::init_starknet_client(starknet_rpc_url_type, starknet_client_type).await;
 
@@ -248,8 +276,11 @@ impl TestConfigBuilder {
         // Creating the SNS ARN
         create_sns_arn(provider_config.clone(), &params.alert_params).await.expect("Unable to create the sns arn");
 
-        let snos_processing_lock =
-            JobProcessingState::new(params.orchestrator_params.service_config.max_concurrent_snos_jobs.unwrap_or(1));
+        let snos_processing_lock = JobProcessingState::new(
+            SNOS_JOB_PROCESSING_LOCK,
+            params.orchestrator_params.service_config.max_concurrent_snos_jobs.unwrap_or(1),
+            params.orchestrator_params.service_config.job_processing_lease_ttl,
+        );
         let processing_locks = ProcessingLocks { snos_job_processing_lock: Arc::new(snos_processing_lock) };
 
         let config = Arc::new(Config::new(
@@ -308,7 +339,8 @@ pub mod implement_client {
     use starknet::providers::{JsonRpcClient, Url};
 
     use super::{ConfigType, MockType};
-    use crate::alerts::{Alerts, MockAlerts};
+    use crate::alerts::in_memory::InMemoryAlerts;
+    use crate::alerts::Alerts;
     use crate::cli::alert::AlertValidatedArgs;
     use crate::cli::da::DaValidatedArgs;
     use crate::cli::database::DatabaseValidatedArgs;
@@ -318,7 +350,7 @@ pub mod implement_client {
     use crate::cli::storage::StorageValidatedArgs;
     use crate::config::{
         build_alert_client, build_da_client, build_database_client, build_prover_service, build_queue_client,
-        build_settlement_client, ProviderConfig,
+        build_settlement_client, build_storage_client, ProviderConfig,
     };
     use crate::data_storage::{DataStorage, MockDataStorage};
     use crate::database::{Database, MockDatabase};
@@ -387,7 +419,8 @@ pub mod implement_client {
         match service {
             ConfigType::Mock(client) => client.into(),
             ConfigType::Actual => build_alert_client(alert_params, provider_config).await,
-            ConfigType::Dummy => Box::new(MockAlerts::new()),
+            // failed jobs send alerts, the in memory alerts spare the tests from expecting them
+            ConfigType::Dummy => Box::new(InMemoryAlerts::new()),
         }
     }
 
@@ -404,6 +437,9 @@ pub mod implement_client {
                     storage.as_ref().create_bucket(&aws_s3_params.bucket_name).await.unwrap();
                     storage
                 }
+                StorageValidatedArgs::LocalFs(_) | StorageValidatedArgs::InMemory => {
+                    build_storage_client(storage_cfg, provider_config).await
+                }
             },
             ConfigType::Dummy => Box::new(MockDataStorage::new()),
         }
@@ -488,22 +524,46 @@ struct EnvParams {
 }
 
 fn get_env_params() -> EnvParams {
-    let db_params = DatabaseValidatedArgs::MongoDB(MongoDBValidatedArgs {
-        connection_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_MONGODB_CONNECTION_URL"))
-            .expect("Invalid MongoDB connection URL"),
-        database_name: get_env_var_or_panic("MADARA_ORCHESTRATOR_DATABASE_NAME"),
-    });
+    // The tests use the in memory database, queues, storage and alerts instead of the containers when
+    // set
+    let in_memory = get_env_var_optional("MADARA_ORCHESTRATOR_TEST_IN_MEMORY")
+        .expect("Couldn't get in memory flag")
+        .is_some_and(|s| s.parse::<bool>().unwrap_or(false));
+
+    // The tests run against Postgres when its connection URL is set, MongoDB otherwise
+    let db_params = match get_env_var_optional("MADARA_ORCHESTRATOR_POSTGRES_CONNECTION_URL")
+        .expect("Couldn't get Postgres connection URL")
+    {
+        _ if in_memory => DatabaseValidatedArgs::InMemory,
+        Some(connection_url) if !connection_url.is_empty() => DatabaseValidatedArgs::Postgres(PostgresValidatedArgs {
+            connection_url: Url::parse(&connection_url).expect("Invalid Postgres connection URL"),
+            max_connections: 5,
+        }),
+        _ => DatabaseValidatedArgs::MongoDB(MongoDBValidatedArgs {
+            connection_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_MONGODB_CONNECTION_URL"))
+                .expect("Invalid MongoDB connection URL"),
+            database_name: get_env_var_or_panic("MADARA_ORCHESTRATOR_DATABASE_NAME"),
+        }),
+    };
 
-    let storage_params = StorageValidatedArgs::AWSS3(AWSS3ValidatedArgs {
-        bucket_name: get_env_var_or_panic("MADARA_ORCHESTRATOR_AWS_S3_BUCKET_NAME"),
-    });
+    let storage_params = if in_memory {
+        StorageValidatedArgs::InMemory
+    } else {
+        StorageValidatedArgs::AWSS3(AWSS3ValidatedArgs {
+            bucket_name: get_env_var_or_panic("MADARA_ORCHESTRATOR_AWS_S3_BUCKET_NAME"),
+        })
+    };
 
-    let queue_params = QueueValidatedArgs::AWSSQS(AWSSQSValidatedArgs {
-        queue_base_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_SQS_BASE_QUEUE_URL"))
-            .expect("Invalid queue base URL"),
-        sqs_prefix: get_env_var_or_panic("MADARA_ORCHESTRATOR_SQS_PREFIX"),
-        sqs_suffix: get_env_var_or_panic("MADARA_ORCHESTRATOR_SQS_SUFFIX"),
-    });
+    let queue_params = if in_memory {
+        QueueValidatedArgs::InMemory
+    } else {
+        QueueValidatedArgs::AWSSQS(AWSSQSValidatedArgs {
+            queue_base_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_SQS_BASE_QUEUE_URL"))
+                .expect("Invalid queue base URL"),
+            sqs_prefix: get_env_var_or_panic("MADARA_ORCHESTRATOR_SQS_PREFIX"),
+            sqs_suffix: get_env_var_or_panic("MADARA_ORCHESTRATOR_SQS_SUFFIX"),
+        })
+    };
 
     let aws_params = AWSConfigValidatedArgs {
         aws_access_key_id: get_env_var_or_panic("AWS_ACCESS_KEY_ID"),
@@ -516,9 +576,13 @@ fn get_env_params() -> EnvParams {
             .expect("Failed to parse MADARA_ORCHESTRATOR_ETHEREUM_RPC_URL"),
     });
 
-    let alert_params = AlertValidatedArgs::AWSSNS(AWSSNSValidatedArgs {
-        topic_arn: get_env_var_or_panic("MADARA_ORCHESTRATOR_AWS_SNS_ARN"),
-    });
+    let alert_params = if in_memory {
+        AlertValidatedArgs::InMemory
+    } else {
+        AlertValidatedArgs::AWSSNS(AWSSNSValidatedArgs {
+            topic_arn: get_env_var_or_panic("MADARA_ORCHESTRATOR_AWS_SNS_ARN"),
+        })
+    };
 
     let settlement_params = SettlementValidatedArgs::Ethereum(EthereumSettlementValidatedArgs {
         ethereum_rpc_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_ETHEREUM_SETTLEMENT_RPC_URL"))
@@ -532,6 +596,8 @@ fn get_env_params() -> EnvParams {
             "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS",
         ))
         .expect("Invalid Starknet operator address"),
+        gps_verifier_contract_address: None,
+        memory_page_fact_registry_address: None,
     });
 
     let snos_config = SNOSParams {
@@ -550,8 +616,145 @@ fn get_env_params() -> EnvParams {
     let max_concurrent_snos_jobs: Option<usize> =
         env.and_then(|s| if s.is_empty() { None } else { Some(s.parse::<usize>().unwrap()) });
 
-    let service_config =
-        ServiceParams { max_block_to_process: max_block, min_block_to_process: min_block, max_concurrent_snos_jobs };
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_JOB_PROCESSING_LEASE_TTL")
+        .expect("Couldn't get job processing lease ttl");
+    let job_processing_lease_ttl: Duration = env
+        .and_then(|s| if s.is_empty() { None } else { Some(Duration::from_secs(s.parse::<u64>().unwrap())) })
+        .unwrap_or(DEFAULT_JOB_PROCESSING_LEASE_TTL);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_WORKER_LEADER_LEASE_TTL")
+        .expect("Couldn't get worker leader lease ttl");
+    let worker_leader_lease_ttl: Duration = env
+        .and_then(|s| if s.is_empty() { None } else { Some(Duration::from_secs(s.parse::<u64>().unwrap())) })
+        .unwrap_or(DEFAULT_WORKER_LEADER_LEASE_TTL);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_REGISTER_PROOFS").expect("Couldn't get register proofs");
+    let register_proofs: bool = env.is_some_and(|s| s.parse::<bool>().unwrap_or(false));
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_HALT_SCOPE").expect("Couldn't get halt scope");
+    let halt_scope: HaltScope = env
+        .filter(|s| !s.is_empty())
+        .map(|s| HaltScope::from_str(&s, true).expect("Failed to parse MADARA_ORCHESTRATOR_HALT_SCOPE"))
+        .unwrap_or_default();
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_HALT_FAILURE_THRESHOLD")
+        .expect("Couldn't get halt failure threshold");
+    let halt_failure_threshold: usize =
+        env.and_then(|s| if s.is_empty() { None } else { Some(s.parse::<usize>().unwrap()) }).unwrap_or(1);
+
+    let env =
+        get_env_var_optional("MADARA_ORCHESTRATOR_HALT_FAILURE_WINDOW").expect("Couldn't get halt failure window");
+    let halt_failure_window: Option<Duration> =
+        env.and_then(|s| if s.is_empty() { None } else { Some(Duration::from_secs(s.parse::<u64>().unwrap())) });
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_STUCK_JOB_DEADLINE").expect("Couldn't get stuck job deadline");
+    let stuck_job_deadline: Duration = env
+        .and_then(|s| if s.is_empty() { None } else { Some(Duration::from_secs(s.parse::<u64>().unwrap())) })
+        .unwrap_or(DEFAULT_STUCK_JOB_DEADLINE);
+
+    let env =
+        get_env_var_optional("MADARA_ORCHESTRATOR_STUCK_JOB_DEADLINES").expect("Couldn't get stuck job deadlines");
+    let stuck_job_deadlines: HashMap<JobType, Duration> = env
+        .map(|s| parse_stuck_job_deadlines(&s).expect("Failed to parse MADARA_ORCHESTRATOR_STUCK_JOB_DEADLINES"))
+        .unwrap_or_default();
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_STUCK_JOB_MAX_REQUEUES")
+        .expect("Couldn't get stuck job max requeues");
+    let stuck_job_max_requeues: usize =
+        env.and_then(|s| if s.is_empty() { None } else { Some(s.parse::<usize>().unwrap()) }).unwrap_or(3);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_SHUTDOWN_TIMEOUT").expect("Couldn't get shutdown timeout");
+    let shutdown_timeout: Duration = env
+        .and_then(|s| if s.is_empty() { None } else { Some(Duration::from_secs(s.parse::<u64>().unwrap())) })
+        .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_BACKLOG_METRICS_INTERVAL")
+        .expect("Couldn't get backlog metrics interval");
+    let backlog_metrics_interval: Duration = env
+        .and_then(|s| if s.is_empty() { None } else { Some(Duration::from_secs(s.parse::<u64>().unwrap())) })
+        .unwrap_or(DEFAULT_BACKLOG_METRICS_INTERVAL);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_QUEUE_CONCURRENCY").expect("Couldn't get queue concurrency");
+    let queue_concurrency: usize = env
+        .and_then(|s| if s.is_empty() { None } else { Some(s.parse::<usize>().unwrap()) })
+        .unwrap_or(DEFAULT_QUEUE_CONCURRENCY);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_QUEUE_CONCURRENCY_OVERRIDES")
+        .expect("Couldn't get queue concurrency overrides");
+    let queue_concurrency_overrides: HashMap<QueueType, usize> = env
+        .map(|s| {
+            parse_queue_concurrency_overrides(&s)
+                .expect("Failed to parse MADARA_ORCHESTRATOR_QUEUE_CONCURRENCY_OVERRIDES")
+        })
+        .unwrap_or_default();
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_QUEUE_RECEIVE_BATCH_SIZE")
+        .expect("Couldn't get queue receive batch size");
+    let queue_receive_batch_size: usize = env
+        .and_then(|s| if s.is_empty() { None } else { Some(s.parse::<usize>().unwrap()) })
+        .unwrap_or(DEFAULT_RECEIVE_BATCH_SIZE);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_QUEUE_RECEIVE_WAIT").expect("Couldn't get queue receive wait");
+    let queue_receive_wait: Duration = env
+        .and_then(|s| if s.is_empty() { None } else { Some(Duration::from_secs(s.parse::<u64>().unwrap())) })
+        .unwrap_or(DEFAULT_RECEIVE_WAIT);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_QUEUE_TRANSIENT_RETRIES")
+        .expect("Couldn't get queue transient retries");
+    let queue_transient_retries: usize = env
+        .and_then(|s| if s.is_empty() { None } else { Some(s.parse::<usize>().unwrap()) })
+        .unwrap_or(DEFAULT_TRANSIENT_RETRIES);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_QUEUE_TRANSIENT_RETRIES_OVERRIDES")
+        .expect("Couldn't get queue transient retries overrides");
+    let queue_transient_retries_overrides: HashMap<QueueType, usize> = env
+        .map(|s| {
+            parse_queue_transient_retries_overrides(&s)
+                .expect("Failed to parse MADARA_ORCHESTRATOR_QUEUE_TRANSIENT_RETRIES_OVERRIDES")
+        })
+        .unwrap_or_default();
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_QUEUE_TRANSIENT_RETRY_DELAY")
+        .expect("Couldn't get queue transient retry delay");
+    let queue_transient_retry_delay: Duration = env
+        .and_then(|s| if s.is_empty() { None } else { Some(Duration::from_secs(s.parse::<u64>().unwrap())) })
+        .unwrap_or(DEFAULT_TRANSIENT_RETRY_DELAY);
+
+    let env = get_env_var_optional("MADARA_ORCHESTRATOR_DISABLED_CONSUMERS").expect("Couldn't get disabled consumers");
+    let disabled_consumers: HashSet<QueueType> = env
+        .map(|s| parse_queue_names(&s).expect("Failed to parse MADARA_ORCHESTRATOR_DISABLED_CONSUMERS"))
+        .unwrap_or_default();
+
+    let service_config = ServiceParams {
+        max_block_to_process: max_block,
+        min_block_to_process: min_block,
+        max_concurrent_snos_jobs,
+        job_processing_lease_ttl,
+        worker_leader_lease_ttl,
+        register_proofs,
+        circuit_breaker: CircuitBreakerParams {
+            scope: halt_scope,
+            failure_threshold: halt_failure_threshold,
+            failure_window: halt_failure_window,
+        },
+        stuck_job_reaper: StuckJobReaperParams {
+            deadlines: stuck_job_deadlines,
+            default_deadline: stuck_job_deadline,
+            max_requeues: stuck_job_max_requeues,
+        },
+        shutdown_timeout,
+        backlog_metrics_interval,
+        queue_consumers: QueueConsumerParams {
+            concurrency: queue_concurrency,
+            concurrency_overrides: queue_concurrency_overrides,
+            receive_batch_size: queue_receive_batch_size,
+            receive_wait: queue_receive_wait,
+            transient_retries: queue_transient_retries,
+            transient_retries_overrides: queue_transient_retries_overrides,
+            transient_retry_delay: queue_transient_retry_delay,
+            disabled_consumers,
+        },
+    };
 
     let server_config = ServerParams {
         host: get_env_var_or_panic("MADARA_ORCHESTRATOR_HOST"),
@@ -575,6 +778,9 @@ fn get_env_params() -> EnvParams {
         otel_collector_endpoint: get_env_var_optional("MADARA_ORCHESTRATOR_OTEL_COLLECTOR_ENDPOINT")
             .expect("Couldn't get otel collector endpoint")
             .map(|url| Url::parse(&url).expect("Failed to parse MADARA_ORCHESTRATOR_OTEL_COLLECTOR_ENDPOINT")),
+        prometheus_metrics: get_env_var_optional("MADARA_ORCHESTRATOR_PROMETHEUS_METRICS")
+            .expect("Couldn't get prometheus metrics")
+            .is_some_and(|s| s.parse::<bool>().unwrap_or(false)),
     };
 
     let prover_params = ProverValidatedArgs::Sharp(SharpValidatedArgs {
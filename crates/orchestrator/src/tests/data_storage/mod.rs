@@ -2,6 +2,9 @@ use bytes::Bytes;
 use rstest::rstest;
 use serde_json::json;
 
+use crate::data_storage::in_memory::InMemoryStorage;
+use crate::data_storage::local_fs::{LocalFsStorage, LocalFsValidatedArgs};
+use crate::data_storage::DataStorage;
 use crate::tests::config::{ConfigType, TestConfigBuilder};
 
 /// This test checks the ability to put and get data from AWS S3 using `AWSS3`.
@@ -34,3 +37,43 @@ async fn test_put_and_get_data_s3() -> color_eyre::Result<()> {
 
     Ok(())
 }
+
+/// This test checks the ability to put and get data from the local filesystem using
+/// `LocalFsStorage`, overwriting an existing file and rejecting keys outside of the root dir.
+#[rstest]
+#[tokio::test]
+async fn test_put_and_get_data_local_fs() -> color_eyre::Result<()> {
+    let root_dir = tempfile::tempdir()?;
+    let local_fs = LocalFsStorage::new_with_args(&LocalFsValidatedArgs { root_dir: root_dir.path().to_path_buf() });
+
+    let key = "1/snos_output.json";
+    local_fs.put_data(Bytes::from("first"), key).await.expect("Unable to put data into the directory.");
+    local_fs.put_data(Bytes::from("second"), key).await.expect("Unable to overwrite data in the directory.");
+
+    let data = local_fs.get_data(key).await.expect("Unable to get the data from the directory.");
+    assert_eq!(data, Bytes::from("second"));
+    // no temporary file should be left behind
+    assert_eq!(std::fs::read_dir(root_dir.path().join("1"))?.count(), 1);
+
+    assert!(local_fs.get_data("2/snos_output.json").await.is_err());
+    assert!(local_fs.put_data(Bytes::from("data"), "../outside.txt").await.is_err());
+    assert!(local_fs.put_data(Bytes::from("data"), "/tmp/outside.txt").await.is_err());
+
+    Ok(())
+}
+
+/// This test checks the ability to put and get data using `InMemoryStorage`.
+#[rstest]
+#[tokio::test]
+async fn test_put_and_get_data_in_memory() -> color_eyre::Result<()> {
+    let storage = InMemoryStorage::new();
+
+    let key = "1/snos_output.json";
+    storage.put_data(Bytes::from("first"), key).await?;
+    storage.put_data(Bytes::from("second"), key).await?;
+
+    assert_eq!(storage.get_data(key).await?, Bytes::from("second"));
+    assert!(storage.get_data("2/snos_output.json").await.is_err());
+
+    Ok(())
+}
@@ -1,8 +1,15 @@
+use std::time::Duration;
+
+use chrono::{SubsecRound, Utc};
 use rstest::*;
+use uuid::Uuid;
 
+use crate::database::{Fence, JobCursor, JobFilter};
 use crate::jobs::metadata::JobSpecificMetadata;
 use crate::jobs::types::{JobItemUpdates, JobStatus, JobType};
 use crate::jobs::JobError;
+use crate::queue::dead_letter::{DeadLetterAction, DeadLetterAuditEntry};
+use crate::queue::QueueType;
 use crate::tests::config::{ConfigType, TestConfigBuilder};
 use crate::tests::utils::build_job_item;
 
@@ -189,6 +196,110 @@ async fn database_get_jobs_after_internal_id_by_job_type_works() {
     assert_eq!(jobs_after_internal_id[1], job_vec[5]);
 }
 
+/// Test for `get_jobs` operation in database trait.
+///
+/// - Creates 6 jobs of two types.
+///
+/// - Should return the filtered jobs one page at a time, each job exactly once
+#[rstest]
+#[tokio::test]
+async fn database_get_jobs_with_filter_and_cursor_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+
+    let job_vec = [
+        build_job_item(JobType::SnosRun, JobStatus::Completed, 1),
+        build_job_item(JobType::SnosRun, JobStatus::Failed, 2),
+        build_job_item(JobType::ProofCreation, JobStatus::Completed, 3),
+        build_job_item(JobType::SnosRun, JobStatus::Completed, 4),
+        build_job_item(JobType::SnosRun, JobStatus::Completed, 5),
+        build_job_item(JobType::SnosRun, JobStatus::Completed, 6),
+    ];
+    for job in job_vec.iter() {
+        database_client.create_job(job.clone()).await.unwrap();
+    }
+
+    let filter = JobFilter {
+        job_type: Some(JobType::SnosRun),
+        status: Some(JobStatus::Completed),
+        internal_id_from: Some(2),
+        ..Default::default()
+    };
+
+    let first_page = database_client.get_jobs(filter.clone(), None, 2).await.unwrap();
+    assert_eq!(first_page.len(), 2, "Number of jobs in the first page assertion failed");
+    let cursor = JobCursor::from(first_page.last().unwrap());
+    let second_page = database_client.get_jobs(filter, Some(cursor), 2).await.unwrap();
+    assert_eq!(second_page.len(), 1, "Number of jobs in the second page assertion failed");
+
+    let mut internal_ids: Vec<String> =
+        first_page.iter().chain(second_page.iter()).map(|job| job.internal_id.clone()).collect();
+    internal_ids.sort();
+    assert_eq!(internal_ids, vec!["4", "5", "6"]);
+
+    // the cursor round trips through its string representation
+    assert_eq!(cursor.to_string().parse::<JobCursor>().unwrap(), cursor);
+}
+
+#[rstest]
+#[tokio::test]
+async fn database_count_jobs_by_type_and_status_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let database_client = services.config.database();
+
+    let job_vec = [
+        build_job_item(JobType::SnosRun, JobStatus::Completed, 1),
+        build_job_item(JobType::SnosRun, JobStatus::Completed, 2),
+        build_job_item(JobType::SnosRun, JobStatus::Failed, 3),
+        build_job_item(JobType::ProofCreation, JobStatus::Completed, 1),
+    ];
+    for job in job_vec.iter() {
+        database_client.create_job(job.clone()).await.unwrap();
+    }
+
+    let mut counts = database_client.count_jobs_by_type_and_status().await.unwrap();
+    counts.sort_by_key(|(job_type, status, _)| format!("{:?}_{:?}", job_type, status));
+    assert_eq!(
+        counts,
+        vec![
+            (JobType::ProofCreation, JobStatus::Completed, 1),
+            (JobType::SnosRun, JobStatus::Completed, 2),
+            (JobType::SnosRun, JobStatus::Failed, 1),
+        ]
+    );
+}
+
+/// The first block is the lowest one as a number, among the jobs which failed within the window.
+#[rstest]
+#[tokio::test]
+async fn database_get_first_block_of_jobs_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let database_client = services.config.database();
+    let now = Utc::now().round_subsecs(0);
+
+    let mut old_failed_job = build_job_item(JobType::SnosRun, JobStatus::Failed, 2);
+    old_failed_job.metadata.common.failed_at = Some(now - chrono::Duration::seconds(120));
+    let job_vec = [
+        old_failed_job,
+        build_job_item(JobType::SnosRun, JobStatus::Failed, 10),
+        build_job_item(JobType::SnosRun, JobStatus::Failed, 9),
+        build_job_item(JobType::SnosRun, JobStatus::Completed, 1),
+    ];
+    for job in job_vec.iter() {
+        database_client.create_job(job.clone()).await.unwrap();
+    }
+
+    let filter = JobFilter { job_type: Some(JobType::SnosRun), status: Some(JobStatus::Failed), ..Default::default() };
+    assert_eq!(database_client.get_first_block_of_jobs(filter.clone()).await.unwrap(), Some(2));
+
+    let filter = JobFilter { failed_after: Some(now - chrono::Duration::seconds(60)), ..filter };
+    assert_eq!(database_client.get_first_block_of_jobs(filter.clone()).await.unwrap(), Some(9));
+
+    let filter = JobFilter { job_type: Some(JobType::ProofCreation), ..filter };
+    assert_eq!(database_client.get_first_block_of_jobs(filter).await.unwrap(), None);
+}
+
 #[rstest]
 #[tokio::test]
 async fn database_test_update_job() {
@@ -240,3 +351,169 @@ async fn database_test_update_job() {
         panic!("Job not found in Database.")
     }
 }
+
+/// Test for the optimistic locking of `update_job`.
+/// Updating a job with an outdated version should fail and leave the job untouched.
+#[rstest]
+#[tokio::test]
+async fn database_update_job_with_outdated_version_fails() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+
+    let job = build_job_item(JobType::DataSubmission, JobStatus::Created, 457);
+    database_client.create_job(job.clone()).await.unwrap();
+
+    database_client
+        .update_job(&job, JobItemUpdates::new().update_status(JobStatus::LockedForProcessing).build())
+        .await
+        .unwrap();
+
+    // `job` still has version 0 while the job in the database has version 1
+    let result = database_client
+        .update_job(&job, JobItemUpdates::new().update_status(JobStatus::PendingVerification).build())
+        .await;
+    assert!(result.is_err());
+
+    let job_after_updates_db = database_client.get_job_by_id(job.id).await.unwrap().unwrap();
+    assert_eq!(JobStatus::LockedForProcessing, job_after_updates_db.status);
+    assert_eq!(1, job_after_updates_db.version);
+}
+
+/// Tests for the lease semaphore operations in database trait.
+/// Takes the 2 slots of a semaphore, checks that a third holder can't get one until a slot is
+/// released or its lease expires.
+#[rstest]
+#[tokio::test]
+async fn database_lease_semaphore_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+
+    let lease_ttl = Duration::from_secs(60);
+    let (holder_1, holder_2, holder_3) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+    assert!(database_client.acquire_lease("test_lock", 2, holder_1, lease_ttl).await.unwrap());
+    assert!(database_client.acquire_lease("test_lock", 2, holder_2, lease_ttl).await.unwrap());
+    assert!(!database_client.acquire_lease("test_lock", 2, holder_3, lease_ttl).await.unwrap());
+    // a holder keeps its slot when acquiring again
+    assert!(database_client.acquire_lease("test_lock", 2, holder_1, lease_ttl).await.unwrap());
+    // other semaphores are independent
+    assert!(database_client.acquire_lease("other_lock", 2, holder_3, lease_ttl).await.unwrap());
+
+    let mut holders = database_client.get_lease_holders("test_lock").await.unwrap();
+    holders.sort();
+    let mut expected_holders = vec![holder_1, holder_2];
+    expected_holders.sort();
+    assert_eq!(holders, expected_holders);
+
+    database_client.release_lease("test_lock", holder_1).await.unwrap();
+    assert!(!database_client.renew_lease("test_lock", holder_1, lease_ttl).await.unwrap());
+    assert!(database_client.acquire_lease("test_lock", 2, holder_3, lease_ttl).await.unwrap());
+
+    // an expired lease frees its slot, as if its holder crashed
+    assert!(database_client.renew_lease("test_lock", holder_2, Duration::from_secs(1)).await.unwrap());
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert!(database_client.acquire_lease("test_lock", 2, holder_1, lease_ttl).await.unwrap());
+    assert!(!database_client.renew_lease("test_lock", holder_2, lease_ttl).await.unwrap());
+}
+
+/// Tests for the leader election operations in database trait.
+/// Checks that a single candidate leads at a time and that the fencing token increases every
+/// time the leadership changes hands.
+#[rstest]
+#[tokio::test]
+async fn database_leader_election_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+
+    let lease_ttl = Duration::from_secs(60);
+    let (candidate_1, candidate_2) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let fencing_token_1 = database_client.acquire_leadership("test_election", candidate_1, lease_ttl).await.unwrap();
+    assert!(fencing_token_1.is_some());
+    assert_eq!(database_client.acquire_leadership("test_election", candidate_2, lease_ttl).await.unwrap(), None);
+    // the leader keeps its fencing token
+    assert_eq!(
+        database_client.acquire_leadership("test_election", candidate_1, lease_ttl).await.unwrap(),
+        fencing_token_1
+    );
+    let fencing_token_1 = fencing_token_1.unwrap();
+    assert!(database_client.renew_leadership("test_election", candidate_1, fencing_token_1, lease_ttl).await.unwrap());
+
+    // a released leadership goes to the next candidate, with the next fencing token
+    database_client.release_leadership("test_election", candidate_1, fencing_token_1).await.unwrap();
+    let fencing_token_2 =
+        database_client.acquire_leadership("test_election", candidate_2, lease_ttl).await.unwrap().unwrap();
+    assert!(fencing_token_2 > fencing_token_1);
+    assert!(!database_client.renew_leadership("test_election", candidate_1, fencing_token_1, lease_ttl).await.unwrap());
+
+    // an expired leadership is taken over, as if its leader crashed
+    assert!(
+        database_client
+            .renew_leadership("test_election", candidate_2, fencing_token_2, Duration::from_secs(1))
+            .await
+            .unwrap()
+    );
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let fencing_token_3 =
+        database_client.acquire_leadership("test_election", candidate_1, lease_ttl).await.unwrap().unwrap();
+    assert!(fencing_token_3 > fencing_token_2);
+    assert!(!database_client.renew_leadership("test_election", candidate_2, fencing_token_2, lease_ttl).await.unwrap());
+}
+
+/// Jobs created under a leadership are rejected once another candidate took it over.
+#[rstest]
+#[tokio::test]
+async fn database_create_job_fenced_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let database_client = services.config.database();
+
+    let (candidate_1, candidate_2) = (Uuid::new_v4(), Uuid::new_v4());
+    let fencing_token_1 = database_client
+        .acquire_leadership("test_election", candidate_1, Duration::from_secs(60))
+        .await
+        .unwrap()
+        .unwrap();
+    let fence_1 = Fence { election: "test_election".to_string(), fencing_token: fencing_token_1 };
+    database_client.create_job_fenced(build_job_item(JobType::SnosRun, JobStatus::Created, 1), &fence_1).await.unwrap();
+
+    database_client.release_leadership("test_election", candidate_1, fencing_token_1).await.unwrap();
+    database_client.acquire_leadership("test_election", candidate_2, Duration::from_secs(60)).await.unwrap().unwrap();
+    let result =
+        database_client.create_job_fenced(build_job_item(JobType::SnosRun, JobStatus::Created, 2), &fence_1).await;
+    assert!(matches!(result, Err(JobError::StaleFence { .. })));
+    assert!(database_client.get_job_by_internal_id_and_type("2", &JobType::SnosRun).await.unwrap().is_none());
+}
+
+/// Tests for the dead letter audit log operations in database trait.
+/// Appends 3 entries and checks that the latest ones are listed first.
+#[rstest]
+#[tokio::test]
+async fn database_dead_letter_audit_log_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+
+    let base_time = Utc::now().round_subsecs(3);
+    let entries: Vec<DeadLetterAuditEntry> =
+        [DeadLetterAction::Replayed, DeadLetterAction::Discarded, DeadLetterAction::Replayed]
+            .into_iter()
+            .enumerate()
+            .map(|(i, action)| DeadLetterAuditEntry {
+                id: Uuid::new_v4(),
+                job_id: Uuid::new_v4(),
+                action,
+                actor: "operator".to_string(),
+                source_queue: QueueType::SnosJobProcessing,
+                created_at: base_time + chrono::Duration::seconds(i as i64),
+            })
+            .collect();
+    for entry in &entries {
+        database_client.create_dead_letter_audit_entry(entry.clone()).await.unwrap();
+    }
+
+    let latest_entries = database_client.get_dead_letter_audit_entries(2).await.unwrap();
+    assert_eq!(latest_entries, vec![entries[2].clone(), entries[1].clone()]);
+}
@@ -12,10 +12,14 @@ use crate::jobs::job_handler_factory::mock_factory;
 use crate::jobs::metadata::{
     CommonMetadata, JobMetadata, JobSpecificMetadata, ProvingInputType, ProvingMetadata, SnosMetadata,
 };
+use crate::jobs::retry_policy::RetryPolicy;
 use crate::jobs::types::{ExternalId, JobStatus, JobType, JobVerificationStatus};
-use crate::jobs::{create_job, handle_job_failure, process_job, retry_job, verify_job, Job, JobError, MockJob};
+use crate::jobs::{
+    create_job, fail_job_after_transient_retries, handle_job_failure, process_job, retry_job, verify_job, Job,
+    JobError, MockJob,
+};
 use crate::queue::job_queue::QueueNameForJobType;
-use crate::queue::QueueType;
+use crate::queue::{DeadLetterSource, QueueType};
 use crate::tests::common::MessagePayloadType;
 use crate::tests::config::{ConfigType, TestConfigBuilder};
 use crate::tests::utils::build_job_item;
@@ -26,6 +30,9 @@ pub mod da_job;
 #[cfg(test)]
 pub mod proving_job;
 
+#[cfg(test)]
+pub mod register_proof_job;
+
 #[cfg(test)]
 pub mod state_update_job;
 
@@ -69,7 +76,7 @@ async fn create_job_job_does_not_exists_in_db_works() {
         }),
     };
 
-    assert!(create_job(JobType::SnosRun, "0".to_string(), metadata, services.config.clone()).await.is_ok());
+    assert!(create_job(JobType::SnosRun, "0".to_string(), metadata, None, services.config.clone()).await.is_ok());
 
     // Db checks.
     let job_in_db = services.config.database().get_job_by_id(job_item.id).await.unwrap().unwrap();
@@ -113,7 +120,7 @@ async fn create_job_job_exists_in_db_works() {
         }),
     };
 
-    assert!(create_job(JobType::ProofCreation, "0".to_string(), metadata, services.config.clone()).await.is_ok());
+    assert!(create_job(JobType::ProofCreation, "0".to_string(), metadata, None, services.config.clone()).await.is_ok());
 
     // There should be only 1 job in the db
     let jobs_in_db = database_client.get_jobs_by_statuses(vec![JobStatus::Created], None).await.unwrap();
@@ -157,7 +164,7 @@ async fn create_job_job_handler_is_not_implemented_panics() {
         }),
     };
 
-    assert!(create_job(job_type.clone(), "0".to_string(), metadata, services.config.clone()).await.is_err());
+    assert!(create_job(job_type.clone(), "0".to_string(), metadata, None, services.config.clone()).await.is_err());
 
     // Waiting for 5 secs for message to be passed into the queue
     sleep(Duration::from_secs(5)).await;
@@ -195,7 +202,7 @@ async fn process_job_with_job_exists_in_db_and_valid_job_processing_status_works
     database_client.create_job(job_item.clone()).await.unwrap();
     // Expecting process job function in job processor to return the external ID.
     job_handler.expect_process_job().times(1).returning(move |_, _| Ok("0xbeef".to_string()));
-    job_handler.expect_verification_polling_delay_seconds().return_const(1u64);
+    job_handler.expect_verification_polling_policy().return_const(RetryPolicy::fixed(Duration::from_secs(1)));
     job_handler.expect_job_processing_lock().return_const(None);
 
     // Mocking the `get_job_handler` call in create_job function.
@@ -347,7 +354,7 @@ async fn process_job_two_workers_process_same_job_works() {
     let mut job_handler = MockJob::new();
     // Expecting process job function in job processor to return the external ID.
     job_handler.expect_process_job().times(1).returning(move |_, _| Ok("0xbeef".to_string()));
-    job_handler.expect_verification_polling_delay_seconds().return_const(1u64);
+    job_handler.expect_verification_polling_policy().return_const(RetryPolicy::fixed(Duration::from_secs(1)));
     job_handler.expect_job_processing_lock().return_const(None);
 
     // Mocking the `get_job_handler` call in create_job function.
@@ -403,7 +410,7 @@ async fn process_job_job_handler_returns_error_works() {
         .expect_process_job()
         .times(1)
         .returning(move |_, _| Err(JobError::Other(failure_reason.to_string().into())));
-    job_handler.expect_verification_polling_delay_seconds().return_const(1u64);
+    job_handler.expect_verification_polling_policy().return_const(RetryPolicy::fixed(Duration::from_secs(1)));
     job_handler.expect_job_processing_lock().return_const(None);
 
     // Mocking the `get_job_handler` call in create_job function.
@@ -431,6 +438,46 @@ async fn process_job_job_handler_returns_error_works() {
     assert!(final_job_in_db.metadata.common.failure_reason.as_ref().unwrap().contains(failure_reason));
 }
 
+/// Tests `process_job` function when the job handler returns a transient error.
+/// The job should be unlocked for its message to be retried, and failed once it ran out of
+/// retries.
+#[rstest]
+#[tokio::test]
+async fn process_job_job_handler_returns_transient_error_works() {
+    let mut job_handler = MockJob::new();
+    let failure_reason = "RPC request timed out";
+    job_handler
+        .expect_process_job()
+        .times(1)
+        .returning(move |_, _| Err(JobError::Transient(failure_reason.to_string().into())));
+    job_handler.expect_verification_polling_policy().return_const(RetryPolicy::fixed(Duration::from_secs(1)));
+    job_handler.expect_job_processing_lock().return_const(None);
+
+    let job_handler: Arc<Box<dyn Job>> = Arc::new(Box::new(job_handler));
+    let ctx = mock_factory::get_job_handler_context();
+    ctx.expect().times(1).with(eq(JobType::SnosRun)).returning(move |_| Arc::clone(&job_handler));
+
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .build()
+        .await;
+    let db_client = services.config.database();
+
+    let job_item = build_job_item(JobType::SnosRun, JobStatus::Created, 1);
+    db_client.create_job(job_item.clone()).await.unwrap();
+
+    let error = process_job(job_item.id, services.config.clone()).await.unwrap_err();
+    assert!(error.is_transient());
+    let job_in_db = db_client.get_job_by_id(job_item.id).await.unwrap().unwrap();
+    assert_eq!(job_in_db.status, JobStatus::PendingRetry);
+
+    assert!(fail_job_after_transient_retries(job_item.id, &error, services.config.clone()).await.unwrap());
+    let final_job_in_db = db_client.get_job_by_id(job_item.id).await.unwrap().unwrap();
+    assert_eq!(final_job_in_db.status, JobStatus::Failed);
+    assert!(final_job_in_db.metadata.common.failure_reason.as_ref().unwrap().contains(failure_reason));
+}
+
 /// Tests `verify_job` function when job is having expected status
 /// and returns a `Verified` verification status.
 #[rstest]
@@ -498,6 +545,7 @@ async fn verify_job_with_rejected_status_adds_to_queue_works() {
     database_client.create_job(job_item.clone()).await.unwrap();
     job_handler.expect_verify_job().times(1).returning(move |_, _| Ok(JobVerificationStatus::Rejected("".to_string())));
     job_handler.expect_max_process_attempts().returning(move || 2u64);
+    job_handler.expect_process_retry_policy().returning(move || RetryPolicy::fixed(Duration::from_secs(1)));
 
     let job_handler: Arc<Box<dyn Job>> = Arc::new(Box::new(job_handler));
     let ctx = mock_factory::get_job_handler_context();
@@ -509,6 +557,7 @@ async fn verify_job_with_rejected_status_adds_to_queue_works() {
     // DB checks.
     let updated_job = database_client.get_job_by_id(job_item.id).await.unwrap().unwrap();
     assert_eq!(updated_job.status, JobStatus::VerificationFailed);
+    assert!(updated_job.metadata.common.next_attempt_at.is_some());
 
     // Waiting for 5 secs for message to be passed into the queue
     sleep(Duration::from_secs(5)).await;
@@ -596,7 +645,7 @@ async fn verify_job_with_pending_status_adds_to_queue_works() {
     // Expecting verify_job function to return Pending status
     job_handler.expect_verify_job().times(1).returning(move |_, _| Ok(JobVerificationStatus::Pending));
     job_handler.expect_max_verification_attempts().returning(move || 2u64);
-    job_handler.expect_verification_polling_delay_seconds().returning(move || 2u64);
+    job_handler.expect_verification_polling_policy().returning(move || RetryPolicy::fixed(Duration::from_secs(2)));
 
     // Mocking the `get_job_handler` call
     let job_handler: Arc<Box<dyn Job>> = Arc::new(Box::new(job_handler));
@@ -651,7 +700,7 @@ async fn verify_job_with_pending_status_works() {
     // Expecting verify_job function to return Pending status
     job_handler.expect_verify_job().times(1).returning(move |_, _| Ok(JobVerificationStatus::Pending));
     job_handler.expect_max_verification_attempts().returning(move || 1u64);
-    job_handler.expect_verification_polling_delay_seconds().returning(move || 2u64);
+    job_handler.expect_verification_polling_policy().returning(move || RetryPolicy::fixed(Duration::from_secs(2)));
 
     // Mocking the `get_job_handler` call
     let job_handler: Arc<Box<dyn Job>> = Arc::new(Box::new(job_handler));
@@ -680,8 +729,7 @@ async fn verify_job_with_pending_status_works() {
 #[case(JobType::DataSubmission, JobStatus::Completed)] // code should panic here, how can completed move to dl queue ?
 #[case(JobType::SnosRun, JobStatus::PendingVerification)]
 #[case(JobType::ProofCreation, JobStatus::LockedForProcessing)]
-// #[case(JobType::ProofRegistration, JobStatus::Created)] TODO: add this case when we have the metadata for proof
-// registration
+#[case(JobType::ProofRegistration, JobStatus::Created)]
 #[case(JobType::StateTransition, JobStatus::Completed)]
 #[case(JobType::ProofCreation, JobStatus::VerificationTimeout)]
 #[case(JobType::DataSubmission, JobStatus::VerificationFailed)]
@@ -708,13 +756,19 @@ async fn handle_job_failure_with_failed_job_status_works(#[case] job_type: JobTy
     database_client.create_job(job_expected.clone()).await.unwrap();
 
     // Calling handle_job_failure
-    handle_job_failure(job_id, services.config.clone()).await.expect("handle_job_failure failed to run");
+    handle_job_failure(job_id, DeadLetterSource::default(), services.config.clone())
+        .await
+        .expect("handle_job_failure failed to run");
 
-    // Fetch the job from DB and verify it's unchanged (since it's already in Failed status)
+    // Fetch the job from DB and verify it's still failed, with its dead letter recorded
     let job_fetched =
         services.config.database().get_job_by_id(job_id).await.expect("Unable to fetch Job Data").unwrap();
 
-    assert_eq!(job_fetched, job_expected);
+    assert_eq!(job_fetched.status, JobStatus::Failed);
+    assert_eq!(job_fetched.metadata.common.failure_reason, job_expected.metadata.common.failure_reason);
+    let dead_letter = job_fetched.metadata.common.dead_letter.expect("Dead letter isn't recorded");
+    assert_eq!(dead_letter.source_queue, job_type.process_queue_name());
+    assert_eq!(dead_letter.job_status, JobStatus::Failed);
 }
 
 #[rstest]
@@ -739,7 +793,9 @@ async fn handle_job_failure_with_correct_job_status_works(#[case] job_type: JobT
     database_client.create_job(job.clone()).await.unwrap();
 
     // Calling handle_job_failure
-    handle_job_failure(job_id, services.config.clone()).await.expect("handle_job_failure failed to run");
+    handle_job_failure(job_id, DeadLetterSource::default(), services.config.clone())
+        .await
+        .expect("handle_job_failure failed to run");
 
     let job_fetched =
         services.config.database().get_job_by_id(job_id).await.expect("Unable to fetch Job Data").unwrap();
@@ -753,9 +809,40 @@ async fn handle_job_failure_with_correct_job_status_works(#[case] job_type: JobT
     job_expected.metadata.common.failure_reason =
         Some(format!("Received failure queue message for job with status: {}", job_status));
 
+    // the dead letter is kept to be replayed on the verification queue
+    let dead_letter = job_fetched.metadata.common.dead_letter.clone().expect("Dead letter isn't recorded");
+    assert_eq!(dead_letter.source_queue, job_type.verify_queue_name());
+    assert_eq!(dead_letter.receive_count, 5);
+    assert_eq!(dead_letter.job_status, job_status);
+    job_expected.metadata.common.dead_letter = Some(dead_letter);
+
     assert_eq!(job_fetched, job_expected);
 }
 
+#[rstest]
+#[tokio::test]
+async fn handle_job_failure_records_the_broker_source() {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .build()
+        .await;
+
+    // the job is waiting on its verification, but its processing message was the one dead lettered
+    let job = build_job_item(JobType::SnosRun, JobStatus::PendingVerification, 1);
+    services.config.database().create_job(job.clone()).await.unwrap();
+    let source = DeadLetterSource { queue: Some(QueueType::SnosJobProcessing), receive_count: Some(7) };
+
+    handle_job_failure(job.id, source, services.config.clone()).await.expect("handle_job_failure failed to run");
+
+    let job_fetched = services.config.database().get_job_by_id(job.id).await.unwrap().unwrap();
+    assert_eq!(job_fetched.status, JobStatus::Failed);
+    let dead_letter = job_fetched.metadata.common.dead_letter.expect("Dead letter isn't recorded");
+    assert_eq!(dead_letter.source_queue, QueueType::SnosJobProcessing);
+    assert_eq!(dead_letter.receive_count, 7);
+    assert_eq!(dead_letter.job_status, JobStatus::PendingVerification);
+}
+
 #[rstest]
 #[case(JobType::DataSubmission)]
 #[tokio::test]
@@ -779,7 +866,7 @@ async fn handle_job_failure_job_status_completed_works(#[case] job_type: JobType
     database_client.create_job(job_expected.clone()).await.unwrap();
 
     // Calling handle_job_failure
-    handle_job_failure(job_id, services.config.clone())
+    handle_job_failure(job_id, DeadLetterSource::default(), services.config.clone())
         .await
         .expect("Test call to handle_job_failure should have passed.");
 
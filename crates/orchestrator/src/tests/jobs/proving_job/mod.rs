@@ -4,23 +4,28 @@ use std::path::Path;
 
 use bytes::Bytes;
 use chrono::{SubsecRound, Utc};
+use color_eyre::eyre::eyre;
 use httpmock::prelude::*;
 use mockall::predicate::eq;
 use prover_client_interface::{MockProverClient, TaskStatus};
 use rstest::*;
+use settlement_client_interface::MockSettlementClient;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use url::Url;
 use uuid::Uuid;
 
 use super::super::common::default_job_item;
-use crate::constants::CAIRO_PIE_FILE_NAME;
+use crate::constants::{CAIRO_PIE_FILE_NAME, PROOF_FILE_NAME};
 use crate::data_storage::MockDataStorage;
-use crate::jobs::metadata::{CommonMetadata, JobMetadata, JobSpecificMetadata, ProvingInputType, ProvingMetadata};
+use crate::jobs::metadata::{
+    CommonMetadata, JobMetadata, JobSpecificMetadata, ProofRegistrationMetadata, ProvingInputType, ProvingMetadata,
+};
 use crate::jobs::proving_job::ProvingJob;
-use crate::jobs::types::{JobItem, JobStatus, JobType};
+use crate::jobs::register_proof_job::RegisterProofJob;
+use crate::jobs::types::{JobItem, JobStatus, JobType, JobVerificationStatus};
 use crate::jobs::Job;
-use crate::tests::config::TestConfigBuilder;
+use crate::tests::config::{ConfigType, TestConfigBuilder};
 
 #[rstest]
 #[tokio::test]
@@ -68,6 +73,109 @@ async fn test_verify_job(#[from(default_job_item)] mut job_item: JobItem) {
     assert!(ProvingJob.verify_job(services.config, &mut job_item).await.is_ok());
 }
 
+/// The proof of a succeeded task is downloaded, converted to the settlement layer format and
+/// stored where the proof registration job registers it from.
+#[rstest]
+#[tokio::test]
+async fn test_verify_job_stores_proof_for_registration(#[from(default_job_item)] mut job_item: JobItem) {
+    let prover_proof = r#"{"proof": ["0x1"]}"#;
+    let converted_proof = br#"["0x1"]"#.to_vec();
+    let proof_path = format!("0/{}", PROOF_FILE_NAME);
+
+    let mut prover_client = MockProverClient::new();
+    prover_client.expect_get_task_status().times(1).returning(|_, _, _| Ok(TaskStatus::Succeeded));
+    prover_client
+        .expect_get_proof()
+        .withf(|task_id| task_id == "task_id")
+        .times(1)
+        .returning(move |_| Ok(prover_proof.to_string()));
+
+    let mut settlement_client = MockSettlementClient::new();
+    let proof = converted_proof.clone();
+    settlement_client
+        .expect_convert_proof()
+        .withf(move |proof| proof == prover_proof)
+        .times(1)
+        .returning(move |_| Ok(proof.clone()));
+    settlement_client
+        .expect_register_proof()
+        .with(eq(converted_proof))
+        .times(1)
+        .returning(|_| Ok("0xbeef".to_string()));
+
+    let services = TestConfigBuilder::new()
+        .configure_prover_client(prover_client.into())
+        .configure_settlement_client(settlement_client.into())
+        .configure_storage_client(ConfigType::Actual)
+        .build()
+        .await;
+
+    job_item.job_type = JobType::ProofCreation;
+    job_item.external_id = "task_id".to_string().into();
+    job_item.metadata.specific = JobSpecificMetadata::Proving(ProvingMetadata {
+        block_number: 0,
+        input_path: None,
+        ensure_on_chain_registration: None,
+        download_proof: Some(proof_path.clone()),
+    });
+
+    assert_eq!(
+        ProvingJob.verify_job(services.config.clone(), &mut job_item).await.unwrap(),
+        JobVerificationStatus::Verified
+    );
+
+    let mut registration_job = RegisterProofJob
+        .create_job(
+            services.config.clone(),
+            "0".to_string(),
+            JobMetadata {
+                common: CommonMetadata::default(),
+                specific: JobSpecificMetadata::ProofRegistration(ProofRegistrationMetadata {
+                    from_block: 0,
+                    to_block: 0,
+                    proof_task_id: "task_id".to_string(),
+                    fact: None,
+                    proof_path: Some(proof_path),
+                    tx_hash: None,
+                }),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(RegisterProofJob.process_job(services.config, &mut registration_job).await.unwrap(), "0xbeef");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_verify_job_fails_when_proof_cannot_be_converted(#[from(default_job_item)] mut job_item: JobItem) {
+    let mut prover_client = MockProverClient::new();
+    prover_client.expect_get_task_status().times(1).returning(|_, _, _| Ok(TaskStatus::Succeeded));
+    prover_client.expect_get_proof().times(1).returning(|_| Ok("not a proof".to_string()));
+
+    let mut settlement_client = MockSettlementClient::new();
+    settlement_client.expect_convert_proof().times(1).returning(|_| Err(eyre!("Failed to deserialize proof")));
+
+    let mut storage = MockDataStorage::new();
+    storage.expect_put_data().never();
+
+    let services = TestConfigBuilder::new()
+        .configure_prover_client(prover_client.into())
+        .configure_settlement_client(settlement_client.into())
+        .configure_storage_client(storage.into())
+        .build()
+        .await;
+
+    job_item.job_type = JobType::ProofCreation;
+    job_item.metadata.specific = JobSpecificMetadata::Proving(ProvingMetadata {
+        block_number: 0,
+        input_path: None,
+        ensure_on_chain_registration: None,
+        download_proof: Some(format!("0/{}", PROOF_FILE_NAME)),
+    });
+
+    assert!(ProvingJob.verify_job(services.config, &mut job_item).await.is_err());
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_process_job() {
@@ -0,0 +1,127 @@
+use assert_matches::assert_matches;
+use bytes::Bytes;
+use mockall::predicate::eq;
+use rstest::*;
+use settlement_client_interface::{MockSettlementClient, SettlementVerificationStatus};
+
+use super::super::common::default_job_item;
+use crate::constants::PROOF_FILE_NAME;
+use crate::data_storage::MockDataStorage;
+use crate::jobs::metadata::{CommonMetadata, JobMetadata, JobSpecificMetadata, ProofRegistrationMetadata};
+use crate::jobs::register_proof_job::{ProofRegistrationError, RegisterProofJob};
+use crate::jobs::types::{ExternalId, JobItem, JobStatus, JobType, JobVerificationStatus};
+use crate::jobs::{Job, JobError};
+use crate::tests::config::TestConfigBuilder;
+
+const FACT: &str = "0x1e8f40f1a4c8c3c1c1b2b37d6ce9b84a1b0bfc4b1d5ff0dd7e2e23dcbeb83f0a";
+
+fn registration_metadata(proof_path: Option<String>) -> JobMetadata {
+    JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::ProofRegistration(ProofRegistrationMetadata {
+            from_block: 0,
+            to_block: 2,
+            proof_task_id: "task_id".to_string(),
+            fact: Some(FACT.to_string()),
+            proof_path,
+            tx_hash: None,
+        }),
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_create_job() {
+    let services = TestConfigBuilder::new().build().await;
+
+    let job = RegisterProofJob
+        .create_job(
+            services.config.clone(),
+            String::from("0"),
+            registration_metadata(Some(format!("0/{}", PROOF_FILE_NAME))),
+        )
+        .await;
+    assert!(job.is_ok());
+
+    let job = job.unwrap();
+
+    assert_eq!(job.job_type, JobType::ProofRegistration, "job_type should be ProofRegistration");
+    assert!(!(job.id.is_nil()), "id should not be nil");
+    assert_eq!(job.status, JobStatus::Created, "status should be Created");
+    assert_eq!(job.version, 0_i32, "version should be 0");
+    assert_eq!(job.external_id.unwrap_string().unwrap(), String::new(), "external_id should be empty string");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_process_job(#[from(default_job_item)] mut job_item: JobItem) {
+    let proof = br#"{"proof": []}"#.to_vec();
+    let proof_path = format!("0/{}", PROOF_FILE_NAME);
+
+    let mut storage = MockDataStorage::new();
+    let proof_bytes = Bytes::from(proof.clone());
+    storage.expect_get_data().with(eq(proof_path.clone())).return_once(move |_| Ok(proof_bytes));
+
+    let mut settlement_client = MockSettlementClient::new();
+    settlement_client.expect_register_proof().with(eq(proof)).times(1).returning(|_| Ok("0xbeef".to_string()));
+
+    let services = TestConfigBuilder::new()
+        .configure_storage_client(storage.into())
+        .configure_settlement_client(settlement_client.into())
+        .build()
+        .await;
+
+    job_item.job_type = JobType::ProofRegistration;
+    job_item.metadata = registration_metadata(Some(format!("0/{}", PROOF_FILE_NAME)));
+
+    assert_eq!(RegisterProofJob.process_job(services.config, &mut job_item).await.unwrap(), "0xbeef".to_string());
+
+    let metadata: ProofRegistrationMetadata = job_item.metadata.specific.try_into().unwrap();
+    assert_eq!(metadata.tx_hash, Some("0xbeef".to_string()));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_process_job_without_proof_path_fails(#[from(default_job_item)] mut job_item: JobItem) {
+    let mut settlement_client = MockSettlementClient::new();
+    settlement_client.expect_register_proof().never();
+
+    let services = TestConfigBuilder::new().configure_settlement_client(settlement_client.into()).build().await;
+
+    job_item.job_type = JobType::ProofRegistration;
+    job_item.metadata = registration_metadata(None);
+
+    assert_matches!(
+        RegisterProofJob.process_job(services.config, &mut job_item).await.unwrap_err(),
+        JobError::ProofRegistrationJobError(ProofRegistrationError::ProofPathNotFound { .. })
+    );
+}
+
+#[rstest]
+#[case::verified(SettlementVerificationStatus::Verified, JobVerificationStatus::Verified)]
+#[case::rejected(
+    SettlementVerificationStatus::Rejected("reverted".to_string()),
+    JobVerificationStatus::Rejected("reverted".to_string())
+)]
+#[tokio::test]
+async fn test_verify_job(
+    #[from(default_job_item)] mut job_item: JobItem,
+    #[case] tx_status: SettlementVerificationStatus,
+    #[case] expected_status: JobVerificationStatus,
+) {
+    let mut settlement_client = MockSettlementClient::new();
+    settlement_client
+        .expect_verify_tx_inclusion()
+        .with(eq("0xbeef"))
+        .times(1)
+        .returning(move |_| Ok(tx_status.clone()));
+    settlement_client.expect_wait_for_tx_finality().never();
+
+    let services = TestConfigBuilder::new().configure_settlement_client(settlement_client.into()).build().await;
+
+    job_item.job_type = JobType::ProofRegistration;
+    job_item.external_id = ExternalId::String("0xbeef".to_string().into_boxed_str());
+    job_item.metadata = registration_metadata(Some(format!("0/{}", PROOF_FILE_NAME)));
+
+    assert_eq!(RegisterProofJob.verify_job(services.config, &mut job_item).await.unwrap(), expected_status);
+}
@@ -2,6 +2,7 @@ use std::fs;
 use std::fs::read_to_string;
 use std::path::PathBuf;
 
+use alloy::primitives::U256;
 use assert_matches::assert_matches;
 use bytes::Bytes;
 use color_eyre::eyre::eyre;
@@ -18,7 +19,7 @@ use url::Url;
 use crate::constants::{BLOB_DATA_FILE_NAME, PROGRAM_OUTPUT_FILE_NAME, SNOS_OUTPUT_FILE_NAME};
 use crate::data_storage::MockDataStorage;
 use crate::jobs::metadata::{CommonMetadata, JobMetadata, JobSpecificMetadata, StateUpdateMetadata};
-use crate::jobs::state_update_job::utils::hex_string_to_u8_vec;
+use crate::jobs::state_update_job::utils::{hex_string_to_u8_vec, split_calldata_program_output};
 use crate::jobs::state_update_job::{StateUpdateError, StateUpdateJob};
 use crate::jobs::types::{JobStatus, JobType};
 use crate::jobs::{Job, JobError};
@@ -224,7 +225,6 @@ async fn process_job_works_unit_test() {
     // Mock the latest block settled
     settlement_client.expect_get_last_settled_block().returning(|| Ok(651052_u64));
 
-    // TODO: have tests for update_state_calldata, only kzg for now
     let block_numbers = ["651053", "651054", "651055", "651056"];
     for block_no in block_numbers {
         let _state_diff: Vec<u8> = load_state_diff_file(block_no.parse::<u64>().unwrap()).await;
@@ -318,6 +318,107 @@ async fn process_job_works_unit_test() {
     assert_eq!(StateUpdateJob.process_job(services.config, &mut job).await.unwrap(), "651056".to_string())
 }
 
+#[rstest]
+#[tokio::test]
+async fn process_job_with_calldata_da_works() {
+    let mut settlement_client = MockSettlementClient::new();
+    let mut storage_client = MockDataStorage::new();
+
+    settlement_client.expect_get_last_settled_block().returning(|| Ok(651052_u64));
+    settlement_client.expect_get_nonce().returning(|| Ok(1));
+
+    let block_no = "651053";
+    let snos_output_key = block_no.to_owned() + "/" + SNOS_OUTPUT_FILE_NAME;
+    let snos_output_data = fs::read_to_string(
+        CURRENT_PATH.join(format!("src/tests/jobs/state_update_job/test_data/calldata/{}", SNOS_OUTPUT_FILE_NAME)),
+    )
+    .expect("Failed to read the snos output data json file");
+    storage_client
+        .expect_get_data()
+        .with(eq(snos_output_key))
+        .returning(move |_| Ok(Bytes::from(snos_output_data.clone())));
+
+    let blob_data_key = block_no.to_owned() + "/" + BLOB_DATA_FILE_NAME;
+    let blob_data_vec = load_state_diff_file(block_no.parse::<u64>().unwrap()).await;
+    storage_client.expect_get_data().with(eq(blob_data_key)).returning(move |_| Ok(Bytes::from(blob_data_vec.clone())));
+
+    // the program output ends with the state diff of a single contract: its nonce and one storage
+    // write, the hash being the keccak of these 6 felts
+    let program_output_key = block_no.to_owned() + "/" + PROGRAM_OUTPUT_FILE_NAME;
+    let program_output = read_file_to_vec_u8_32(
+        CURRENT_PATH
+            .join(format!("src/tests/jobs/state_update_job/test_data/calldata/{}", PROGRAM_OUTPUT_FILE_NAME))
+            .to_str()
+            .unwrap(),
+    )
+    .unwrap();
+    let expected_main_output = program_output[..12].to_vec();
+    let expected_onchain_data_hash: [u8; 32] =
+        hex_string_to_u8_vec("46434ed7e5294d199a5e9567dce0091895f144f6a61ce889d72062c781e56ee7")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let expected_onchain_data_size: [u8; 32] = U256::from(6).to_be_bytes();
+    storage_client
+        .expect_get_data()
+        .with(eq(program_output_key))
+        .returning(move |_| Ok(Bytes::from(bincode::serialize(&program_output).unwrap())));
+
+    settlement_client.expect_update_state_with_blobs().never();
+    settlement_client
+        .expect_update_state_calldata()
+        .with(eq(expected_main_output), eq(expected_onchain_data_hash), eq(expected_onchain_data_size))
+        .times(1)
+        .returning(|_, _, _| Ok(String::from("0x5d17fac98d9454030426606019364f6e68d915b91f6210ef1e2628cd6987442")));
+
+    let services = TestConfigBuilder::new()
+        .configure_settlement_client(settlement_client.into())
+        .configure_storage_client(storage_client.into())
+        .build()
+        .await;
+
+    let metadata = JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::StateUpdate(StateUpdateMetadata {
+            blocks_to_settle: vec![651053],
+            snos_output_paths: vec![format!("{}/{}", block_no, SNOS_OUTPUT_FILE_NAME)],
+            program_output_paths: vec![format!("{}/{}", block_no, PROGRAM_OUTPUT_FILE_NAME)],
+            blob_data_paths: vec![format!("{}/{}", block_no, BLOB_DATA_FILE_NAME)],
+            last_failed_block_no: None,
+            tx_hashes: vec![],
+        }),
+    };
+
+    let mut job =
+        StateUpdateJob.create_job(services.config.clone(), String::from("internal_id"), metadata).await.unwrap();
+    assert_eq!(StateUpdateJob.process_job(services.config, &mut job).await.unwrap(), "651053".to_string())
+}
+
+#[rstest]
+#[case::no_messages(vec![0, 0], 6)]
+#[case::messages_to_l1_and_l2(vec![3, 1, 2, 1, 2, 1, 1], 6)]
+#[case::no_state_diff(vec![1, 7, 0], 0)]
+fn split_calldata_program_output_skips_messages(#[case] messages: Vec<u64>, #[case] state_diff_len: u64) {
+    let felt = |value: u64| U256::from(value).to_be_bytes::<32>();
+    let header: Vec<[u8; 32]> = (0..10).map(felt).collect();
+    let state_diff: Vec<[u8; 32]> = (100..100 + state_diff_len).map(felt).collect();
+    let program_output = [header, messages.iter().copied().map(felt).collect(), state_diff.clone()].concat();
+
+    let (main_output, onchain_data) = split_calldata_program_output(&program_output).unwrap();
+
+    assert_eq!(main_output.len(), 10 + messages.len());
+    assert_eq!(onchain_data, state_diff);
+}
+
+#[rstest]
+fn split_calldata_program_output_fails_on_truncated_messages() {
+    let mut program_output = vec![[0u8; 32]; 11];
+    // 5 felts of messages to L1 announced, none present
+    program_output[10] = U256::from(5).to_be_bytes();
+
+    assert!(split_calldata_program_output(&program_output).is_err());
+}
+
 #[rstest]
 #[case(vec![651052, 651054, 651051, 651056], "numbers aren't sorted in increasing order")]
 #[case(vec![651052, 651052, 651052, 651052], "Duplicated block numbers")]
@@ -1,7 +1,138 @@
+use std::time::Duration;
+
+use omniqueue::QueueError;
 use rstest::*;
 
+use crate::queue::in_memory::InMemoryQueue;
+use crate::queue::{DeadLetterSource, QueueConfig, QueueDepth, QueueProvider, QueueType, QUEUES};
+
 #[rstest]
 #[tokio::test]
 async fn test_queue() {
     // TODO: write test case
 }
+
+/// Messages sent with a delay are only delivered once the delay has passed.
+#[rstest]
+#[tokio::test]
+async fn test_in_memory_queue_delayed_delivery() {
+    let queue = InMemoryQueue::new();
+    queue
+        .send_message_to_queue(QueueType::WorkerTrigger, "Snos".to_string(), Some(Duration::from_millis(200)))
+        .await
+        .unwrap();
+
+    assert!(matches!(queue.consume_message_from_queue(QueueType::WorkerTrigger).await, Err(QueueError::NoData)));
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let delivery = queue.consume_message_from_queue(QueueType::WorkerTrigger).await.unwrap();
+    assert_eq!(delivery.borrow_payload(), Some("Snos".as_bytes()));
+
+    // acknowledged messages are not delivered again
+    queue.ack_message(QueueType::WorkerTrigger, delivery).await.unwrap();
+    assert!(matches!(queue.consume_message_from_queue(QueueType::WorkerTrigger).await, Err(QueueError::NoData)));
+}
+
+/// A message nack-ed `max_receive_count` times is moved to the dead letter queue.
+#[rstest]
+#[tokio::test]
+async fn test_in_memory_queue_moves_message_to_dlq() {
+    let queue = InMemoryQueue::new();
+    let dlq_config = QUEUES
+        .iter()
+        .find(|config| config.name == QueueType::SnosJobProcessing)
+        .and_then(|config| config.dlq_config.clone())
+        .unwrap();
+
+    queue.send_message_to_queue(QueueType::SnosJobProcessing, "message".to_string(), None).await.unwrap();
+
+    for _ in 0..dlq_config.max_receive_count {
+        let delivery = queue.consume_message_from_queue(QueueType::SnosJobProcessing).await.unwrap();
+        assert_eq!(delivery.borrow_payload(), Some("message".as_bytes()));
+        delivery.nack().await.map_err(|(e, _)| e).unwrap();
+    }
+
+    assert!(matches!(queue.consume_message_from_queue(QueueType::SnosJobProcessing).await, Err(QueueError::NoData)));
+    let dead_letters =
+        queue.consume_dead_letter_messages(dlq_config.dlq_name.clone(), 10, Duration::ZERO).await.unwrap();
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].payload, "message");
+    assert_eq!(
+        dead_letters[0].source,
+        DeadLetterSource {
+            queue: Some(QueueType::SnosJobProcessing),
+            receive_count: Some(dlq_config.max_receive_count)
+        }
+    );
+
+    // a handled dead letter is gone, an unhandled one comes back after its visibility timeout
+    queue.settle_dead_letter_message(dlq_config.dlq_name.clone(), &dead_letters[0].receipt, false).await.unwrap();
+    let depth = queue.get_queue_depth(dlq_config.dlq_name.clone()).await.unwrap();
+    assert_eq!(depth, QueueDepth { visible: 0, in_flight: 0, delayed: 1 });
+}
+
+/// The depth of a queue counts its messages as visible, delayed or in flight.
+#[rstest]
+#[tokio::test]
+async fn test_in_memory_queue_depth() {
+    let queue = InMemoryQueue::new();
+    queue.send_message_to_queue(QueueType::WorkerTrigger, "Snos".to_string(), None).await.unwrap();
+    queue.send_message_to_queue(QueueType::WorkerTrigger, "Proving".to_string(), None).await.unwrap();
+    queue
+        .send_message_to_queue(QueueType::WorkerTrigger, "DataSubmission".to_string(), Some(Duration::from_secs(60)))
+        .await
+        .unwrap();
+
+    let delivery = queue.consume_message_from_queue(QueueType::WorkerTrigger).await.unwrap();
+
+    let depth = queue.get_queue_depth(QueueType::WorkerTrigger).await.unwrap();
+    assert_eq!(depth, QueueDepth { visible: 1, in_flight: 1, delayed: 1 });
+
+    // acked messages aren't in flight anymore
+    queue.ack_message(QueueType::WorkerTrigger, delivery).await.unwrap();
+    let depth = queue.get_queue_depth(QueueType::WorkerTrigger).await.unwrap();
+    assert_eq!(depth, QueueDepth { visible: 1, in_flight: 0, delayed: 1 });
+}
+
+/// A message neither acked nor nack-ed is received again once its visibility timeout expires,
+/// unless the timeout is extended.
+#[rstest]
+#[tokio::test]
+async fn test_in_memory_queue_redelivers_unsettled_message() {
+    let queue = InMemoryQueue::new();
+    queue
+        .create_queue(&QueueConfig { name: QueueType::WorkerTrigger, visibility_timeout: 1, dlq_config: None })
+        .await
+        .unwrap();
+    queue.send_message_to_queue(QueueType::WorkerTrigger, "Snos".to_string(), None).await.unwrap();
+
+    let mut delivery = queue.consume_message_from_queue(QueueType::WorkerTrigger).await.unwrap();
+    queue.extend_message_visibility(&mut delivery, Duration::from_secs(2)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1200)).await;
+    assert!(matches!(queue.consume_message_from_queue(QueueType::WorkerTrigger).await, Err(QueueError::NoData)));
+
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    let redelivery = queue.consume_message_from_queue(QueueType::WorkerTrigger).await.unwrap();
+    assert_eq!(redelivery.borrow_payload(), Some("Snos".as_bytes()));
+}
+
+/// A batched receive returns up to the requested number of visible messages.
+#[rstest]
+#[tokio::test]
+async fn test_in_memory_queue_batched_receive() {
+    let queue = InMemoryQueue::new();
+    for worker in ["Snos", "Proving", "DataSubmission"] {
+        queue.send_message_to_queue(QueueType::WorkerTrigger, worker.to_string(), None).await.unwrap();
+    }
+
+    let deliveries = queue.consume_messages_from_queue(QueueType::WorkerTrigger, 2, Duration::ZERO).await.unwrap();
+    assert_eq!(deliveries.len(), 2);
+    assert_eq!(deliveries[0].borrow_payload(), Some("Snos".as_bytes()));
+
+    let deliveries = queue.consume_messages_from_queue(QueueType::WorkerTrigger, 2, Duration::ZERO).await.unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].borrow_payload(), Some("DataSubmission".as_bytes()));
+
+    let deliveries = queue.consume_messages_from_queue(QueueType::WorkerTrigger, 2, Duration::ZERO).await.unwrap();
+    assert!(deliveries.is_empty());
+}
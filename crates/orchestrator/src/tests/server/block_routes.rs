@@ -0,0 +1,118 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use hyper::{Body, Request};
+use rstest::*;
+
+use crate::config::Config;
+use crate::jobs::metadata::JobSpecificMetadata;
+use crate::jobs::types::{JobItem, JobStatus, JobType};
+use crate::routes::types::{BlockRangeStatusResponse, BlockStatusResponse};
+use crate::tests::config::{ConfigType, TestConfigBuilder};
+use crate::tests::utils::build_job_item;
+
+#[fixture]
+async fn setup_server() -> (SocketAddr, Arc<Config>) {
+    dotenvy::from_filename("../.env.test").expect("Failed to load the .env.test file");
+
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_api_server(ConfigType::Actual)
+        .build()
+        .await;
+
+    (services.api_server_address.unwrap(), services.config)
+}
+
+/// Creates the jobs of blocks 4 to 6: SNOS and proving jobs for each block, a proof registration
+/// job covering 4 to 6 and a state transition job settling 4 and 5.
+async fn create_pipeline_jobs(config: &Config) -> (JobItem, JobItem) {
+    for block_number in 4..=6 {
+        config
+            .database()
+            .create_job(build_job_item(JobType::SnosRun, JobStatus::Completed, block_number))
+            .await
+            .unwrap();
+        config
+            .database()
+            .create_job(build_job_item(JobType::ProofCreation, JobStatus::PendingVerification, block_number))
+            .await
+            .unwrap();
+    }
+
+    let mut proof_registration_job = build_job_item(JobType::ProofRegistration, JobStatus::Created, 4);
+    if let JobSpecificMetadata::ProofRegistration(metadata) = &mut proof_registration_job.metadata.specific {
+        metadata.to_block = 6;
+    }
+    config.database().create_job(proof_registration_job.clone()).await.unwrap();
+
+    let mut state_transition_job = build_job_item(JobType::StateTransition, JobStatus::Failed, 4);
+    state_transition_job.metadata.common.failure_reason = Some("settlement failed".to_string());
+    if let JobSpecificMetadata::StateUpdate(metadata) = &mut state_transition_job.metadata.specific {
+        metadata.blocks_to_settle = vec![4, 5];
+    }
+    config.database().create_job(state_transition_job.clone()).await.unwrap();
+
+    (proof_registration_job, state_transition_job)
+}
+
+#[tokio::test]
+#[rstest]
+async fn test_block_status(#[future] setup_server: (SocketAddr, Arc<Config>)) {
+    let (addr, config) = setup_server.await;
+    let (proof_registration_job, state_transition_job) = create_pipeline_jobs(&config).await;
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(Request::builder().uri(format!("http://{}/blocks/5/status", addr)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let block: BlockStatusResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(block.block_number, 5);
+    assert_eq!(block.snos.unwrap().internal_id, "5");
+    assert_eq!(block.proving.unwrap().status, JobStatus::PendingVerification);
+    assert!(block.data_submission.is_none());
+    assert_eq!(block.proof_registration.unwrap().job_id, proof_registration_job.id);
+    let state_transition = block.state_transition.unwrap();
+    assert_eq!(state_transition.job_id, state_transition_job.id);
+    assert_eq!(state_transition.failure_reason, Some("settlement failed".to_string()));
+}
+
+#[tokio::test]
+#[rstest]
+async fn test_block_range_status(#[future] setup_server: (SocketAddr, Arc<Config>)) {
+    let (addr, config) = setup_server.await;
+    create_pipeline_jobs(&config).await;
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(
+            Request::builder().uri(format!("http://{}/blocks/status?from=3&to=6", addr)).body(Body::empty()).unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let range: BlockRangeStatusResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    let block_numbers: Vec<u64> = range.blocks.iter().map(|block| block.block_number).collect();
+    assert_eq!(block_numbers, vec![3, 4, 5, 6]);
+    assert!(range.blocks[0].snos.is_none());
+    assert_eq!(range.blocks[1].state_transition, Some(JobStatus::Failed));
+    assert_eq!(range.blocks[3].proof_registration, Some(JobStatus::Created));
+    assert!(range.blocks[3].state_transition.is_none());
+
+    // reversed range
+    let response = client
+        .request(
+            Request::builder().uri(format!("http://{}/blocks/status?from=6&to=3", addr)).body(Body::empty()).unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
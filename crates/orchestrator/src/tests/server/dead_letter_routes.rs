@@ -0,0 +1,115 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use hyper::{Body, Method, Request, Response};
+use rstest::*;
+use serde_json::json;
+
+use crate::jobs::types::{JobItem, JobStatus, JobType};
+use crate::queue::dead_letter::{dead_letter_of, DeadLetterAction, DeadLetterActionReport};
+use crate::queue::job_queue::{JobQueueMessage, QueueNameForJobType};
+use crate::queue::DeadLetterSource;
+use crate::routes::types::{DeadLetterAuditResponse, DeadLetterListResponse};
+use crate::tests::config::{ConfigType, TestConfigBuilder};
+use crate::tests::utils::build_job_item;
+
+/// A failed job whose message was dead lettered while the job was in `status`.
+fn build_dead_lettered_job(job_type: JobType, status: JobStatus, internal_id: u64) -> JobItem {
+    let mut job = build_job_item(job_type, status, internal_id);
+    job.metadata.common.dead_letter = Some(dead_letter_of(&job, DeadLetterSource::default()));
+    job.metadata.common.failure_reason = Some("Received failure queue message".to_string());
+    job.status = JobStatus::Failed;
+    job
+}
+
+async fn request(addr: SocketAddr, method: Method, path: &str, body: Option<serde_json::Value>) -> Response<Body> {
+    let request = Request::builder().method(method).uri(format!("http://{}{}", addr, path));
+    let request = match body {
+        Some(body) => request.header("content-type", "application/json").body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    };
+    hyper::Client::new().request(request.unwrap()).await.unwrap()
+}
+
+async fn response_json<T: serde::de::DeserializeOwned>(response: Response<Body>) -> T {
+    assert_eq!(response.status(), 200);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+#[tokio::test]
+#[rstest]
+async fn test_dead_letter_replay_and_discard() {
+    dotenvy::from_filename("../.env.test").expect("Failed to load the .env.test file");
+
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_api_server(ConfigType::Actual)
+        .build()
+        .await;
+    let addr = services.api_server_address.unwrap();
+    let config = services.config;
+
+    let processing_job = build_dead_lettered_job(JobType::DataSubmission, JobStatus::LockedForProcessing, 1);
+    let verification_job = build_dead_lettered_job(JobType::SnosRun, JobStatus::PendingVerification, 2);
+    // failed without going through the dead letter queue
+    let failed_job = build_job_item(JobType::ProofCreation, JobStatus::Failed, 3);
+    for job in [&processing_job, &verification_job, &failed_job] {
+        config.database().create_job(job.clone()).await.unwrap();
+    }
+
+    let list: DeadLetterListResponse = response_json(request(addr, Method::GET, "/dead_letters", None).await).await;
+    assert_eq!(list.dead_letters.len(), 2);
+    let source_queue = |job: &JobItem| {
+        list.dead_letters.iter().find(|dead_letter| dead_letter.job_id == job.id).map(|dead_letter| {
+            assert_eq!(dead_letter.receive_count, 5);
+            dead_letter.source_queue.clone()
+        })
+    };
+    assert_eq!(source_queue(&processing_job), Some(JobType::DataSubmission.process_queue_name()));
+    assert_eq!(source_queue(&verification_job), Some(JobType::SnosRun.verify_queue_name()));
+
+    // the selection must be given by exactly one of job_ids and all
+    let body = json!({ "job_ids": [processing_job.id], "all": true, "actor": "operator" });
+    assert_eq!(request(addr, Method::POST, "/dead_letters/replay", Some(body)).await.status(), 400);
+
+    let body = json!({ "job_ids": [processing_job.id, failed_job.id], "actor": "operator" });
+    let report: DeadLetterActionReport =
+        response_json(request(addr, Method::POST, "/dead_letters/replay", Some(body)).await).await;
+    assert_eq!(report.succeeded, vec![processing_job.id]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].job_id, failed_job.id);
+
+    let replayed_job = config.database().get_job_by_id(processing_job.id).await.unwrap().unwrap();
+    assert_eq!(replayed_job.status, JobStatus::PendingRetry);
+    assert_eq!(replayed_job.metadata.common.dead_letter, None);
+    let message =
+        config.queue().consume_message_from_queue(JobType::DataSubmission.process_queue_name()).await.unwrap();
+    let message: JobQueueMessage = message.payload_serde_json().unwrap().unwrap();
+    assert_eq!(message.id, processing_job.id);
+
+    // the audit log is ordered by time
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let body = json!({ "all": true, "actor": "operator" });
+    let report: DeadLetterActionReport =
+        response_json(request(addr, Method::POST, "/dead_letters/discard", Some(body)).await).await;
+    assert_eq!(report.succeeded, vec![verification_job.id]);
+    assert!(report.failed.is_empty());
+
+    let discarded_job = config.database().get_job_by_id(verification_job.id).await.unwrap().unwrap();
+    assert_eq!(discarded_job.status, JobStatus::Failed);
+    assert_eq!(discarded_job.metadata.common.dead_letter, None);
+
+    let list: DeadLetterListResponse = response_json(request(addr, Method::GET, "/dead_letters", None).await).await;
+    assert!(list.dead_letters.is_empty());
+
+    let audit: DeadLetterAuditResponse =
+        response_json(request(addr, Method::GET, "/dead_letters/audit?limit=10", None).await).await;
+    let actions: Vec<_> = audit.entries.iter().map(|entry| (entry.job_id, entry.action)).collect();
+    assert_eq!(
+        actions,
+        vec![(verification_job.id, DeadLetterAction::Discarded), (processing_job.id, DeadLetterAction::Replayed)]
+    );
+    assert!(audit.entries.iter().all(|entry| entry.actor == "operator"));
+}
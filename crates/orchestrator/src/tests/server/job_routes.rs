@@ -13,11 +13,12 @@ use utils::env_utils::get_env_var_or_panic;
 
 use crate::config::Config;
 use crate::jobs::job_handler_factory::mock_factory;
+use crate::jobs::retry_policy::RetryPolicy;
 use crate::jobs::types::{JobStatus, JobType};
 use crate::jobs::{Job, MockJob};
 use crate::queue::init_consumers;
 use crate::queue::job_queue::{JobQueueMessage, QueueNameForJobType};
-use crate::routes::types::ApiResponse;
+use crate::routes::types::{ApiResponse, JobListResponse, JobResponse};
 use crate::tests::config::{ConfigType, TestConfigBuilder};
 use crate::tests::utils::build_job_item;
 
@@ -100,7 +101,7 @@ async fn test_trigger_verify_job(#[future] setup_trigger: (SocketAddr, Arc<Confi
 
     // Set up mock job handler
     let mut job_handler = MockJob::new();
-    job_handler.expect_verification_polling_delay_seconds().return_const(1u64);
+    job_handler.expect_verification_polling_policy().return_const(RetryPolicy::fixed(Duration::from_secs(1)));
     let job_handler: Arc<Box<dyn Job>> = Arc::new(Box::new(job_handler));
 
     let ctx = mock_factory::get_job_handler_context();
@@ -206,6 +207,81 @@ async fn test_trigger_retry_job_not_allowed(
     assert!(queue_result.is_err(), "Queue should be empty - no message should be added for non-Failed jobs");
 }
 
+#[tokio::test]
+#[rstest]
+async fn test_get_job(#[future] setup_trigger: (SocketAddr, Arc<Config>)) {
+    let (addr, config) = setup_trigger.await;
+
+    let job_item = build_job_item(JobType::SnosRun, JobStatus::Completed, 1);
+    config.database().create_job(job_item.clone()).await.unwrap();
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(Request::builder().uri(format!("http://{}/jobs/{}", addr, job_item.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let response: JobResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(response, JobResponse::from(job_item));
+
+    // unknown job
+    let response = client
+        .request(
+            Request::builder()
+                .uri(format!("http://{}/jobs/{}", addr, uuid::Uuid::new_v4()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+#[rstest]
+async fn test_list_jobs(#[future] setup_trigger: (SocketAddr, Arc<Config>)) {
+    let (addr, config) = setup_trigger.await;
+
+    for internal_id in 1..=3 {
+        let job_item = build_job_item(JobType::SnosRun, JobStatus::Completed, internal_id);
+        config.database().create_job(job_item).await.unwrap();
+    }
+    let failed_job_item = build_job_item(JobType::SnosRun, JobStatus::Failed, 4);
+    config.database().create_job(failed_job_item).await.unwrap();
+
+    let client = hyper::Client::new();
+    let mut internal_ids = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut uri = format!("http://{}/jobs?job_type=SnosRun&status=Completed&limit=2", addr);
+        if let Some(cursor) = &cursor {
+            uri.push_str(&format!("&cursor={}", cursor));
+        }
+        let response = client.request(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let page: JobListResponse = serde_json::from_slice(&body_bytes).unwrap();
+        internal_ids.extend(page.jobs.into_iter().map(|job| job.internal_id));
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    internal_ids.sort();
+    assert_eq!(internal_ids, vec!["1", "2", "3"]);
+
+    // invalid cursor
+    let response = client
+        .request(Request::builder().uri(format!("http://{}/jobs?cursor=invalid", addr)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_init_consumer() {
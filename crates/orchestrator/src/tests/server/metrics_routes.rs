@@ -0,0 +1,34 @@
+use hyper::{Body, Request};
+use opentelemetry::metrics::MeterProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use rstest::*;
+
+use crate::telemetry::prometheus::init_prometheus_exporter;
+use crate::tests::config::{ConfigType, TestConfigBuilder};
+
+#[tokio::test]
+#[rstest]
+async fn test_prometheus_metrics() {
+    dotenvy::from_filename("../.env.test").expect("Failed to load the .env.test file");
+
+    // the route is only served once the exporter is set up
+    let exporter = init_prometheus_exporter().unwrap();
+    let provider = SdkMeterProvider::builder().with_reader(exporter).build();
+    provider.meter("orchestrator").f64_counter("test_scraped_jobs").init().add(1.0, &[KeyValue::new("job", "snos")]);
+
+    let services = TestConfigBuilder::new().configure_api_server(ConfigType::Actual).build().await;
+    let addr = services.api_server_address.unwrap();
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(Request::builder().uri(format!("http://{}/metrics", addr)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let metrics = String::from_utf8(body_bytes.to_vec()).unwrap();
+    assert!(metrics.contains("# TYPE test_scraped_jobs_total counter"));
+    assert!(metrics.contains("test_scraped_jobs_total{job=\"snos\"} 1"));
+}
@@ -1,4 +1,8 @@
+pub mod block_routes;
+pub mod dead_letter_routes;
 pub mod job_routes;
+pub mod metrics_routes;
+pub mod worker_routes;
 use std::io::Read;
 
 use axum::http::StatusCode;
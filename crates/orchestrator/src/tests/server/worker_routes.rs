@@ -0,0 +1,43 @@
+use hyper::{Body, Request};
+use rstest::*;
+
+use crate::jobs::types::{JobStatus, JobType};
+use crate::routes::types::WorkerStatusResponse;
+use crate::tests::config::{ConfigType, TestConfigBuilder};
+use crate::tests::utils::build_job_item;
+use crate::workers::circuit_breaker::{CircuitState, HaltScope};
+
+#[tokio::test]
+#[rstest]
+async fn test_worker_status() {
+    dotenvy::from_filename("../.env.test").expect("Failed to load the .env.test file");
+
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_api_server(ConfigType::Actual)
+        .build()
+        .await;
+    let addr = services.api_server_address.unwrap();
+
+    let client = hyper::Client::new();
+    let request_worker_status = || async {
+        let response = client
+            .request(Request::builder().uri(format!("http://{}/workers/status", addr)).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice::<WorkerStatusResponse>(&body_bytes).unwrap()
+    };
+
+    let status = request_worker_status().await;
+    assert_eq!(status.halt_scope, HaltScope::Global);
+    assert_eq!(status.workers.len(), 5);
+    assert!(status.workers.iter().all(|worker| worker.circuit == CircuitState::Closed));
+
+    // with the default global halt scope, a failed job halts every worker
+    services.config.database().create_job(build_job_item(JobType::ProofCreation, JobStatus::Failed, 1)).await.unwrap();
+
+    let status = request_worker_status().await;
+    assert!(status.workers.iter().all(|worker| matches!(worker.circuit, CircuitState::Open { .. })));
+}
@@ -3,8 +3,8 @@ use uuid::Uuid;
 
 use crate::constants::{BLOB_DATA_FILE_NAME, CAIRO_PIE_FILE_NAME, PROGRAM_OUTPUT_FILE_NAME, SNOS_OUTPUT_FILE_NAME};
 use crate::jobs::metadata::{
-    CommonMetadata, DaMetadata, JobMetadata, JobSpecificMetadata, ProvingInputType, ProvingMetadata, SnosMetadata,
-    StateUpdateMetadata,
+    CommonMetadata, DaMetadata, JobMetadata, JobSpecificMetadata, ProofRegistrationMetadata, ProvingInputType,
+    ProvingMetadata, SnosMetadata, StateUpdateMetadata,
 };
 use crate::jobs::types::{ExternalId, JobItem, JobStatus, JobType};
 
@@ -52,7 +52,17 @@ pub fn build_job_item(job_type: JobType, job_status: JobStatus, internal_id: u64
                 tx_hash: None,
             }),
         },
-        _ => panic!("Invalid job type"),
+        JobType::ProofRegistration => JobMetadata {
+            common: CommonMetadata::default(),
+            specific: JobSpecificMetadata::ProofRegistration(ProofRegistrationMetadata {
+                from_block: internal_id,
+                to_block: internal_id,
+                proof_task_id: internal_id.to_string(),
+                fact: None,
+                proof_path: None,
+                tx_hash: None,
+            }),
+        },
     };
 
     JobItem {
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use rstest::*;
+use uuid::Uuid;
+
+use crate::tests::config::{ConfigType, TestConfigBuilder};
+use crate::workers::leader_election::LeaderElection;
+
+/// The leadership is kept across the runs of the leader, the other candidates only take it once
+/// it's released.
+#[rstest]
+#[tokio::test]
+async fn leader_election_keeps_leadership_across_runs() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+
+    let lease_ttl = Duration::from_secs(60);
+    let election = LeaderElection::new("test_worker", Uuid::new_v4(), lease_ttl);
+    let other_election = LeaderElection::new("test_worker", Uuid::new_v4(), lease_ttl);
+
+    let fencing_token = election.lead(config.clone()).await.unwrap().unwrap().fencing_token();
+    // the leadership dropped at the end of the run is still held by the leader
+    assert_eq!(election.lead(config.clone()).await.unwrap().unwrap().fencing_token(), fencing_token);
+    assert!(other_election.try_acquire(config.clone()).await.unwrap().is_none());
+
+    config.leaderships().release_all().await;
+    let other_leadership = other_election.try_acquire(config.clone()).await.unwrap().unwrap();
+    assert!(other_leadership.fencing_token() > fencing_token);
+}
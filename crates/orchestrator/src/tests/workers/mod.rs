@@ -1,3 +1,6 @@
+mod leader_election;
+#[cfg(test)]
+pub mod proof_registration;
 #[cfg(test)]
 pub mod proving;
 #[cfg(test)]
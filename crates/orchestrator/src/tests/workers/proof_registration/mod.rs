@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use mockall::predicate::eq;
+use rstest::*;
+
+use crate::jobs::job_handler_factory::mock_factory;
+use crate::jobs::metadata::{JobSpecificMetadata, ProofRegistrationMetadata};
+use crate::jobs::register_proof_job::RegisterProofJob;
+use crate::jobs::types::{ExternalId, JobItemUpdates, JobStatus, JobType};
+use crate::tests::config::{ConfigType, TestConfigBuilder, TestConfigBuilderReturns};
+use crate::tests::utils::build_job_item;
+use crate::workers::proof_registration::ProofRegistrationWorker;
+use crate::workers::Worker;
+
+/// Stores a completed SNOS job and a completed proving job (proven by `task_id`) for `block_number`
+async fn store_proven_block(services: &TestConfigBuilderReturns, block_number: u64, task_id: &str) {
+    store_proving_block(services, block_number, task_id, JobStatus::Completed).await;
+}
+
+/// Stores a completed SNOS job and a proving job in `status` (proven by `task_id`) for
+/// `block_number`
+async fn store_proving_block(services: &TestConfigBuilderReturns, block_number: u64, task_id: &str, status: JobStatus) {
+    let mut snos_job = build_job_item(JobType::SnosRun, JobStatus::Completed, block_number);
+    if let JobSpecificMetadata::Snos(ref mut snos_metadata) = snos_job.metadata.specific {
+        snos_metadata.snos_fact = Some(format!("0x{:064x}", block_number));
+    }
+    services.config.database().create_job(snos_job).await.unwrap();
+
+    let mut proving_job = build_job_item(JobType::ProofCreation, status, block_number);
+    proving_job.external_id = ExternalId::String(task_id.to_string().into_boxed_str());
+    services.config.database().create_job(proving_job).await.unwrap();
+}
+
+#[rstest]
+#[tokio::test]
+async fn proof_registration_worker_groups_blocks_by_proof() {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_register_proofs(true)
+        .build()
+        .await;
+
+    // Blocks 1 and 2 are proven together, block 3 has its own proof
+    store_proven_block(&services, 1, "task_a").await;
+    store_proven_block(&services, 2, "task_a").await;
+    store_proven_block(&services, 3, "task_b").await;
+
+    let ctx = mock_factory::get_job_handler_context();
+    ctx.expect().times(2).with(eq(JobType::ProofRegistration)).returning(move |_| Arc::new(Box::new(RegisterProofJob)));
+
+    let proof_registration_worker = ProofRegistrationWorker {};
+    assert!(proof_registration_worker.run_worker(services.config.clone(), None, None).await.is_ok());
+    // Blocks of an already registered proof must not lead to another registration job
+    assert!(proof_registration_worker.run_worker(services.config.clone(), None, None).await.is_ok());
+
+    let database = services.config.database();
+    for (from_block, to_block, task_id) in [(1, 2, "task_a"), (3, 3, "task_b")] {
+        let job = database
+            .get_job_by_internal_id_and_type(&from_block.to_string(), &JobType::ProofRegistration)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(job.status, JobStatus::Created);
+
+        let metadata: ProofRegistrationMetadata = job.metadata.specific.try_into().unwrap();
+        assert_eq!(metadata.from_block, from_block);
+        assert_eq!(metadata.to_block, to_block);
+        assert_eq!(metadata.proof_task_id, task_id);
+        assert_eq!(metadata.fact, Some(format!("0x{:064x}", to_block)));
+    }
+    assert!(database.get_job_by_internal_id_and_type("2", &JobType::ProofRegistration).await.unwrap().is_none());
+}
+
+#[rstest]
+#[tokio::test]
+async fn proof_registration_worker_waits_for_whole_proof() {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_register_proofs(true)
+        .build()
+        .await;
+
+    // Block 2 shares the proof of block 1 but isn't proven yet
+    store_proven_block(&services, 1, "task_a").await;
+    store_proving_block(&services, 2, "task_a", JobStatus::PendingVerification).await;
+
+    let ctx = mock_factory::get_job_handler_context();
+    ctx.expect().times(1).with(eq(JobType::ProofRegistration)).returning(move |_| Arc::new(Box::new(RegisterProofJob)));
+
+    let proof_registration_worker = ProofRegistrationWorker {};
+    assert!(proof_registration_worker.run_worker(services.config.clone(), None, None).await.is_ok());
+    let database = services.config.database();
+    assert!(database.get_job_by_internal_id_and_type("1", &JobType::ProofRegistration).await.unwrap().is_none());
+
+    let proving_job = database.get_job_by_internal_id_and_type("2", &JobType::ProofCreation).await.unwrap().unwrap();
+    database.update_job(&proving_job, JobItemUpdates::new().update_status(JobStatus::Completed).build()).await.unwrap();
+    assert!(proof_registration_worker.run_worker(services.config.clone(), None, None).await.is_ok());
+
+    let job = database.get_job_by_internal_id_and_type("1", &JobType::ProofRegistration).await.unwrap().unwrap();
+    let metadata: ProofRegistrationMetadata = job.metadata.specific.try_into().unwrap();
+    assert_eq!((metadata.from_block, metadata.to_block), (1, 2));
+    assert_eq!(metadata.fact, Some(format!("0x{:064x}", 2)));
+}
+
+#[rstest]
+#[tokio::test]
+async fn proof_registration_worker_registers_grown_proof_again() {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_register_proofs(true)
+        .build()
+        .await;
+
+    // The proof of block 1 was registered before block 2 joined it
+    store_proven_block(&services, 1, "task_a").await;
+    store_proven_block(&services, 2, "task_a").await;
+    let mut registration_job = build_job_item(JobType::ProofRegistration, JobStatus::Completed, 1);
+    if let JobSpecificMetadata::ProofRegistration(ref mut metadata) = registration_job.metadata.specific {
+        metadata.proof_task_id = "task_a".to_string();
+    }
+    services.config.database().create_job(registration_job).await.unwrap();
+
+    let ctx = mock_factory::get_job_handler_context();
+    ctx.expect().times(1).with(eq(JobType::ProofRegistration)).returning(move |_| Arc::new(Box::new(RegisterProofJob)));
+
+    let proof_registration_worker = ProofRegistrationWorker {};
+    assert!(proof_registration_worker.run_worker(services.config.clone(), None, None).await.is_ok());
+    // the whole proof is covered now
+    assert!(proof_registration_worker.run_worker(services.config.clone(), None, None).await.is_ok());
+
+    let job =
+        services.config.database().get_job_by_internal_id_and_type("2", &JobType::ProofRegistration).await.unwrap();
+    let metadata: ProofRegistrationMetadata = job.unwrap().metadata.specific.try_into().unwrap();
+    assert_eq!((metadata.from_block, metadata.to_block), (1, 2));
+    assert_eq!(metadata.proof_task_id, "task_a");
+}
+
+#[rstest]
+#[tokio::test]
+async fn proof_registration_worker_disabled() {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_register_proofs(false)
+        .build()
+        .await;
+
+    store_proven_block(&services, 1, "task_a").await;
+
+    let ctx = mock_factory::get_job_handler_context();
+    ctx.expect().times(0).with(eq(JobType::ProofRegistration)).returning(move |_| Arc::new(Box::new(RegisterProofJob)));
+
+    let proof_registration_worker = ProofRegistrationWorker {};
+    assert!(proof_registration_worker.run_worker(services.config.clone(), None, None).await.is_ok());
+
+    let job =
+        services.config.database().get_job_by_internal_id_and_type("1", &JobType::ProofRegistration).await.unwrap();
+    assert!(job.is_none());
+}
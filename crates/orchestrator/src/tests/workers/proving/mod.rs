@@ -115,7 +115,7 @@ async fn test_proving_worker(#[case] incomplete_runs: bool) -> Result<(), Box<dy
     }
 
     let proving_worker = ProvingWorker {};
-    proving_worker.run_worker(services.config).await?;
+    proving_worker.run_worker(services.config, None, None).await?;
 
     Ok(())
 }
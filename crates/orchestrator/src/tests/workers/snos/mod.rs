@@ -118,7 +118,7 @@ async fn test_snos_worker(#[case] db_val: bool) -> Result<(), Box<dyn Error>> {
     });
 
     let snos_worker = SnosWorker {};
-    snos_worker.run_worker(services.config).await?;
+    snos_worker.run_worker(services.config, None, None).await?;
 
     rpc_block_call_mock.assert();
 
@@ -10,6 +10,7 @@ use crate::jobs::metadata::{CommonMetadata, JobMetadata, JobSpecificMetadata, St
 use crate::jobs::state_update_job::StateUpdateJob;
 use crate::jobs::types::{JobStatus, JobType};
 use crate::tests::config::{ConfigType, TestConfigBuilder};
+use crate::tests::utils::build_job_item;
 use crate::tests::workers::utils::{create_and_store_prerequisite_jobs, get_job_item_mock_by_id};
 use crate::workers::update_state::UpdateStateWorker;
 use crate::workers::Worker;
@@ -30,7 +31,7 @@ async fn update_state_worker_with_pending_jobs() {
     services.config.database().create_job(job_item).await.unwrap();
 
     let update_state_worker = UpdateStateWorker {};
-    assert!(update_state_worker.run_worker(services.config.clone()).await.is_ok());
+    assert!(update_state_worker.run_worker(services.config.clone(), None, None).await.is_ok());
 
     let latest_job =
         services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().unwrap();
@@ -55,7 +56,7 @@ async fn update_state_worker_first_block() {
     ctx.expect().with(eq(JobType::StateTransition)).returning(move |_| Arc::new(Box::new(StateUpdateJob)));
 
     let update_state_worker = UpdateStateWorker {};
-    assert!(update_state_worker.run_worker(services.config.clone()).await.is_ok());
+    assert!(update_state_worker.run_worker(services.config.clone(), None, None).await.is_ok());
 
     let latest_job =
         services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().unwrap();
@@ -84,7 +85,7 @@ async fn update_state_worker_first_block_missing() {
     ctx.expect().with(eq(JobType::StateTransition)).returning(move |_| Arc::new(Box::new(StateUpdateJob)));
 
     let update_state_worker = UpdateStateWorker {};
-    assert!(update_state_worker.run_worker(services.config.clone()).await.is_ok());
+    assert!(update_state_worker.run_worker(services.config.clone(), None, None).await.is_ok());
 
     // update state worker should not create any job
     assert!(services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().is_none());
@@ -108,7 +109,7 @@ async fn update_state_worker_selects_consective_blocks() {
     ctx.expect().with(eq(JobType::StateTransition)).returning(move |_| Arc::new(Box::new(StateUpdateJob)));
 
     let update_state_worker = UpdateStateWorker {};
-    assert!(update_state_worker.run_worker(services.config.clone()).await.is_ok());
+    assert!(update_state_worker.run_worker(services.config.clone(), None, None).await.is_ok());
 
     let latest_job =
         services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().unwrap();
@@ -121,6 +122,36 @@ async fn update_state_worker_selects_consective_blocks() {
     assert_eq!(state_metadata.blocks_to_settle, vec![0, 1]);
 }
 
+#[rstest]
+#[tokio::test]
+async fn update_state_worker_waits_for_proof_registration() {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_register_proofs(true)
+        .build()
+        .await;
+
+    // Blocks 0 and 1 are available, but only the proof of block 0 is registered
+    let (_, _) = create_and_store_prerequisite_jobs(services.config.clone(), 0, JobStatus::Completed).await.unwrap();
+    let (_, _) = create_and_store_prerequisite_jobs(services.config.clone(), 1, JobStatus::Completed).await.unwrap();
+    let registration_job = build_job_item(JobType::ProofRegistration, JobStatus::Completed, 0);
+    services.config.database().create_job(registration_job).await.unwrap();
+    let registration_job = build_job_item(JobType::ProofRegistration, JobStatus::PendingVerification, 1);
+    services.config.database().create_job(registration_job).await.unwrap();
+
+    let ctx = mock_factory::get_job_handler_context();
+    ctx.expect().with(eq(JobType::StateTransition)).returning(move |_| Arc::new(Box::new(StateUpdateJob)));
+
+    let update_state_worker = UpdateStateWorker {};
+    assert!(update_state_worker.run_worker(services.config.clone(), None, None).await.is_ok());
+
+    let latest_job =
+        services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().unwrap();
+    let state_metadata: StateUpdateMetadata = latest_job.metadata.specific.clone().try_into().unwrap();
+    assert_eq!(state_metadata.blocks_to_settle, vec![0]);
+}
+
 #[rstest]
 #[tokio::test]
 async fn update_state_worker_continues_from_previous_state_update() {
@@ -175,7 +206,7 @@ async fn update_state_worker_continues_from_previous_state_update() {
     ctx.expect().with(eq(JobType::StateTransition)).returning(move |_| Arc::new(Box::new(StateUpdateJob)));
 
     let update_state_worker = UpdateStateWorker {};
-    assert!(update_state_worker.run_worker(services.config.clone()).await.is_ok());
+    assert!(update_state_worker.run_worker(services.config.clone(), None, None).await.is_ok());
 
     let latest_job =
         services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().unwrap();
@@ -244,7 +275,7 @@ async fn update_state_worker_next_block_missing() {
     ctx.expect().with(eq(JobType::StateTransition)).returning(move |_| Arc::new(Box::new(StateUpdateJob)));
 
     let update_state_worker = UpdateStateWorker {};
-    assert!(update_state_worker.run_worker(services.config.clone()).await.is_ok());
+    assert!(update_state_worker.run_worker(services.config.clone(), None, None).await.is_ok());
 
     let latest_job =
         services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().unwrap();
@@ -0,0 +1,255 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use opentelemetry::KeyValue;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::database::JobFilter;
+use crate::jobs::types::{JobItem, JobStatus, JobType};
+use crate::metrics::ORCHESTRATOR_METRICS;
+
+/// Statuses of the failed jobs, which halt job creation until they're resolved.
+pub const HALTING_STATUSES: [JobStatus; 3] = [JobStatus::Failed, JobStatus::VerificationTimeout, JobStatus::Stale];
+
+/// Which job creation a failed job halts.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize, strum_macros::Display,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum HaltScope {
+    /// A failed job halts every worker
+    #[default]
+    Global,
+    /// A failed job only halts the worker creating jobs of its type
+    JobType,
+    /// A failed job halts the worker creating jobs of its type and the workers depending on them
+    Pipeline,
+    /// A failed job halts job creation for the blocks after its block
+    LaterBlocks,
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerParams {
+    pub scope: HaltScope,
+    /// Number of failed jobs needed to halt job creation
+    pub failure_threshold: usize,
+    /// Only the jobs which failed within this window count, all the failed jobs count if `None`
+    pub failure_window: Option<Duration>,
+}
+
+impl Default for CircuitBreakerParams {
+    /// Any failed job halts every worker until it's resolved.
+    fn default() -> Self {
+        Self { scope: HaltScope::Global, failure_threshold: 1, failure_window: None }
+    }
+}
+
+/// Whether a worker can create jobs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Jobs can be created
+    Closed,
+    /// No job can be created
+    Open { reason: String },
+    /// Jobs can only be created up to `last_allowed_block`, included
+    OpenAfterBlock { last_allowed_block: u64, reason: String },
+}
+
+impl CircuitState {
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            CircuitState::Closed => None,
+            CircuitState::Open { reason } | CircuitState::OpenAfterBlock { reason, .. } => Some(reason),
+        }
+    }
+}
+
+/// Whether jobs of `job_type` are created out of the output of `dependency` jobs, directly or not.
+fn depends_on(job_type: &JobType, dependency: &JobType) -> bool {
+    job_type.dependencies().iter().any(|direct| direct == dependency || depends_on(direct, dependency))
+}
+
+/// Whether a failed job of `failed_job_type` halts the worker creating `job_type` jobs.
+fn halts(scope: HaltScope, job_type: &JobType, failed_job_type: &JobType) -> bool {
+    match scope {
+        HaltScope::Global | HaltScope::LaterBlocks => true,
+        HaltScope::JobType => failed_job_type == job_type,
+        HaltScope::Pipeline => failed_job_type == job_type || depends_on(job_type, failed_job_type),
+    }
+}
+
+/// Start of the failure window, `None` if all the failed jobs count.
+fn window_start(params: &CircuitBreakerParams, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    params
+        .failure_window
+        .and_then(|window| chrono::Duration::from_std(window).ok())
+        .and_then(|window| now.checked_sub_signed(window))
+}
+
+/// Computes the state of the circuit of the worker creating `job_type` jobs out of the failed jobs.
+///
+/// `first_failed_block` is the first block of all the relevant failed jobs, when `failed_jobs` only
+/// holds some of them.
+pub fn evaluate_circuit_state(
+    params: &CircuitBreakerParams,
+    job_type: &JobType,
+    failed_jobs: &[JobItem],
+    first_failed_block: Option<u64>,
+    now: DateTime<Utc>,
+) -> CircuitState {
+    let window_start = window_start(params, now);
+
+    let relevant_failed_jobs: Vec<&JobItem> = failed_jobs
+        .iter()
+        .filter(|job| window_start.map_or(true, |start| job.failed_at() >= start))
+        .filter(|job| halts(params.scope, job_type, &job.job_type))
+        .collect();
+
+    if relevant_failed_jobs.is_empty() || relevant_failed_jobs.len() < params.failure_threshold {
+        return CircuitState::Closed;
+    }
+
+    let mut failed_job_types: Vec<String> =
+        relevant_failed_jobs.iter().map(|job| format!("{:?}", job.job_type)).collect();
+    failed_job_types.sort();
+    failed_job_types.dedup();
+    let reason = format!(
+        "{} failed job(s) of type {} reached the threshold of {} ({} halt scope)",
+        relevant_failed_jobs.len(),
+        failed_job_types.join(", "),
+        params.failure_threshold,
+        params.scope
+    );
+
+    match params.scope {
+        HaltScope::LaterBlocks => {
+            // the internal id of a job is its first block
+            let failed_blocks = relevant_failed_jobs.iter().filter_map(|job| job.internal_id.parse::<u64>().ok());
+            match failed_blocks.chain(first_failed_block).min() {
+                Some(first_failed_block) => {
+                    CircuitState::OpenAfterBlock { last_allowed_block: first_failed_block, reason }
+                }
+                None => CircuitState::Open { reason },
+            }
+        }
+        _ => CircuitState::Open { reason },
+    }
+}
+
+/// Fetches the failed jobs and computes the state of the circuit of the worker creating
+/// `job_type` jobs.
+///
+/// The failed jobs are counted first, so they're only fetched when they may reach the threshold,
+/// and then at most `failure_threshold` of each type and status. The first failed block the
+/// `LaterBlocks` scope halts after is queried separately, since jobs can fail in any order.
+pub async fn get_circuit_state(config: &Config, job_type: &JobType) -> color_eyre::Result<CircuitState> {
+    let params = &config.service_config().circuit_breaker;
+    let now = Utc::now();
+
+    let halting_counts: Vec<(JobType, JobStatus, u64)> = config
+        .database()
+        .count_jobs_by_type_and_status()
+        .await?
+        .into_iter()
+        .filter(|(failed_job_type, status, count)| {
+            *count > 0 && HALTING_STATUSES.contains(status) && halts(params.scope, job_type, failed_job_type)
+        })
+        .collect();
+
+    let mut failed_jobs = Vec::new();
+    let mut first_failed_block: Option<u64> = None;
+    // the counts include the failures out of the window, below the threshold the circuit is closed
+    // anyway
+    if halting_counts.iter().map(|(_, _, count)| *count as usize).sum::<usize>() >= params.failure_threshold {
+        for (failed_job_type, status, _) in halting_counts {
+            let filter = JobFilter {
+                job_type: Some(failed_job_type),
+                status: Some(status),
+                failed_after: window_start(params, now),
+                ..Default::default()
+            };
+            let limit = params.failure_threshold.max(1) as i64;
+            failed_jobs.extend(config.database().get_jobs(filter.clone(), None, limit).await?);
+            if params.scope == HaltScope::LaterBlocks {
+                let first_block = config.database().get_first_block_of_jobs(filter).await?;
+                first_failed_block = first_failed_block.into_iter().chain(first_block).min();
+            }
+        }
+    }
+    let state = evaluate_circuit_state(params, job_type, &failed_jobs, first_failed_block, now);
+
+    // the reason is only logged and exposed by the API, a label per reason would leave a series
+    // behind every time it changes
+    let attributes =
+        [KeyValue::new("job_type", format!("{:?}", job_type)), KeyValue::new("halt_scope", params.scope.to_string())];
+    let halted = if state == CircuitState::Closed { 0.0 } else { 1.0 };
+    ORCHESTRATOR_METRICS.halted_workers.record(halted, &attributes);
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod circuit_breaker_test {
+    use chrono::SubsecRound;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::tests::utils::build_job_item;
+
+    fn failed_job(job_type: JobType, block_number: u64) -> JobItem {
+        build_job_item(job_type, JobStatus::Failed, block_number)
+    }
+
+    #[rstest]
+    #[case(HaltScope::Global, JobType::SnosRun, false)]
+    #[case(HaltScope::JobType, JobType::SnosRun, true)]
+    #[case(HaltScope::JobType, JobType::ProofCreation, false)]
+    #[case(HaltScope::Pipeline, JobType::SnosRun, true)]
+    #[case(HaltScope::Pipeline, JobType::DataSubmission, false)]
+    #[case(HaltScope::Pipeline, JobType::StateTransition, false)]
+    fn test_scope(#[case] scope: HaltScope, #[case] job_type: JobType, #[case] is_closed: bool) {
+        let params = CircuitBreakerParams { scope, ..Default::default() };
+        let failed_jobs = vec![failed_job(JobType::ProofCreation, 10)];
+
+        let state = evaluate_circuit_state(&params, &job_type, &failed_jobs, None, Utc::now());
+        assert_eq!(state == CircuitState::Closed, is_closed);
+    }
+
+    #[rstest]
+    fn test_later_blocks_scope() {
+        let params = CircuitBreakerParams { scope: HaltScope::LaterBlocks, ..Default::default() };
+        let failed_jobs = vec![failed_job(JobType::ProofCreation, 12), failed_job(JobType::DataSubmission, 10)];
+
+        let state = evaluate_circuit_state(&params, &JobType::SnosRun, &failed_jobs, None, Utc::now());
+        assert!(matches!(state, CircuitState::OpenAfterBlock { last_allowed_block: 10, .. }));
+
+        // a failed job out of the fetched ones may hold an earlier block
+        let state = evaluate_circuit_state(&params, &JobType::SnosRun, &failed_jobs, Some(4), Utc::now());
+        assert!(matches!(state, CircuitState::OpenAfterBlock { last_allowed_block: 4, .. }));
+    }
+
+    #[rstest]
+    fn test_threshold_and_window() {
+        let now = Utc::now().round_subsecs(0);
+        let params = CircuitBreakerParams {
+            scope: HaltScope::Global,
+            failure_threshold: 2,
+            failure_window: Some(Duration::from_secs(60)),
+        };
+        // the failure time counts, not the last update
+        let mut old_failed_job = failed_job(JobType::SnosRun, 1);
+        old_failed_job.metadata.common.failed_at = Some(now - chrono::Duration::seconds(120));
+        let mut failed_jobs = vec![old_failed_job, failed_job(JobType::SnosRun, 2)];
+
+        // the old failure is out of the window
+        let state = evaluate_circuit_state(&params, &JobType::SnosRun, &failed_jobs, None, now);
+        assert_eq!(state, CircuitState::Closed);
+
+        failed_jobs.push(failed_job(JobType::SnosRun, 3));
+        let state = evaluate_circuit_state(&params, &JobType::SnosRun, &failed_jobs, None, now);
+        assert!(matches!(state, CircuitState::Open { .. }));
+    }
+}
@@ -2,23 +2,35 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use opentelemetry::KeyValue;
+use tracing::Instrument as _;
 
 use crate::config::Config;
 use crate::constants::BLOB_DATA_FILE_NAME;
+use crate::database::Fence;
 use crate::jobs::create_job;
 use crate::jobs::metadata::{CommonMetadata, DaMetadata, JobMetadata, JobSpecificMetadata, ProvingMetadata};
 use crate::jobs::types::{JobStatus, JobType};
 use crate::metrics::ORCHESTRATOR_METRICS;
+use crate::telemetry::propagation::continue_trace;
 use crate::workers::Worker;
 
 pub struct DataSubmissionWorker;
 
 #[async_trait]
 impl Worker for DataSubmissionWorker {
+    fn job_type(&self) -> Option<JobType> {
+        Some(JobType::DataSubmission)
+    }
+
     // 0. All ids are assumed to be block numbers.
     // 1. Fetch the latest completed Proving jobs without Data Submission jobs as successor jobs
     // 2. Create jobs.
-    async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()> {
+    async fn run_worker(
+        &self,
+        config: Arc<Config>,
+        last_allowed_block: Option<u64>,
+        _fence: Option<&Fence>,
+    ) -> color_eyre::Result<()> {
         tracing::trace!(log_type = "starting", category = "DataSubmissionWorker", "DataSubmissionWorker started.");
 
         let successful_proving_jobs = config
@@ -37,6 +49,11 @@ impl Worker for DataSubmissionWorker {
                 e
             })?;
 
+            if last_allowed_block.is_some_and(|last_allowed_block| proving_metadata.block_number > last_allowed_block) {
+                tracing::debug!(block_id = %proving_job.internal_id, "Block is halted, skipping data submission job creation");
+                continue;
+            }
+
             // Create DA metadata
             let da_metadata = JobMetadata {
                 common: CommonMetadata::default(),
@@ -49,8 +66,18 @@ impl Worker for DataSubmissionWorker {
                 }),
             };
 
-            match create_job(JobType::DataSubmission, proving_job.internal_id.clone(), da_metadata, config.clone())
-                .await
+            // the data submission job continues the trace of the proving job of the block
+            let span = tracing::info_span!("create_successor_job", block_id = %proving_job.internal_id);
+            continue_trace(&span, &proving_job.metadata.common.trace_context);
+            match create_job(
+                JobType::DataSubmission,
+                proving_job.internal_id.clone(),
+                da_metadata,
+                None,
+                config.clone(),
+            )
+            .instrument(span)
+            .await
             {
                 Ok(_) => tracing::info!(
                     block_id = %proving_job.internal_id,
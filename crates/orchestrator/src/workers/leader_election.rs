@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use tokio::sync::{watch, Mutex, OwnedMappedMutexGuard, OwnedMutexGuard};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::database::Fence;
+
+/// Identifies this orchestrator process among the replicas running elections.
+pub static REPLICA_ID: Lazy<Uuid> = Lazy::new(Uuid::new_v4);
+
+/// How long a leadership lasts without being renewed, if not configured.
+pub const DEFAULT_WORKER_LEADER_LEASE_TTL: Duration = Duration::from_secs(30);
+
+/// An election among the orchestrator replicas sharing the database, of which at most one replica
+/// leads at a time.
+pub struct LeaderElection {
+    name: String,
+    candidate: Uuid,
+    lease_ttl: Duration,
+}
+
+impl LeaderElection {
+    pub fn new(name: impl Into<String>, candidate: Uuid, lease_ttl: Duration) -> Self {
+        Self { name: name.into(), candidate, lease_ttl }
+    }
+
+    /// Leads the election, returns `None` if another candidate leads.
+    ///
+    /// The leadership held by this replica is reused as long as it's not lost, it's only taken
+    /// again if it was. It's kept once the returned leadership is dropped.
+    pub async fn lead(&self, config: Arc<Config>) -> color_eyre::Result<Option<HeldLeadership>> {
+        let slot = config.leaderships().slot(&self.name).await;
+        let mut held = slot.lock_owned().await;
+        // dropping a lost leadership releases it, in case its lease can still be released
+        if held.as_ref().is_some_and(Leadership::is_lost) {
+            *held = None;
+        }
+        if held.is_none() {
+            *held = self.try_acquire(config).await?;
+        }
+        Ok(OwnedMutexGuard::try_map(held, Option::as_mut).ok())
+    }
+
+    /// Tries to take the leadership, returns `None` if another candidate leads.
+    pub async fn try_acquire(&self, config: Arc<Config>) -> color_eyre::Result<Option<Leadership>> {
+        let acquired_at = Instant::now();
+        let Some(fencing_token) =
+            config.database().acquire_leadership(&self.name, self.candidate, self.lease_ttl).await?
+        else {
+            return Ok(None);
+        };
+
+        let (lost_sender, lost) = watch::channel(false);
+        let heartbeat = tokio::spawn(renew_leadership_periodically(
+            self.name.clone(),
+            self.candidate,
+            fencing_token,
+            self.lease_ttl,
+            acquired_at,
+            lost_sender,
+            config.clone(),
+        ));
+        Ok(Some(Leadership {
+            name: self.name.clone(),
+            candidate: self.candidate,
+            fencing_token,
+            config,
+            heartbeat,
+            lost,
+            released: false,
+        }))
+    }
+}
+
+/// The leaderships held by this replica, by election.
+///
+/// A leadership is kept across the runs it's taken for, and renewed in the background until it's
+/// lost or released on shutdown, so that it only changes hands when its leader stops or can't
+/// renew its lease.
+#[derive(Default)]
+pub struct HeldLeaderships {
+    leaderships: Mutex<HashMap<String, Arc<Mutex<Option<Leadership>>>>>,
+}
+
+/// A leadership held by this replica, the other runs of the replica wait for it to be dropped
+/// before leading the same election.
+pub type HeldLeadership = OwnedMappedMutexGuard<Option<Leadership>, Leadership>;
+
+impl HeldLeaderships {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn slot(&self, name: &str) -> Arc<Mutex<Option<Leadership>>> {
+        self.leaderships.lock().await.entry(name.to_string()).or_default().clone()
+    }
+
+    /// Releases all the leaderships, so that the other replicas take over without waiting for the
+    /// leases to expire.
+    pub async fn release_all(&self) {
+        let slots: Vec<_> = self.leaderships.lock().await.drain().map(|(_, slot)| slot).collect();
+        for slot in slots {
+            if let Some(leadership) = slot.lock().await.take() {
+                let name = leadership.name.clone();
+                if let Err(e) = leadership.release().await {
+                    tracing::warn!(election = %name, error = ?e, "Failed to release leadership");
+                }
+            }
+        }
+    }
+}
+
+/// The leadership of an election, renewed in the background until released.
+///
+/// If it's dropped without being released, it's released in the background.
+pub struct Leadership {
+    name: String,
+    candidate: Uuid,
+    fencing_token: u64,
+    config: Arc<Config>,
+    heartbeat: JoinHandle<()>,
+    lost: watch::Receiver<bool>,
+    released: bool,
+}
+
+impl Leadership {
+    /// Tells this leadership apart from the previous and next ones of the election, it increases
+    /// every time the leadership changes hands.
+    pub fn fencing_token(&self) -> u64 {
+        self.fencing_token
+    }
+
+    /// Fences the writes made under this leadership, see `Fence`.
+    pub fn fence(&self) -> Fence {
+        Fence { election: self.name.clone(), fencing_token: self.fencing_token }
+    }
+
+    /// Resolves once the leadership is lost, i.e. its lease couldn't be renewed before expiring and
+    /// another candidate may lead.
+    pub async fn lost(&self) {
+        let mut lost = self.lost.clone();
+        // the sender is only dropped once the heartbeat stops, which means the leadership is lost
+        let _ = lost.wait_for(|lost| *lost).await;
+    }
+
+    /// Whether the leadership is lost, see `Leadership::lost`.
+    pub fn is_lost(&self) -> bool {
+        *self.lost.borrow()
+    }
+
+    pub async fn release(mut self) -> color_eyre::Result<()> {
+        self.heartbeat.abort();
+        self.released = true;
+        self.config.database().release_leadership(&self.name, self.candidate, self.fencing_token).await
+    }
+}
+
+impl Drop for Leadership {
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+        if self.released {
+            return;
+        }
+        let (name, candidate, fencing_token, config) =
+            (self.name.clone(), self.candidate, self.fencing_token, self.config.clone());
+        tokio::spawn(async move {
+            if let Err(e) = config.database().release_leadership(&name, candidate, fencing_token).await {
+                // the leadership ends anyway once its lease expires
+                tracing::warn!(election = %name, fencing_token, error = ?e, "Failed to release leadership");
+            }
+        });
+    }
+}
+
+/// Renews the leadership three times per TTL. The leadership is considered lost as soon as a
+/// renewal is rejected, or when its lease would expire before the next renewal.
+async fn renew_leadership_periodically(
+    name: String,
+    candidate: Uuid,
+    fencing_token: u64,
+    ttl: Duration,
+    acquired_at: Instant,
+    lost: watch::Sender<bool>,
+    config: Arc<Config>,
+) {
+    let mut renewed_at = acquired_at;
+    loop {
+        tokio::time::sleep(ttl / 3).await;
+        let renewal_started_at = Instant::now();
+        match config.database().renew_leadership(&name, candidate, fencing_token, ttl).await {
+            Ok(true) => renewed_at = renewal_started_at,
+            Ok(false) => {
+                tracing::error!(election = %name, fencing_token, "Leadership lost, it was taken by another candidate");
+                break;
+            }
+            Err(e) => {
+                tracing::warn!(election = %name, fencing_token, error = ?e, "Failed to renew leadership");
+                // the lease would expire before the next renewal
+                if renewed_at.elapsed() + ttl / 3 >= ttl {
+                    tracing::error!(election = %name, fencing_token, "Leadership lost, it couldn't be renewed in time");
+                    break;
+                }
+            }
+        }
+    }
+    let _ = lost.send(true);
+}
@@ -2,15 +2,22 @@ use std::error::Error;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use color_eyre::eyre::eyre;
 use thiserror::Error;
 
 use crate::config::Config;
-use crate::jobs::types::JobStatus;
+use crate::database::Fence;
+use crate::jobs::types::JobType;
+use crate::workers::circuit_breaker::{get_circuit_state, CircuitState};
+use crate::workers::leader_election::{LeaderElection, REPLICA_ID};
 
+pub mod circuit_breaker;
 pub mod data_submission_worker;
+pub mod leader_election;
 pub mod proof_registration;
 pub mod proving;
 pub mod snos;
+pub mod stuck_job_reaper;
 pub mod update_state;
 
 #[derive(Error, Debug)]
@@ -28,34 +35,75 @@ pub enum WorkerError {
 #[async_trait]
 pub trait Worker: Send + Sync {
     async fn run_worker_if_enabled(&self, config: Arc<Config>) -> color_eyre::Result<()> {
-        if !self.is_worker_enabled(config.clone()).await? {
-            return Ok(());
-        }
-        self.run_worker(config).await
-    }
+        // workers which don't create jobs aren't halted by failed jobs
+        let Some(job_type) = self.job_type() else {
+            return self.run_worker(config, None, None).await;
+        };
 
-    async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()>;
+        // Assumption
+        // If say a job for block X fails, we don't want the worker to respawn another job for the
+        // same block we will resolve the existing failed job first.
 
-    // Assumption
-    // If say a job for block X fails, we don't want the worker to respawn another job for the same
-    // block we will resolve the existing failed job first.
+        // Failed jobs (JobStatus::Failed, JobStatus::VerificationTimeout, JobStatus::Stale) halt
+        // job creation until they are resolved. Which workers and blocks are halted, and after how
+        // many failures, is set by `CircuitBreakerParams`. By default any failed job halts every
+        // worker.
+        let last_allowed_block = match get_circuit_state(&config, &job_type).await? {
+            CircuitState::Closed => None,
+            CircuitState::OpenAfterBlock { last_allowed_block, reason } => {
+                tracing::warn!(job_type = ?job_type, %reason, last_allowed_block, "Worker halted for the later blocks");
+                Some(last_allowed_block)
+            }
+            CircuitState::Open { reason } => {
+                tracing::warn!(job_type = ?job_type, %reason, "Worker halted");
+                return Ok(());
+            }
+        };
 
-    // We assume the system to keep working till a job hasn't failed,
-    // as soon as it fails we currently halt any more execution and wait for manual intervention.
+        if !self.is_singleton() {
+            return self.run_worker(config, last_allowed_block, None).await;
+        }
 
-    // Checks if any of the jobs have failed
-    // Failure : JobStatus::VerificationFailed, JobStatus::VerificationTimeout, JobStatus::Failed
-    // Halts any new job creation till all the count of failed jobs is not Zero.
-    async fn is_worker_enabled(&self, config: Arc<Config>) -> color_eyre::Result<bool> {
-        let failed_jobs = config
-            .database()
-            .get_jobs_by_statuses(vec![JobStatus::Failed, JobStatus::VerificationTimeout], Some(1))
-            .await?;
+        // singleton workers only run on the replica leading their election, the other replicas
+        // skip the trigger. The leadership is kept across the triggers, and the run is stopped if
+        // it's lost in the meantime
+        let election = LeaderElection::new(
+            format!("{:?}_worker", job_type),
+            *REPLICA_ID,
+            config.service_config().worker_leader_lease_ttl,
+        );
+        let Some(leadership) = election.lead(config.clone()).await? else {
+            tracing::debug!(job_type = ?job_type, "Another replica leads the worker, skipping");
+            return Ok(());
+        };
+        tracing::debug!(job_type = ?job_type, fencing_token = leadership.fencing_token(), "Leading the worker");
 
-        if !failed_jobs.is_empty() {
-            return Ok(false);
+        // the jobs are created under the leadership, a former leader can't create them anymore
+        // once another replica took over
+        let fence = leadership.fence();
+        tokio::select! {
+            result = self.run_worker(config.clone(), last_allowed_block, Some(&fence)) => result,
+            _ = leadership.lost() => {
+                Err(eyre!("Lost the leadership of the {:?} worker while running it", job_type))
+            }
         }
+    }
+
+    /// Creates the jobs of the worker, for blocks up to `last_allowed_block` if set and under the
+    /// leadership of `fence` if set.
+    async fn run_worker(
+        &self,
+        config: Arc<Config>,
+        last_allowed_block: Option<u64>,
+        fence: Option<&Fence>,
+    ) -> color_eyre::Result<()>;
+
+    /// Type of the jobs created by the worker, `None` if it doesn't create jobs.
+    fn job_type(&self) -> Option<JobType>;
 
-        Ok(true)
+    /// Whether the worker must only run on one replica at a time, e.g. because the jobs it
+    /// creates can't be created concurrently. Only applies to workers creating jobs.
+    fn is_singleton(&self) -> bool {
+        false
     }
 }
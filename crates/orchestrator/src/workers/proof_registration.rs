@@ -1,18 +1,208 @@
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use opentelemetry::KeyValue;
 
 use crate::config::Config;
+use crate::database::{Fence, JobFilter};
+use crate::jobs::create_job;
+use crate::jobs::metadata::{
+    CommonMetadata, JobMetadata, JobSpecificMetadata, ProofRegistrationMetadata, ProvingMetadata, SnosMetadata,
+};
+use crate::jobs::types::{ExternalId, JobItem, JobStatus, JobType};
+use crate::metrics::ORCHESTRATOR_METRICS;
 use crate::workers::Worker;
 
+/// Number of proving jobs fetched at once while listing the blocks of a proof.
+const PROOF_GROUP_PAGE_SIZE: i64 = 100;
+
 pub struct ProofRegistrationWorker;
 
 #[async_trait]
 impl Worker for ProofRegistrationWorker {
+    fn job_type(&self) -> Option<JobType> {
+        Some(JobType::ProofRegistration)
+    }
+
+    fn is_singleton(&self) -> bool {
+        true
+    }
+
     /// 1. Fetch all blocks with a successful proving job run
-    /// 2. Group blocks that have the same proof
-    /// 3. For each group, create a proof registration job with from and to block in metadata
-    async fn run_worker(&self, _config: Arc<Config>) -> color_eyre::Result<()> {
-        todo!()
+    /// 2. Group blocks that have the same proof, once every block of the proof is proven
+    /// 3. For each group not registered for its whole range yet, create a proof registration job
+    ///    with from and to block in metadata
+    async fn run_worker(
+        &self,
+        config: Arc<Config>,
+        last_allowed_block: Option<u64>,
+        fence: Option<&Fence>,
+    ) -> color_eyre::Result<()> {
+        tracing::trace!(
+            log_type = "starting",
+            category = "ProofRegistrationWorker",
+            "ProofRegistrationWorker started."
+        );
+
+        if !config.service_config().register_proofs {
+            tracing::debug!("Proof registration is disabled, skipping");
+            return Ok(());
+        }
+
+        let successful_proving_jobs = config
+            .database()
+            .get_jobs_without_successor(JobType::ProofCreation, JobStatus::Completed, JobType::ProofRegistration)
+            .await?;
+
+        tracing::debug!(
+            "Found {} successful proving jobs without proof registration jobs",
+            successful_proving_jobs.len()
+        );
+
+        // Blocks proven by the same prover task share the same proof
+        let task_ids: BTreeSet<String> = successful_proving_jobs
+            .iter()
+            .map(|proving_job| proving_job.external_id.unwrap_string().map(str::to_string))
+            .collect::<color_eyre::Result<_>>()?;
+
+        for task_id in task_ids {
+            // the group is recomputed from every block of the proof, not only the ones without a
+            // registration yet, so that the registered range and fact cover the whole proof
+            let group = get_proving_jobs_of_task(&config, &task_id).await?;
+            if group.iter().any(|proving_job| proving_job.status != JobStatus::Completed) {
+                tracing::debug!(%task_id, "Some blocks of the proof aren't proven yet, skipping proof registration");
+                continue;
+            }
+            let mut blocks = group
+                .into_iter()
+                .map(|proving_job| {
+                    proving_job.metadata.specific.try_into().map_err(|e| {
+                        let job_id = &proving_job.internal_id;
+                        tracing::error!(%job_id, error = %e, "Invalid metadata type for proving job");
+                        e
+                    })
+                })
+                .collect::<color_eyre::Result<Vec<ProvingMetadata>>>()?;
+            blocks.sort_by_key(|metadata| metadata.block_number);
+            let (from_block, to_block) = match (blocks.first(), blocks.last()) {
+                (Some(first), Some(last)) => (first.block_number, last.block_number),
+                _ => continue,
+            };
+
+            // the proof can only be registered once none of its blocks is halted
+            if last_allowed_block.is_some_and(|last_allowed_block| to_block > last_allowed_block) {
+                tracing::debug!(%task_id, from_block, to_block, "Blocks are halted, skipping proof registration");
+                continue;
+            }
+
+            // Registration jobs are matched to their proof by its task id. A proof which got more
+            // blocks once registered is registered again for its whole range, keyed by the first
+            // block the previous registrations didn't cover.
+            let registrations: Vec<ProofRegistrationMetadata> = config
+                .database()
+                .get_jobs_by_block_range(from_block, to_block)
+                .await?
+                .into_iter()
+                .filter(|job| job.job_type == JobType::ProofRegistration)
+                .map(|job| job.metadata.specific.try_into())
+                .collect::<color_eyre::Result<Vec<ProofRegistrationMetadata>>>()?
+                .into_iter()
+                .filter(|registration| registration.proof_task_id == task_id)
+                .collect();
+            let uncovered_block = blocks.iter().map(|metadata| metadata.block_number).find(|block| {
+                !registrations
+                    .iter()
+                    .any(|registration| (registration.from_block..=registration.to_block).contains(block))
+            });
+            let Some(internal_id) = uncovered_block else {
+                tracing::trace!(%task_id, from_block, to_block, "Proof is already registered, skipping");
+                continue;
+            };
+
+            // The fact of the proof is the SNOS fact of the last block it covers
+            let snos_metadata: Option<SnosMetadata> = config
+                .database()
+                .get_job_by_internal_id_and_type(&to_block.to_string(), &JobType::SnosRun)
+                .await?
+                .map(|job| job.metadata.specific.try_into())
+                .transpose()?;
+            let snos_fact = match snos_metadata.and_then(|metadata| metadata.snos_fact) {
+                Some(fact) => fact,
+                None => {
+                    tracing::error!(block_no = %to_block, "SNOS fact not found for proof registration");
+                    continue;
+                }
+            };
+
+            let registration_metadata = JobMetadata {
+                common: CommonMetadata::default(),
+                specific: JobSpecificMetadata::ProofRegistration(ProofRegistrationMetadata {
+                    from_block,
+                    to_block,
+                    proof_task_id: task_id,
+                    fact: Some(snos_fact),
+                    proof_path: blocks.last().and_then(|metadata| metadata.download_proof.clone()),
+                    tx_hash: None,
+                }),
+            };
+
+            tracing::debug!(from_block, to_block, "Creating proof registration job");
+            match create_job(
+                JobType::ProofRegistration,
+                internal_id.to_string(),
+                registration_metadata,
+                fence,
+                config.clone(),
+            )
+            .await
+            {
+                Ok(_) => tracing::info!(block_id = %internal_id, "Successfully created new proof registration job"),
+                Err(e) => {
+                    tracing::warn!(block_id = %internal_id, error = %e, "Failed to create new proof registration job");
+                    let attributes = [
+                        KeyValue::new("operation_job_type", format!("{:?}", JobType::ProofRegistration)),
+                        KeyValue::new("operation_type", format!("{:?}", "create_job")),
+                    ];
+                    ORCHESTRATOR_METRICS.failed_job_operations.add(1.0, &attributes);
+                }
+            }
+        }
+
+        tracing::trace!(
+            log_type = "completed",
+            category = "ProofRegistrationWorker",
+            "ProofRegistrationWorker completed."
+        );
+        Ok(())
     }
 }
+
+/// Lists the proving jobs of every block proven by the prover task `task_id`, whatever their
+/// status.
+async fn get_proving_jobs_of_task(config: &Config, task_id: &str) -> color_eyre::Result<Vec<JobItem>> {
+    let filter = JobFilter {
+        job_type: Some(JobType::ProofCreation),
+        external_id: Some(ExternalId::String(task_id.into())),
+        ..Default::default()
+    };
+    let mut proving_jobs = Vec::new();
+    let mut cursor = None;
+    loop {
+        let jobs = config.database().get_jobs(filter.clone(), cursor, PROOF_GROUP_PAGE_SIZE).await?;
+        cursor = jobs.last().map(Into::into);
+        let last_page = (jobs.len() as i64) < PROOF_GROUP_PAGE_SIZE;
+        proving_jobs.extend(jobs);
+        if last_page {
+            return Ok(proving_jobs);
+        }
+    }
+}
+
+/// Whether the proof of `block_number` was registered on the settlement layer by a completed
+/// proof registration job. The data submission and state transition jobs of a block wait for it
+/// when the orchestrator registers the proofs, as the state update reverts without the fact.
+pub async fn is_proof_registered(config: &Config, block_number: u64) -> color_eyre::Result<bool> {
+    let jobs = config.database().get_jobs_by_block_range(block_number, block_number).await?;
+    Ok(jobs.iter().any(|job| job.job_type == JobType::ProofRegistration && job.status == JobStatus::Completed))
+}
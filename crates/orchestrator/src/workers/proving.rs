@@ -2,23 +2,36 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use opentelemetry::KeyValue;
+use tracing::Instrument as _;
 
 use crate::config::Config;
+use crate::constants::PROOF_FILE_NAME;
+use crate::database::Fence;
 use crate::jobs::create_job;
 use crate::jobs::metadata::{
     CommonMetadata, JobMetadata, JobSpecificMetadata, ProvingInputType, ProvingMetadata, SnosMetadata,
 };
 use crate::jobs::types::{JobStatus, JobType};
 use crate::metrics::ORCHESTRATOR_METRICS;
+use crate::telemetry::propagation::continue_trace;
 use crate::workers::Worker;
 
 pub struct ProvingWorker;
 
 #[async_trait]
 impl Worker for ProvingWorker {
+    fn job_type(&self) -> Option<JobType> {
+        Some(JobType::ProofCreation)
+    }
+
     /// 1. Fetch all successful SNOS job runs that don't have a proving job
     /// 2. Create a proving job for each SNOS job run
-    async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()> {
+    async fn run_worker(
+        &self,
+        config: Arc<Config>,
+        last_allowed_block: Option<u64>,
+        _fence: Option<&Fence>,
+    ) -> color_eyre::Result<()> {
         tracing::trace!(log_type = "starting", category = "ProvingWorker", "ProvingWorker started.");
 
         let successful_snos_jobs = config
@@ -35,6 +48,11 @@ impl Worker for ProvingWorker {
                 e
             })?;
 
+            if last_allowed_block.is_some_and(|last_allowed_block| snos_metadata.block_number > last_allowed_block) {
+                tracing::debug!(block_id = %snos_job.internal_id, "Block is halted, skipping proving job creation");
+                continue;
+            }
+
             // Get SNOS fact early to handle the error case
             let snos_fact = match &snos_metadata.snos_fact {
                 Some(fact) => fact.clone(),
@@ -51,16 +69,30 @@ impl Worker for ProvingWorker {
                     block_number: snos_metadata.block_number,
                     // Set input path as CairoPie type
                     input_path: snos_metadata.cairo_pie_path.map(ProvingInputType::CairoPie),
-                    // Set download path if needed
-                    download_proof: None,
-                    // Set SNOS fact for on-chain verification
-                    ensure_on_chain_registration: Some(snos_fact),
+                    // Set download path if the proof has to be registered by the orchestrator
+                    download_proof: config
+                        .service_config()
+                        .register_proofs
+                        .then(|| format!("{}/{}", snos_metadata.block_number, PROOF_FILE_NAME)),
+                    // Set SNOS fact for on-chain verification, unless the orchestrator registers the proof
+                    // itself in which case the fact can't be on chain yet
+                    ensure_on_chain_registration: (!config.service_config().register_proofs).then_some(snos_fact),
                 }),
             };
 
             tracing::debug!(job_id = %snos_job.internal_id, "Creating proof creation job for SNOS job");
-            match create_job(JobType::ProofCreation, snos_job.internal_id.clone(), proving_metadata, config.clone())
-                .await
+            // the proving job continues the trace of the SNOS job of the block
+            let span = tracing::info_span!("create_successor_job", block_id = %snos_job.internal_id);
+            continue_trace(&span, &snos_job.metadata.common.trace_context);
+            match create_job(
+                JobType::ProofCreation,
+                snos_job.internal_id.clone(),
+                proving_metadata,
+                None,
+                config.clone(),
+            )
+            .instrument(span)
+            .await
             {
                 Ok(_) => tracing::info!(block_id = %snos_job.internal_id, "Successfully created new proving job"),
                 Err(e) => {
@@ -8,6 +8,7 @@ use starknet::providers::Provider;
 
 use crate::config::Config;
 use crate::constants::{CAIRO_PIE_FILE_NAME, PROGRAM_OUTPUT_FILE_NAME, SNOS_OUTPUT_FILE_NAME};
+use crate::database::Fence;
 use crate::jobs::create_job;
 use crate::jobs::metadata::{CommonMetadata, JobMetadata, JobSpecificMetadata, SnosMetadata};
 use crate::jobs::types::JobType;
@@ -18,10 +19,19 @@ pub struct SnosWorker;
 
 #[async_trait]
 impl Worker for SnosWorker {
+    fn job_type(&self) -> Option<JobType> {
+        Some(JobType::SnosRun)
+    }
+
     /// 1. Fetch the latest completed block from the Starknet chain
     /// 2. Fetch the last block that had a SNOS job run.
     /// 3. Create SNOS run jobs for all the remaining blocks
-    async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()> {
+    async fn run_worker(
+        &self,
+        config: Arc<Config>,
+        last_allowed_block: Option<u64>,
+        _fence: Option<&Fence>,
+    ) -> color_eyre::Result<()> {
         tracing::trace!(log_type = "starting", category = "SnosWorker", "SnosWorker started.");
 
         let provider = config.starknet_client();
@@ -32,6 +42,10 @@ impl Worker for SnosWorker {
         } else {
             block_number_provider
         };
+        let latest_block_number = match last_allowed_block {
+            Some(last_allowed_block) => min(last_allowed_block, latest_block_number),
+            None => latest_block_number,
+        };
 
         tracing::debug!(latest_block_number = %latest_block_number, "Fetched latest block number from starknet");
 
@@ -67,7 +81,7 @@ impl Worker for SnosWorker {
                 }),
             };
 
-            match create_job(JobType::SnosRun, block_num.to_string(), metadata, config.clone()).await {
+            match create_job(JobType::SnosRun, block_num.to_string(), metadata, None, config.clone()).await {
                 Ok(_) => tracing::info!(block_id = %block_num, "Successfully created new Snos job"),
                 Err(e) => {
                     tracing::warn!(block_id = %block_num, error = %e, "Failed to create new Snos job");
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use opentelemetry::KeyValue;
+
+use crate::alerts::types::{Alert, AlertSeverity};
+use crate::config::Config;
+use crate::database::{Fence, JobFilter};
+use crate::jobs::metadata::{ReaperAction, ReaperActionKind};
+use crate::jobs::types::{JobItem, JobItemUpdates, JobStatus, JobType};
+use crate::metrics::ORCHESTRATOR_METRICS;
+use crate::queue::job_queue::{add_job_to_process_queue, add_job_to_verification_queue};
+use crate::workers::Worker;
+
+/// How long a job can stay in the same status before being reaped, if not configured.
+pub const DEFAULT_STUCK_JOB_DEADLINE: Duration = Duration::from_secs(3600);
+
+/// Statuses a job can get stuck in: waiting for processing, processing, or pending verification.
+const REAPED_STATUSES: [JobStatus; 4] =
+    [JobStatus::Created, JobStatus::PendingRetry, JobStatus::LockedForProcessing, JobStatus::PendingVerification];
+
+/// Number of jobs fetched at once by the reaper.
+const REAPER_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Clone)]
+pub struct StuckJobReaperParams {
+    /// How long a job of a type can stay in one of the reaped statuses without being updated
+    pub deadlines: HashMap<JobType, Duration>,
+    /// Deadline of the job types without their own
+    pub default_deadline: Duration,
+    /// Number of times a stuck job is re-enqueued before being marked as stale
+    pub max_requeues: usize,
+}
+
+impl Default for StuckJobReaperParams {
+    fn default() -> Self {
+        Self { deadlines: HashMap::new(), default_deadline: DEFAULT_STUCK_JOB_DEADLINE, max_requeues: 3 }
+    }
+}
+
+impl StuckJobReaperParams {
+    pub fn deadline(&self, job_type: &JobType) -> Duration {
+        self.deadlines.get(job_type).copied().unwrap_or(self.default_deadline)
+    }
+}
+
+/// Parses per job type deadlines in seconds, e.g. `SnosRun=7200,ProofCreation=1800`.
+pub fn parse_stuck_job_deadlines(deadlines: &str) -> Result<HashMap<JobType, Duration>, String> {
+    deadlines
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (job_type, secs) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid stuck job deadline {:?}, expected JobType=secs", entry))?;
+            let job_type: JobType = serde_json::from_value(serde_json::Value::String(job_type.trim().to_string()))
+                .map_err(|_| format!("Unknown job type in stuck job deadline {:?}", entry))?;
+            let secs =
+                secs.trim().parse::<u64>().map_err(|e| format!("Invalid stuck job deadline {:?}: {}", entry, e))?;
+            if secs == 0 {
+                return Err(format!("Stuck job deadline {:?} must be at least 1 second", entry));
+            }
+            Ok((job_type, Duration::from_secs(secs)))
+        })
+        .collect()
+}
+
+/// Reconciles the jobs left behind by a crash or a lost queue message: jobs locked for processing
+/// whose processing never ended, and jobs waiting for processing or pending verification whose
+/// queue message is gone.
+pub struct StuckJobReaperWorker;
+
+#[async_trait]
+impl Worker for StuckJobReaperWorker {
+    fn job_type(&self) -> Option<JobType> {
+        None
+    }
+
+    /// 1. Page through the jobs waiting for processing, locked for processing or pending
+    ///    verification
+    /// 2. Put the ones which weren't updated for longer than their deadline back in their queue, or
+    ///    mark them as stale and alert once they've been put back `max_requeues` times
+    async fn run_worker(
+        &self,
+        config: Arc<Config>,
+        _last_allowed_block: Option<u64>,
+        _fence: Option<&Fence>,
+    ) -> color_eyre::Result<()> {
+        tracing::trace!(log_type = "starting", category = "StuckJobReaperWorker", "StuckJobReaperWorker started.");
+
+        let params = &config.service_config().stuck_job_reaper;
+        let now = Utc::now();
+        let mut stuck_job_count = 0;
+        for status in REAPED_STATUSES {
+            // reaping a job doesn't move it in the listing, which is ordered by creation
+            let filter = JobFilter { status: Some(status), ..Default::default() };
+            let mut cursor = None;
+            loop {
+                let jobs = config.database().get_jobs(filter.clone(), cursor, REAPER_PAGE_SIZE).await?;
+                cursor = jobs.last().map(Into::into);
+                let last_page = (jobs.len() as i64) < REAPER_PAGE_SIZE;
+
+                for job in jobs.into_iter().filter(|job| is_stuck(params, job, now)) {
+                    stuck_job_count += 1;
+                    let job_id = job.id;
+                    if let Err(e) = reap_stuck_job(job, params, config.clone()).await {
+                        tracing::error!(job_id = %job_id, error = ?e, "Failed to reap stuck job");
+                    }
+                }
+                if last_page {
+                    break;
+                }
+            }
+        }
+
+        tracing::debug!("Found {} stuck jobs", stuck_job_count);
+
+        tracing::trace!(log_type = "completed", category = "StuckJobReaperWorker", "StuckJobReaperWorker completed.");
+        Ok(())
+    }
+}
+
+/// Whether the job wasn't updated for longer than the deadline of its type. A job waiting for a
+/// scheduled attempt is only stuck once the deadline passed after the attempt was due.
+fn is_stuck(params: &StuckJobReaperParams, job: &JobItem, now: DateTime<Utc>) -> bool {
+    let last_activity = job.metadata.common.next_attempt_at.map_or(job.updated_at, |due| due.max(job.updated_at));
+    chrono::Duration::from_std(params.deadline(&job.job_type)).is_ok_and(|deadline| last_activity + deadline < now)
+}
+
+/// What to do with a stuck job, out of its status and the previous actions of the reaper.
+/// Only the requeues since the job was last marked as stale count, so a job retried by hand gets
+/// requeued again.
+fn next_action(params: &StuckJobReaperParams, job: &JobItem) -> ReaperActionKind {
+    let requeues = job
+        .metadata
+        .common
+        .reaper_actions
+        .iter()
+        .rev()
+        .take_while(|action| action.kind != ReaperActionKind::MarkedStale)
+        .count();
+
+    if requeues >= params.max_requeues {
+        ReaperActionKind::MarkedStale
+    } else if job.status != JobStatus::PendingVerification {
+        ReaperActionKind::Reprocessed
+    } else {
+        ReaperActionKind::Reverified
+    }
+}
+
+async fn reap_stuck_job(
+    mut job: JobItem,
+    params: &StuckJobReaperParams,
+    config: Arc<Config>,
+) -> color_eyre::Result<()> {
+    let kind = next_action(params, &job);
+    let stuck_status = job.status.clone();
+    job.metadata.common.reaper_actions.push(ReaperAction {
+        kind,
+        stuck_status: stuck_status.clone(),
+        taken_at: Utc::now(),
+    });
+
+    let new_status = match kind {
+        // processing is only started for jobs in these statuses, a job waiting for processing
+        // keeps its status
+        ReaperActionKind::Reprocessed if stuck_status == JobStatus::LockedForProcessing => JobStatus::PendingRetry,
+        ReaperActionKind::Reprocessed => stuck_status.clone(),
+        ReaperActionKind::Reverified => JobStatus::PendingVerification,
+        ReaperActionKind::MarkedStale => {
+            job.metadata.common.failure_reason = Some(format!(
+                "Stuck in {} for longer than {}s after being requeued {} times",
+                stuck_status,
+                params.deadline(&job.job_type).as_secs(),
+                params.max_requeues
+            ));
+            job.metadata.common.failed_at = Some(Utc::now());
+            JobStatus::Stale
+        }
+    };
+
+    // the version check makes sure the job didn't move on in the meantime, e.g. reaped by another
+    // replica
+    let job = config
+        .database()
+        .update_job(&job, JobItemUpdates::new().update_status(new_status).update_metadata(job.metadata.clone()).build())
+        .await?;
+
+    match kind {
+        ReaperActionKind::Reprocessed => add_job_to_process_queue(job.id, &job.job_type, config.clone()).await?,
+        ReaperActionKind::Reverified => {
+            add_job_to_verification_queue(job.id, &job.job_type, Duration::ZERO, config.clone()).await?
+        }
+        ReaperActionKind::MarkedStale => {
+            let alert =
+                Alert::for_job(AlertSeverity::Critical, format!("{:?} job marked as stale", job.job_type), &job)
+                    .with_failure_reason(job.metadata.common.failure_reason.clone().unwrap_or_default());
+            config.alerts().send_alert(&alert).await?
+        }
+    }
+
+    tracing::warn!(job_id = %job.id, job_type = ?job.job_type, stuck_status = %stuck_status, action = ?kind, "Reaped stuck job");
+    let attributes = [
+        KeyValue::new("operation_job_type", format!("{:?}", job.job_type)),
+        KeyValue::new("reaper_action", format!("{:?}", kind)),
+    ];
+    ORCHESTRATOR_METRICS.reaped_stuck_jobs.add(1.0, &attributes);
+    Ok(())
+}
+
+#[cfg(test)]
+mod stuck_job_reaper_test {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::tests::utils::build_job_item;
+
+    #[rstest]
+    #[case("SnosRun=7200, ProofCreation=1800", true)]
+    #[case("", true)]
+    #[case("SnosRun", false)]
+    #[case("UnknownJob=60", false)]
+    #[case("SnosRun=0", false)]
+    fn test_parse_stuck_job_deadlines(#[case] deadlines: &str, #[case] is_valid: bool) {
+        assert_eq!(parse_stuck_job_deadlines(deadlines).is_ok(), is_valid);
+    }
+
+    #[rstest]
+    fn test_is_stuck_uses_the_deadline_of_the_job_type() {
+        let params = StuckJobReaperParams {
+            deadlines: parse_stuck_job_deadlines("SnosRun=7200").unwrap(),
+            default_deadline: Duration::from_secs(600),
+            max_requeues: 3,
+        };
+        let now = Utc::now();
+        let mut snos_job = build_job_item(JobType::SnosRun, JobStatus::LockedForProcessing, 1);
+        snos_job.updated_at = now - chrono::Duration::seconds(3600);
+        let mut proving_job = build_job_item(JobType::ProofCreation, JobStatus::PendingVerification, 1);
+        proving_job.updated_at = now - chrono::Duration::seconds(3600);
+
+        assert!(!is_stuck(&params, &snos_job, now));
+        assert!(is_stuck(&params, &proving_job, now));
+    }
+
+    #[rstest]
+    fn test_is_stuck_waits_for_the_next_attempt() {
+        let params = StuckJobReaperParams { default_deadline: Duration::from_secs(600), ..Default::default() };
+        let now = Utc::now();
+        let mut job = build_job_item(JobType::SnosRun, JobStatus::PendingRetry, 1);
+        job.updated_at = now - chrono::Duration::seconds(3600);
+        job.metadata.common.next_attempt_at = Some(now - chrono::Duration::seconds(300));
+        assert!(!is_stuck(&params, &job, now));
+
+        job.metadata.common.next_attempt_at = Some(now - chrono::Duration::seconds(900));
+        assert!(is_stuck(&params, &job, now));
+    }
+
+    #[rstest]
+    #[case(JobStatus::LockedForProcessing, vec![], ReaperActionKind::Reprocessed)]
+    #[case(JobStatus::Created, vec![], ReaperActionKind::Reprocessed)]
+    #[case(JobStatus::PendingRetry, vec![ReaperActionKind::Reprocessed], ReaperActionKind::Reprocessed)]
+    #[case(JobStatus::PendingVerification, vec![ReaperActionKind::Reverified], ReaperActionKind::Reverified)]
+    #[case(
+        JobStatus::PendingVerification,
+        vec![ReaperActionKind::Reverified, ReaperActionKind::Reverified],
+        ReaperActionKind::MarkedStale
+    )]
+    #[case(
+        JobStatus::LockedForProcessing,
+        vec![ReaperActionKind::Reprocessed, ReaperActionKind::Reprocessed, ReaperActionKind::MarkedStale],
+        ReaperActionKind::Reprocessed
+    )]
+    fn test_next_action(
+        #[case] status: JobStatus,
+        #[case] previous_actions: Vec<ReaperActionKind>,
+        #[case] expected_action: ReaperActionKind,
+    ) {
+        let params = StuckJobReaperParams { max_requeues: 2, ..Default::default() };
+        let mut job = build_job_item(JobType::SnosRun, status.clone(), 1);
+        job.metadata.common.reaper_actions = previous_actions
+            .into_iter()
+            .map(|kind| ReaperAction { kind, stuck_status: status.clone(), taken_at: Utc::now() })
+            .collect();
+
+        assert_eq!(next_action(&params, &job), expected_action);
+    }
+}
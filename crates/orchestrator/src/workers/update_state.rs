@@ -3,21 +3,38 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use color_eyre::eyre::eyre;
 use opentelemetry::KeyValue;
+use tracing::Instrument as _;
 
 use crate::config::Config;
+use crate::database::Fence;
 use crate::jobs::create_job;
 use crate::jobs::metadata::{
     CommonMetadata, DaMetadata, JobMetadata, JobSpecificMetadata, SnosMetadata, StateUpdateMetadata,
 };
 use crate::jobs::types::{JobStatus, JobType};
 use crate::metrics::ORCHESTRATOR_METRICS;
+use crate::telemetry::propagation::{continue_trace, TraceContext};
+use crate::workers::proof_registration::is_proof_registered;
 use crate::workers::Worker;
 
 pub struct UpdateStateWorker;
 
 #[async_trait]
 impl Worker for UpdateStateWorker {
-    async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()> {
+    fn job_type(&self) -> Option<JobType> {
+        Some(JobType::StateTransition)
+    }
+
+    fn is_singleton(&self) -> bool {
+        true
+    }
+
+    async fn run_worker(
+        &self,
+        config: Arc<Config>,
+        last_allowed_block: Option<u64>,
+        fence: Option<&Fence>,
+    ) -> color_eyre::Result<()> {
         tracing::trace!(log_type = "starting", category = "UpdateStateWorker", "UpdateStateWorker started.");
 
         let latest_job = config.database().get_latest_job_by_type(JobType::StateTransition).await?;
@@ -77,6 +94,9 @@ impl Worker for UpdateStateWorker {
 
         let mut blocks_to_process: Vec<u64> =
             completed_da_jobs.iter().map(|j| j.internal_id.parse::<u64>().unwrap()).collect();
+        if let Some(last_allowed_block) = last_allowed_block {
+            blocks_to_process.retain(|block_number| *block_number <= last_allowed_block);
+        }
         blocks_to_process.sort();
 
         // no DA jobs completed after the last settled block
@@ -110,6 +130,24 @@ impl Worker for UpdateStateWorker {
             blocks_to_process = blocks_to_process.into_iter().take(10).collect();
         }
 
+        // the state update reverts unless the facts of the proofs of its blocks are registered
+        if config.service_config().register_proofs {
+            let mut registered_blocks = 0;
+            for block_number in &blocks_to_process {
+                if !is_proof_registered(&config, *block_number).await? {
+                    break;
+                }
+                registered_blocks += 1;
+            }
+            blocks_to_process.truncate(registered_blocks);
+            if blocks_to_process.is_empty() {
+                log::warn!(
+                    "Proof of the block just after the last settled block is not yet registered. Returning safely..."
+                );
+                return Ok(());
+            }
+        }
+
         // Prepare state transition metadata
         let mut state_metadata = StateUpdateMetadata {
             blocks_to_settle: blocks_to_process.clone(),
@@ -121,6 +159,7 @@ impl Worker for UpdateStateWorker {
         };
 
         // Collect paths from SNOS and DA jobs
+        let mut trace_context: Option<TraceContext> = None;
         for block_number in &blocks_to_process {
             // Get SNOS job paths
             let snos_job = config
@@ -146,6 +185,7 @@ impl Worker for UpdateStateWorker {
                 .get_job_by_internal_id_and_type(&block_number.to_string(), &JobType::DataSubmission)
                 .await?
                 .ok_or_else(|| eyre!("DA job not found for block {}", block_number))?;
+            trace_context.get_or_insert_with(|| da_job.metadata.common.trace_context.clone());
 
             let da_metadata: DaMetadata = da_job.metadata.specific.try_into().map_err(|e| {
                 tracing::error!(job_id = %da_job.internal_id, error = %e, "Invalid metadata type for DA job");
@@ -164,7 +204,13 @@ impl Worker for UpdateStateWorker {
 
         // Create the state transition job
         let new_job_id = blocks_to_process[0].to_string();
-        match create_job(JobType::StateTransition, new_job_id.clone(), metadata, config.clone()).await {
+        // the state transition job continues the trace of the data submission job of its first block
+        let span = tracing::info_span!("create_successor_job", block_id = %new_job_id);
+        continue_trace(&span, &trace_context.unwrap_or_default());
+        match create_job(JobType::StateTransition, new_job_id.clone(), metadata, fence, config.clone())
+            .instrument(span)
+            .await
+        {
             Ok(_) => tracing::info!(block_id = %new_job_id, "Successfully created new state transition job"),
             Err(e) => {
                 tracing::error!(job_id = %new_job_id, error = %e, "Failed to create new state transition job");
@@ -39,6 +39,7 @@ impl ProvingLayer for StarknetLayer {
 pub struct AtlanticClient {
     client: HttpClient,
     proving_layer: Box<dyn ProvingLayer>,
+    proof_url: String,
 }
 
 impl AtlanticClient {
@@ -60,7 +61,13 @@ impl AtlanticClient {
             _ => panic!("Invalid settlement layer: {}", atlantic_params.atlantic_settlement_layer),
         };
 
-        Self { client, proving_layer }
+        Self { client, proving_layer, proof_url: atlantic_params.atlantic_proof_url.clone() }
+    }
+
+    /// Fetches the proofs from `proof_url` instead, `{}` being replaced by the query id
+    pub fn with_proof_url(mut self, proof_url: impl Into<String>) -> Self {
+        self.proof_url = proof_url.into();
+        self
     }
 
     pub async fn add_job(
@@ -109,4 +116,15 @@ impl AtlanticClient {
             Err(AtlanticError::SharpService(response.status()))
         }
     }
+
+    pub async fn get_proof_by_task_id(&self, task_id: &str) -> Result<String, AtlanticError> {
+        let proof_url = self.proof_url.replace("{}", task_id);
+        let response = reqwest::get(&proof_url).await.map_err(AtlanticError::GetProofFailure)?;
+
+        if response.status().is_success() {
+            response.text().await.map_err(AtlanticError::GetProofFailure)
+        } else {
+            Err(AtlanticError::SharpService(response.status()))
+        }
+    }
 }
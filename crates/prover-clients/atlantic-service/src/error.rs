@@ -10,6 +10,9 @@ pub enum AtlanticError {
     #[error("Failed to to get status of a Atlantic job: {0}")]
     GetJobStatusFailure(#[source] reqwest::Error),
 
+    #[error("Failed to get the proof of a Atlantic job: {0}")]
+    GetProofFailure(#[source] reqwest::Error),
+
     #[error("Atlantic service returned an error {0}")]
     SharpService(StatusCode),
 
@@ -25,6 +25,8 @@ pub struct AtlanticValidatedArgs {
     pub atlantic_settlement_layer: String,
     pub atlantic_mock_fact_hash: String,
     pub atlantic_prover_type: String,
+    /// Where the proofs are downloaded from, `{}` being replaced by the query id
+    pub atlantic_proof_url: String,
 }
 
 /// Atlantic is a SHARP wrapper service hosted by Herodotus.
@@ -109,6 +111,13 @@ impl ProverClient for AtlanticProverService {
             }
         }
     }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_proof(&self, task_id: &str) -> Result<String, ProverClientError> {
+        let proof = self.atlantic_client.get_proof_by_task_id(task_id).await?;
+        tracing::debug!(task_id, "Fetched proof from atlantic");
+        Ok(proof)
+    }
 }
 
 impl AtlanticProverService {
@@ -130,7 +139,8 @@ impl AtlanticProverService {
 
     pub fn with_test_params(port: u16, atlantic_params: &AtlanticValidatedArgs) -> Self {
         let atlantic_client =
-            AtlanticClient::new_with_args(format!("http://127.0.0.1:{}", port).parse().unwrap(), atlantic_params);
+            AtlanticClient::new_with_args(format!("http://127.0.0.1:{}", port).parse().unwrap(), atlantic_params)
+                .with_proof_url(format!("http://127.0.0.1:{}/queries/{{}}/proof.json", port));
         let fact_checker = FactChecker::new(
             atlantic_params.atlantic_rpc_node_url.clone(),
             atlantic_params.atlantic_verifier_contract_address.clone(),
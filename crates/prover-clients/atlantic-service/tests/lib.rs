@@ -20,6 +20,7 @@ async fn atlantic_client_submit_task_when_mock_works() {
         atlantic_rpc_node_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_RPC_NODE_URL")).unwrap(),
         atlantic_mock_fact_hash: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_MOCK_FACT_HASH"),
         atlantic_prover_type: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_PROVER_TYPE"),
+        atlantic_proof_url: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_PROOF_URL"),
         atlantic_settlement_layer: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_SETTLEMENT_LAYER"),
         atlantic_verifier_contract_address: get_env_var_or_panic(
             "MADARA_ORCHESTRATOR_ATLANTIC_VERIFIER_CONTRACT_ADDRESS",
@@ -58,6 +59,7 @@ async fn atlantic_client_get_task_status_works() {
         atlantic_rpc_node_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_RPC_NODE_URL")).unwrap(),
         atlantic_mock_fact_hash: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_MOCK_FACT_HASH"),
         atlantic_prover_type: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_PROVER_TYPE"),
+        atlantic_proof_url: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_PROOF_URL"),
         atlantic_settlement_layer: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_SETTLEMENT_LAYER"),
         atlantic_verifier_contract_address: get_env_var_or_panic(
             "MADARA_ORCHESTRATOR_ATLANTIC_VERIFIER_CONTRACT_ADDRESS",
@@ -69,3 +71,34 @@ async fn atlantic_client_get_task_status_works() {
     let task_result = atlantic_service.atlantic_client.get_job_status(atlantic_query_id).await;
     assert!(task_result.is_ok());
 }
+
+#[tokio::test]
+async fn atlantic_client_get_proof_when_mock_works() {
+    let _ = env_logger::try_init();
+    dotenvy::from_filename("../.env.test").expect("Failed to load the .env file");
+    let atlantic_params = AtlanticValidatedArgs {
+        atlantic_api_key: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_API_KEY"),
+        atlantic_service_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_SERVICE_URL")).unwrap(),
+        atlantic_rpc_node_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_RPC_NODE_URL")).unwrap(),
+        atlantic_mock_fact_hash: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_MOCK_FACT_HASH"),
+        atlantic_prover_type: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_PROVER_TYPE"),
+        atlantic_proof_url: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_PROOF_URL"),
+        atlantic_settlement_layer: get_env_var_or_panic("MADARA_ORCHESTRATOR_ATLANTIC_SETTLEMENT_LAYER"),
+        atlantic_verifier_contract_address: get_env_var_or_panic(
+            "MADARA_ORCHESTRATOR_ATLANTIC_VERIFIER_CONTRACT_ADDRESS",
+        ),
+    };
+    let mock_server = MockServer::start();
+
+    let proof_mock = mock_server.mock(|when, then| {
+        when.method("GET").path("/queries/mock_query_id_123/proof.json");
+        then.status(200).header("content-type", "application/json").body(r#"{"proof":["0x1"]}"#);
+    });
+
+    let atlantic_service = AtlanticProverService::with_test_params(mock_server.port(), &atlantic_params);
+
+    let proof = atlantic_service.get_proof("mock_query_id_123").await.expect("Failed to get the proof");
+
+    assert_eq!(proof, r#"{"proof":["0x1"]}"#);
+    proof_mock.assert();
+}
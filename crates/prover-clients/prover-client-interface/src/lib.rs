@@ -22,6 +22,8 @@ pub trait ProverClient: Send + Sync {
         fact: Option<String>,
         cross_verify: bool,
     ) -> Result<TaskStatus, ProverClientError>;
+    /// Fetches the proof generated for a succeeded task, as JSON in the prover's format.
+    async fn get_proof(&self, task_id: &str) -> Result<String, ProverClientError>;
 }
 
 pub enum Task {
@@ -53,4 +55,6 @@ pub enum ProverClientError {
     FailedToCreateTempFile(String),
     #[error("Failed to write file: {0}")]
     FailedToWriteFile(String),
+    #[error("Proof download is not supported: {0}")]
+    ProofDownloadNotSupported(String),
 }
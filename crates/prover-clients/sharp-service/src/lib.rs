@@ -152,6 +152,11 @@ impl ProverClient for SharpProverService {
             },
         }
     }
+
+    /// SHARP registers the facts of the proofs it generates itself and doesn't expose the proofs.
+    async fn get_proof(&self, _task_id: &str) -> Result<String, ProverClientError> {
+        Err(ProverClientError::ProofDownloadNotSupported("SHARP does not expose the generated proofs".to_string()))
+    }
 }
 
 impl SharpProverService {
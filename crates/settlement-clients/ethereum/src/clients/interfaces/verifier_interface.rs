@@ -0,0 +1,31 @@
+use alloy::sol;
+use alloy::transports::{RpcError, TransportErrorKind};
+
+// TODO: should be moved to Zaun:
+// https://github.com/keep-starknet-strange/zaun
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface GpsStatementVerifier {
+        function verifyProofAndRegister(uint256[] calldata proofParams, uint256[] calldata proof, uint256[] calldata taskMetadata, uint256[] calldata cairoAuxInput, uint256 cairoVerifierId) external;
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface MemoryPageFactRegistry {
+        function registerContinuousMemoryPage(uint256 startAddr, uint256[] calldata values, uint256 z, uint256 alpha, uint256 prime) external returns (bytes32 factHash, uint256 memoryHash, uint256 prod);
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GpsVerifierContractError {
+    #[error("RPC error: {0}")]
+    RpcError(#[from] RpcError<TransportErrorKind>),
+    #[error("Contract call failed: {0}")]
+    ContractError(#[from] alloy::contract::Error),
+    #[error("Transaction {0} reverted")]
+    TransactionReverted(String),
+}
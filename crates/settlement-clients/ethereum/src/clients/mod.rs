@@ -1,4 +1,6 @@
 pub mod interfaces;
 pub mod validity;
+pub mod verifier;
 
 pub use validity::StarknetValidityContractClient;
+pub use verifier::GpsVerifierClient;
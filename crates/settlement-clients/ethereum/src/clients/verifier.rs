@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use alloy::network::Ethereum;
+use alloy::primitives::{Address, TxHash};
+use alloy::rpc::types::eth::TransactionReceipt;
+use alloy::transports::http::Http;
+
+use crate::clients::interfaces::verifier_interface::{
+    GpsStatementVerifier, GpsVerifierContractError, MemoryPageFactRegistry,
+};
+use crate::types::{ContinuousMemoryPage, GpsProof, LocalWalletSignerMiddleware};
+
+// TODO: should be moved to Zaun:
+// https://github.com/keep-starknet-strange/zaun
+
+/// Client to register proofs through the GPS statement verifier and its memory page fact registry
+pub struct GpsVerifierClient {
+    verifier: GpsStatementVerifier::GpsStatementVerifierInstance<
+        Http<reqwest::Client>,
+        Arc<LocalWalletSignerMiddleware>,
+        Ethereum,
+    >,
+    memory_page_fact_registry: MemoryPageFactRegistry::MemoryPageFactRegistryInstance<
+        Http<reqwest::Client>,
+        Arc<LocalWalletSignerMiddleware>,
+        Ethereum,
+    >,
+}
+
+impl GpsVerifierClient {
+    pub fn new(
+        verifier_address: Address,
+        memory_page_fact_registry_address: Address,
+        client: Arc<LocalWalletSignerMiddleware>,
+    ) -> Self {
+        Self {
+            verifier: GpsStatementVerifier::new(verifier_address, client.clone()),
+            memory_page_fact_registry: MemoryPageFactRegistry::new(memory_page_fact_registry_address, client),
+        }
+    }
+
+    pub fn verifier_address(&self) -> Address {
+        *self.verifier.address()
+    }
+
+    /// Registers a continuous memory page, required by the verifier to check the proof's public
+    /// memory
+    pub async fn register_continuous_memory_page(
+        &self,
+        page: &ContinuousMemoryPage,
+    ) -> Result<TransactionReceipt, GpsVerifierContractError> {
+        let receipt = self
+            .memory_page_fact_registry
+            .registerContinuousMemoryPage(page.start_address, page.values.clone(), page.z, page.alpha, page.prime)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        ensure_success(receipt)
+    }
+
+    /// Sends the transaction verifying the proof on chain and registering its fact in the
+    /// verifier's fact registry, without waiting for it to be included
+    pub async fn verify_proof_and_register(&self, proof: &GpsProof) -> Result<TxHash, GpsVerifierContractError> {
+        let pending_transaction = self
+            .verifier
+            .verifyProofAndRegister(
+                proof.proof_params.clone(),
+                proof.proof.clone(),
+                proof.task_metadata.clone(),
+                proof.cairo_aux_input.clone(),
+                proof.cairo_verifier_id,
+            )
+            .send()
+            .await?;
+        Ok(*pending_transaction.tx_hash())
+    }
+}
+
+fn ensure_success(receipt: TransactionReceipt) -> Result<TransactionReceipt, GpsVerifierContractError> {
+    if receipt.status() {
+        Ok(receipt)
+    } else {
+        Err(GpsVerifierContractError::TransactionReverted(format!("0x{:x}", receipt.transaction_hash)))
+    }
+}
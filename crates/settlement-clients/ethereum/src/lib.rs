@@ -28,7 +28,7 @@ use url::Url;
 use utils::env_utils::get_env_var_or_panic;
 
 use crate::clients::interfaces::validity_interface::StarknetValidityContractTrait;
-use crate::clients::StarknetValidityContractClient;
+use crate::clients::{GpsVerifierClient, StarknetValidityContractClient};
 use crate::conversion::{slice_u8_to_u256, vec_u8_32_to_vec_u256};
 pub mod clients;
 pub mod conversion;
@@ -42,7 +42,7 @@ use mockall::automock;
 use reqwest::Client;
 use tokio::time::sleep;
 
-use crate::types::{bytes_be_to_u128, convert_stark_bigint_to_u256};
+use crate::types::{bytes_be_to_u128, convert_stark_bigint_to_u256, GpsProof};
 
 pub const ENV_PRIVATE_KEY: &str = "MADARA_ORCHESTRATOR_ETHEREUM_PRIVATE_KEY";
 const X_0_POINT_OFFSET: usize = 10;
@@ -71,11 +71,16 @@ pub struct EthereumSettlementValidatedArgs {
     pub l1_core_contract_address: Address,
 
     pub starknet_operator_address: Address,
+
+    pub gps_verifier_contract_address: Option<Address>,
+
+    pub memory_page_fact_registry_address: Option<Address>,
 }
 
 #[allow(dead_code)]
 pub struct EthereumSettlementClient {
     core_contract_client: StarknetValidityContractClient,
+    gps_verifier_client: Option<GpsVerifierClient>,
     wallet: EthereumWallet,
     wallet_address: Address,
     provider: Arc<RootProvider<Http<Client>>>,
@@ -100,10 +105,35 @@ impl EthereumSettlementClient {
                 .on_http(settlement_cfg.ethereum_rpc_url.clone()),
         );
 
+        let gps_verifier_client =
+            match (settlement_cfg.gps_verifier_contract_address, settlement_cfg.memory_page_fact_registry_address) {
+                (Some(verifier_address), Some(memory_page_fact_registry_address)) => Some(GpsVerifierClient::new(
+                    verifier_address,
+                    memory_page_fact_registry_address,
+                    filler_provider.clone(),
+                )),
+                _ => None,
+            };
+
         let core_contract_client =
             StarknetValidityContractClient::new(settlement_cfg.l1_core_contract_address, filler_provider);
 
-        EthereumSettlementClient { provider, core_contract_client, wallet, wallet_address, impersonate_account: None }
+        EthereumSettlementClient {
+            provider,
+            core_contract_client,
+            gps_verifier_client,
+            wallet,
+            wallet_address,
+            impersonate_account: None,
+        }
+    }
+
+    /// Sets the GPS verifier and memory page fact registry used to register proofs
+    pub fn with_gps_verifier(mut self, verifier_address: Address, memory_page_fact_registry_address: Address) -> Self {
+        let client = self.core_contract_client.as_ref().provider().clone();
+        self.gps_verifier_client =
+            Some(GpsVerifierClient::new(verifier_address, memory_page_fact_registry_address, client));
+        self
     }
 
     #[cfg(feature = "testing")]
@@ -126,6 +156,7 @@ impl EthereumSettlementClient {
         EthereumSettlementClient {
             provider: Arc::new(provider),
             core_contract_client,
+            gps_verifier_client: None,
             wallet,
             wallet_address,
             impersonate_account,
@@ -177,9 +208,55 @@ impl EthereumSettlementClient {
 impl SettlementClient for EthereumSettlementClient {
     /// Should register the proof on the base layer and return an external id
     /// which can be used to track the status.
-    #[allow(unused)]
-    async fn register_proof(&self, proof: [u8; 32]) -> Result<String> {
-        todo!("register_proof is not implemented yet")
+    /// `proof` is a JSON serialized [`GpsProof`]: its memory pages are registered first, then the
+    /// proof is verified by the GPS verifier which registers the fact.
+    async fn register_proof(&self, proof: Vec<u8>) -> Result<String> {
+        tracing::info!(
+            log_type = "starting",
+            category = "register_proof",
+            function_type = "gps",
+            "Registering proof on the GPS verifier."
+        );
+        let Some(gps_verifier_client) = &self.gps_verifier_client else {
+            bail!("GPS verifier contracts are not configured, cannot register proof");
+        };
+        let proof: GpsProof = serde_json::from_slice(&proof).wrap_err("Failed to deserialize GPS proof")?;
+
+        for (index, page) in proof.memory_pages.iter().enumerate() {
+            let tx_receipt = gps_verifier_client
+                .register_continuous_memory_page(page)
+                .await
+                .wrap_err(format!("Failed to register memory page #{}", index))?;
+            tracing::debug!(
+                category = "register_proof",
+                page = index,
+                tx_hash = %tx_receipt.transaction_hash,
+                "Memory page registered."
+            );
+        }
+
+        let tx_hash = gps_verifier_client.verify_proof_and_register(&proof).await?;
+        let tx_hash = format!("0x{:x}", tx_hash);
+
+        // the fact is only registered once the transaction is final, which the verification of
+        // the proof registration job waits for
+        tracing::info!(
+            log_type = "completed",
+            category = "register_proof",
+            function_type = "gps",
+            tx_hash = %tx_hash,
+            verifier = %gps_verifier_client.verifier_address(),
+            "Proof registration transaction sent to the GPS verifier."
+        );
+        Ok(tx_hash)
+    }
+
+    /// The prover is expected to split its proof for the GPS verifier (e.g. through the
+    /// stark-evm-adapter). The proof is parsed into a [`GpsProof`] so a malformed proof is
+    /// rejected when downloaded rather than when registered.
+    fn convert_proof(&self, proof: &str) -> Result<Vec<u8>> {
+        let proof: GpsProof = serde_json::from_str(proof).wrap_err("Failed to deserialize GPS proof")?;
+        Ok(serde_json::to_vec(&proof)?)
     }
 
     /// Should be used to update state on core contract when DA is done in calldata
@@ -58,6 +58,26 @@ sol! {
     }
 }
 
+// Accepts any call, used in place of the GPS verifier and its memory page fact registry
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc, bytecode="6001600c60003960016000f300")]
+    contract DummyGpsVerifier {
+        function verifyProofAndRegister(uint256[] calldata proofParams, uint256[] calldata proof, uint256[] calldata taskMetadata, uint256[] calldata cairoAuxInput, uint256 cairoVerifierId) external {
+        }
+    }
+}
+
+// Reverts on any call, used to simulate a proof rejected by the GPS verifier
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc, bytecode="6005600c60003960056000f360006000fd")]
+    contract RejectingGpsVerifier {
+        function verifyProofAndRegister(uint256[] calldata proofParams, uint256[] calldata proof, uint256[] calldata taskMetadata, uint256[] calldata cairoAuxInput, uint256 cairoVerifierId) external {
+        }
+    }
+}
+
 pub struct EthereumTestBuilder {
     fork_block: Option<u64>,
     impersonator: Option<Address>,
@@ -139,10 +159,10 @@ mod settlement_client_tests {
     use super::{BLOCK_TIME, ENV_FILE_PATH};
     use crate::conversion::to_padded_hex;
     use crate::tests::{
-        DummyCoreContract, EthereumTestBuilder, Pipe, CURRENT_PATH, MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS,
-        STARKNET_CORE_CONTRACT, STARKNET_CORE_CONTRACT_ADDRESS,
+        DummyCoreContract, DummyGpsVerifier, EthereumTestBuilder, Pipe, RejectingGpsVerifier, CURRENT_PATH,
+        MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS, STARKNET_CORE_CONTRACT, STARKNET_CORE_CONTRACT_ADDRESS,
     };
-    use crate::types::{bytes_be_to_u128, convert_stark_bigint_to_u256};
+    use crate::types::{bytes_be_to_u128, convert_stark_bigint_to_u256, ContinuousMemoryPage, GpsProof};
     use crate::{EthereumSettlementClient, EthereumSettlementValidatedArgs, Y_HIGH_POINT_OFFSET, Y_LOW_POINT_OFFSET};
 
     #[rstest]
@@ -169,6 +189,8 @@ mod settlement_client_tests {
                 "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS",
             ))
             .expect("Invalid Starknet operator address"),
+            gps_verifier_contract_address: None,
+            memory_page_fact_registry_address: None,
         };
 
         // Deploying a dummy contract
@@ -247,6 +269,8 @@ mod settlement_client_tests {
                 "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS",
             ))
             .expect("Invalid Starknet operator address"),
+            gps_verifier_contract_address: None,
+            memory_page_fact_registry_address: None,
         };
 
         let ethereum_settlement_client = EthereumSettlementClient::with_test_params(
@@ -301,6 +325,90 @@ mod settlement_client_tests {
         assert_eq!(expected_latest_block_number, latest_block_number._0.as_u32());
     }
 
+    #[rstest]
+    #[tokio::test]
+    /// Tests that the memory pages and the proof are sent to the configured GPS verifier contracts.
+    /// Dummy contracts accepting any call are used so the test doesn't depend on a real proof.
+    async fn register_proof_with_dummy_verifier_works() {
+        dotenvy::from_filename(&*ENV_FILE_PATH).expect("Could not load .env.test file.");
+
+        let setup = EthereumTestBuilder::new().build().await;
+
+        let verifier = DummyGpsVerifier::deploy(&setup.provider).await.expect("Unable to deploy verifier");
+        let memory_page_fact_registry =
+            DummyGpsVerifier::deploy(&setup.provider).await.expect("Unable to deploy memory page fact registry");
+
+        let ethereum_settlement_client = EthereumSettlementClient::with_test_params(
+            setup.provider.clone(),
+            *STARKNET_CORE_CONTRACT_ADDRESS,
+            setup.rpc_url.clone(),
+            None,
+        )
+        .with_gps_verifier(*verifier.address(), *memory_page_fact_registry.address());
+
+        let register_proof_result = ethereum_settlement_client
+            .register_proof(serde_json::to_vec(&dummy_gps_proof()).unwrap())
+            .await
+            .expect("Could not go through register_proof.");
+
+        let txn = setup
+            .provider
+            .get_transaction_by_hash(FixedBytes::from_str(register_proof_result.as_str()).expect("Unable to convert txn"))
+            .await
+            .expect("did not get txn from hash")
+            .unwrap();
+        assert_eq!(txn.to.unwrap(), *verifier.address());
+
+        let verified_inclusion = ethereum_settlement_client
+            .verify_tx_inclusion(register_proof_result.as_str())
+            .await
+            .expect("Could not verify inclusion.");
+        assert_eq!(verified_inclusion, SettlementVerificationStatus::Verified);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn register_proof_rejected_by_verifier_fails() {
+        dotenvy::from_filename(&*ENV_FILE_PATH).expect("Could not load .env.test file.");
+
+        let setup = EthereumTestBuilder::new().build().await;
+
+        let verifier = RejectingGpsVerifier::deploy(&setup.provider).await.expect("Unable to deploy verifier");
+        let memory_page_fact_registry =
+            DummyGpsVerifier::deploy(&setup.provider).await.expect("Unable to deploy memory page fact registry");
+
+        let ethereum_settlement_client = EthereumSettlementClient::with_test_params(
+            setup.provider.clone(),
+            *STARKNET_CORE_CONTRACT_ADDRESS,
+            setup.rpc_url.clone(),
+            None,
+        )
+        .with_gps_verifier(*verifier.address(), *memory_page_fact_registry.address());
+
+        assert!(
+            ethereum_settlement_client.register_proof(serde_json::to_vec(&dummy_gps_proof()).unwrap()).await.is_err()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn register_proof_without_verifier_fails() {
+        dotenvy::from_filename(&*ENV_FILE_PATH).expect("Could not load .env.test file.");
+
+        let setup = EthereumTestBuilder::new().build().await;
+
+        let ethereum_settlement_client = EthereumSettlementClient::with_test_params(
+            setup.provider.clone(),
+            *STARKNET_CORE_CONTRACT_ADDRESS,
+            setup.rpc_url.clone(),
+            None,
+        );
+
+        assert!(
+            ethereum_settlement_client.register_proof(serde_json::to_vec(&dummy_gps_proof()).unwrap()).await.is_err()
+        );
+    }
+
     #[rstest]
     #[tokio::test]
     #[case::typical(6806847)]
@@ -319,6 +427,8 @@ mod settlement_client_tests {
                 "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS",
             ))
             .expect("Invalid Starknet operator address"),
+            gps_verifier_contract_address: None,
+            memory_page_fact_registry_address: None,
         };
 
         let ethereum_settlement_client = EthereumSettlementClient::with_test_params(
@@ -364,6 +474,23 @@ mod settlement_client_tests {
 
     // UTILITY FUNCTIONS NEEDED
 
+    fn dummy_gps_proof() -> GpsProof {
+        GpsProof {
+            proof_params: vec![U256::from(1), U256::from(2)],
+            proof: vec![U256::from(3), U256::from(4), U256::from(5)],
+            task_metadata: vec![U256::from(1)],
+            cairo_aux_input: vec![U256::from(6)],
+            cairo_verifier_id: U256::from(6),
+            memory_pages: vec![ContinuousMemoryPage {
+                start_address: U256::from(0),
+                values: vec![U256::from(7), U256::from(8)],
+                z: U256::from(9),
+                alpha: U256::from(10),
+                prime: U256::from(11),
+            }],
+        }
+    }
+
     fn get_program_output(block_no: u64) -> Vec<[u8; 32]> {
         // Program Output
         let program_output_file_path =
@@ -3,6 +3,7 @@ use alloy::providers::fillers::{ChainIdFiller, FillProvider, GasFiller, JoinFill
 use alloy::providers::{Identity, RootProvider};
 use alloy::transports::http::{Client, Http};
 use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
 
 pub type LocalWalletSignerMiddleware = FillProvider<
     JoinFill<
@@ -24,6 +25,29 @@ pub type EthHttpProvider = FillProvider<
     Ethereum,
 >;
 
+/// A continuous memory page of the Cairo program public memory, registered in the memory page
+/// fact registry before the proof can be verified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContinuousMemoryPage {
+    pub start_address: U256,
+    pub values: Vec<U256>,
+    pub z: U256,
+    pub alpha: U256,
+    pub prime: U256,
+}
+
+/// Proof in the format expected by the GPS statement verifier, along with the memory pages it
+/// depends on. This is what `register_proof` expects, serialized as JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpsProof {
+    pub proof_params: Vec<U256>,
+    pub proof: Vec<U256>,
+    pub task_metadata: Vec<U256>,
+    pub cairo_aux_input: Vec<U256>,
+    pub cairo_verifier_id: U256,
+    pub memory_pages: Vec<ContinuousMemoryPage>,
+}
+
 pub fn convert_stark_bigint_to_u256(y_low: u128, y_high: u128) -> U256 {
     let y_high_u256 = U256::from(y_high);
     let y_low_u256 = U256::from(y_low);
@@ -18,7 +18,12 @@ pub enum SettlementVerificationStatus {
 pub trait SettlementClient: Send + Sync {
     /// Should register the proof on the base layer and return an external id
     /// which can be used to track the status.
-    async fn register_proof(&self, proof: [u8; 32]) -> Result<String>;
+    /// The format of the serialized `proof` depends on the verifier of the settlement layer.
+    async fn register_proof(&self, proof: Vec<u8>) -> Result<String>;
+
+    /// Should convert a proof, as downloaded from the prover, into the format `register_proof`
+    /// expects.
+    fn convert_proof(&self, proof: &str) -> Result<Vec<u8>>;
 
     /// Should be used to update state on core contract when DA is done in calldata
     async fn update_state_calldata(
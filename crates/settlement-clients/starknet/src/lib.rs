@@ -15,8 +15,8 @@ use lazy_static::lazy_static;
 use mockall::automock;
 use mockall::predicate::*;
 use settlement_client_interface::{SettlementClient, SettlementVerificationStatus};
-use starknet::accounts::{ConnectedAccount, ExecutionEncoding, SingleOwnerAccount};
-use starknet::core::types::{BlockId, BlockTag, Felt, FunctionCall, TransactionExecutionStatus};
+use starknet::accounts::{Account, ConnectedAccount, ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::{BlockId, BlockTag, Call, Felt, FunctionCall, TransactionExecutionStatus};
 use starknet::core::utils::get_selector_from_name;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, Provider};
@@ -31,6 +31,7 @@ pub struct StarknetSettlementClient {
     pub account: LocalWalletSignerMiddleware,
     pub starknet_core_contract_client: StarknetCoreContractClient,
     pub core_contract_address: Felt,
+    pub fact_registry_address: Option<Felt>,
     pub tx_finality_retry_delay_in_seconds: u64,
 }
 
@@ -47,10 +48,13 @@ pub struct StarknetSettlementValidatedArgs {
     pub starknet_account_address: String,
     pub starknet_cairo_core_contract_address: String,
     pub starknet_finality_retry_wait_in_secs: u64,
+    pub starknet_fact_registry_address: Option<String>,
 }
 
 // Assumed the contract called for settlement looks like:
 // https://github.com/keep-starknet-strange/piltover
+// and the fact registry used to register proofs looks like:
+// https://github.com/HerodotusDev/integrity
 
 impl StarknetSettlementClient {
     pub async fn new_with_args(settlement_cfg: &StarknetSettlementValidatedArgs) -> Self {
@@ -68,6 +72,12 @@ impl StarknetSettlementClient {
         let core_contract_address = Felt::from_hex(&settlement_cfg.starknet_cairo_core_contract_address.to_string())
             .expect("Invalid core contract address");
 
+        let fact_registry_address = settlement_cfg
+            .starknet_fact_registry_address
+            .as_ref()
+            .filter(|address| !address.is_empty())
+            .map(|address| Felt::from_hex(address).expect("Invalid fact registry address"));
+
         let account: Arc<SingleOwnerAccount<Arc<JsonRpcClient<HttpTransport>>, LocalWallet>> =
             Arc::new(SingleOwnerAccount::new(
                 provider.clone(),
@@ -83,6 +93,7 @@ impl StarknetSettlementClient {
         StarknetSettlementClient {
             account,
             core_contract_address,
+            fact_registry_address,
             starknet_core_contract_client,
             tx_finality_retry_delay_in_seconds: settlement_cfg.starknet_finality_retry_wait_in_secs,
         }
@@ -97,6 +108,9 @@ lazy_static! {
     // It should get added to match the solidity implementation of the core contract.
     pub static ref CONTRACT_READ_STATE_BLOCK_NUMBER: Felt =
         get_selector_from_name("stateBlockNumber").expect("Invalid update state selector");
+    pub static ref CONTRACT_WRITE_VERIFY_PROOF_AND_REGISTER_SELECTOR: Felt =
+        get_selector_from_name("verify_proof_full_and_register_fact")
+            .expect("Invalid verify proof and register selector");
 }
 
 // TODO: Note that we already have an implementation of the appchain core contract client available
@@ -109,11 +123,48 @@ lazy_static! {
 #[automock]
 #[async_trait]
 impl SettlementClient for StarknetSettlementClient {
-    /// Should register the proof on the base layer and return an external id
-    /// which can be used to track the status.
-    #[allow(unused)]
-    async fn register_proof(&self, proof: [u8; 32]) -> Result<String> {
-        !unimplemented!("register_proof not implemented yet")
+    /// Registers the proof on the fact registry and returns the hash of the transaction.
+    /// The proof is expected to be the calldata of the registry's verification entrypoint,
+    /// serialized as a JSON array of hex felts.
+    async fn register_proof(&self, proof: Vec<u8>) -> Result<String> {
+        tracing::info!(
+            log_type = "starting",
+            category = "register_proof",
+            function_type = "fact_registry",
+            "Registering proof on the fact registry."
+        );
+        let fact_registry_address = self
+            .fact_registry_address
+            .ok_or_else(|| eyre!("Fact registry address is not configured, cannot register proof"))?;
+        let calldata: Vec<Felt> =
+            serde_json::from_slice(&proof).wrap_err("Failed to deserialize proof as a list of felts")?;
+
+        let invoke_result = self
+            .account
+            .execute_v1(vec![Call {
+                to: fact_registry_address,
+                selector: *CONTRACT_WRITE_VERIFY_PROOF_AND_REGISTER_SELECTOR,
+                calldata,
+            }])
+            .send()
+            .await
+            .wrap_err("Failed to send proof registration transaction")?;
+        tracing::info!(
+            log_type = "completed",
+            category = "register_proof",
+            function_type = "fact_registry",
+            tx_hash = %invoke_result.transaction_hash.to_hex_string(),
+            "Proof registration transaction sent."
+        );
+        Ok(invoke_result.transaction_hash.to_hex_string())
+    }
+
+    /// The fact registry takes the proof serialized as a list of felts, which is parsed so a
+    /// malformed proof is rejected when downloaded rather than when registered.
+    fn convert_proof(&self, proof: &str) -> Result<Vec<u8>> {
+        let calldata: Vec<Felt> =
+            serde_json::from_str(proof).wrap_err("Failed to deserialize proof as a list of felts")?;
+        Ok(serde_json::to_vec(&calldata)?)
     }
 
     /// Should be used to update state on core contract when DA is done in calldata
@@ -197,15 +248,22 @@ impl SettlementClient for StarknetSettlementClient {
         }
     }
 
-    /// Should be used to update state on core contract and publishing the blob simultaneously
-    #[allow(unused)]
+    /// Starknet has no blob space, so the state can't be updated with blobs.
+    /// An error is returned so that the job fails instead of aborting the orchestrator,
+    /// calldata DA should be used when settling on Starknet.
     async fn update_state_with_blobs(
         &self,
-        program_output: Vec<[u8; 32]>,
-        state_diff: Vec<Vec<u8>>,
-        nonce: u64,
+        _program_output: Vec<[u8; 32]>,
+        _state_diff: Vec<Vec<u8>>,
+        _nonce: u64,
     ) -> Result<String> {
-        !unimplemented!("not implemented yet.")
+        tracing::error!(
+            log_type = "failed",
+            category = "update_state",
+            function_type = "blobs",
+            "Blob DA is not supported when settling on Starknet."
+        );
+        Err(eyre!("Updating state with blobs is not supported on Starknet, use calldata DA instead"))
     }
 
     /// Wait for a pending tx to achieve finality
@@ -256,6 +314,6 @@ impl SettlementClient for StarknetSettlementClient {
     /// Returns the nonce for the wallet in use.
     async fn get_nonce(&self) -> Result<u64> {
         let nonce = self.account.get_nonce().await?;
-        Ok(u64_from_felt(nonce).expect("Failed to convert to u64"))
+        u64_from_felt(nonce).wrap_err("Failed to convert nonce from Felt to u64")
     }
 }
@@ -34,3 +34,32 @@ mod Piltover {
         }
     }
 }
+
+#[starknet::interface]
+pub trait IFactRegistry<TContractState> {
+    fn verify_proof_full_and_register_fact(ref self: TContractState, proof: Span<felt252>);
+
+    fn is_fact_registered(self: @TContractState, fact: felt252) -> bool;
+}
+
+#[starknet::contract]
+mod FactRegistry {
+    use starknet::storage::Map;
+    #[storage]
+    struct Storage {
+        registered_facts: Map<felt252, bool>,
+    }
+
+    #[abi(embed_v0)]
+    impl IFactRegistryImpl of super::IFactRegistry<ContractState> {
+        // Does not verify anything, the first element of the proof is registered as the fact
+        fn verify_proof_full_and_register_fact(ref self: ContractState, proof: Span<felt252>) {
+            assert(proof.len() > 0, 'Empty proof');
+            self.registered_facts.write(*proof.at(0), true);
+        }
+
+        fn is_fact_registered(self: @ContractState, fact: felt252) -> bool {
+            self.registered_facts.read(fact)
+        }
+    }
+}
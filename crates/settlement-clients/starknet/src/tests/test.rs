@@ -91,6 +91,7 @@ async fn setup(#[future] spin_up_madara: MadaraCmd) -> (LocalWalletSignerMiddlew
         )
         .parse::<u64>()
         .unwrap(),
+        starknet_fact_registry_address: None,
     };
 
     let rpc_url = Url::parse(starknet_settlement_params.starknet_rpc_url.as_ref()).unwrap();
@@ -110,38 +111,21 @@ async fn setup(#[future] spin_up_madara: MadaraCmd) -> (LocalWalletSignerMiddlew
     (Arc::new(account), madara_process)
 }
 
-#[rstest]
-#[tokio::test]
-async fn test_settle(#[future] setup: (LocalWalletSignerMiddleware, MadaraCmd)) {
-    dotenvy::from_filename_override(".env.test").expect("Failed to load the .env file");
-
-    let (account, madara_process) = setup.await;
-
-    let mut starknet_settlement_params: StarknetSettlementValidatedArgs = StarknetSettlementValidatedArgs {
-        starknet_rpc_url: madara_process.rpc_url.clone(),
-        starknet_private_key: get_env_var_or_panic("MADARA_ORCHESTRATOR_STARKNET_PRIVATE_KEY"),
-        starknet_account_address: get_env_var_or_panic("MADARA_ORCHESTRATOR_STARKNET_ACCOUNT_ADDRESS"),
-        starknet_cairo_core_contract_address: get_env_var_or_panic(
-            "MADARA_ORCHESTRATOR_STARKNET_CAIRO_CORE_CONTRACT_ADDRESS",
-        ),
-        starknet_finality_retry_wait_in_secs: get_env_var_or_panic(
-            "MADARA_ORCHESTRATOR_STARKNET_FINALITY_RETRY_WAIT_IN_SECS",
-        )
-        .parse::<u64>()
-        .unwrap(),
-    };
-
+/// Declares and deploys the mock contract `contract_name`, returning its address
+async fn declare_and_deploy(account: &LocalWalletSignerMiddleware, contract_name: &str) -> Felt {
     let project_root = Path::new(env!("CARGO_MANIFEST_DIR")).ancestors().nth(3).unwrap();
     let contract_path = project_root.join("crates/settlement-clients/starknet/src/tests/mock_contracts/target/dev");
     let sierra_class: SierraClass = serde_json::from_reader(
-        std::fs::File::open(contract_path.join("mock_contracts_Piltover.contract_class.json"))
+        std::fs::File::open(contract_path.join(format!("mock_contracts_{}.contract_class.json", contract_name)))
             .expect("Could not open sierra class file"),
     )
     .expect("Failed to parse SierraClass");
 
     let compiled_class: CompiledClass = serde_json::from_reader(
-        std::fs::File::open(contract_path.join("mock_contracts_Piltover.compiled_contract_class.json"))
-            .expect("Could not open compiled class file"),
+        std::fs::File::open(
+            contract_path.join(format!("mock_contracts_{}.compiled_contract_class.json", contract_name)),
+        )
+        .expect("Could not open compiled class file"),
     )
     .expect("Failed to parse CompiledClass");
 
@@ -152,22 +136,47 @@ async fn test_settle(#[future] setup: (LocalWalletSignerMiddleware, MadaraCmd))
         account.declare_v2(Arc::new(flattened_class.clone()), compiled_class_hash).send().await.unwrap();
     tracing::debug!("declare tx hash {:?}", declare_tx_hash);
 
-    let is_success = wait_for_tx(&account, declare_tx_hash, Duration::from_secs(2)).await;
+    let is_success = wait_for_tx(account, declare_tx_hash, Duration::from_secs(2)).await;
     assert!(is_success, "Declare transaction failed");
 
     let contract_factory = ContractFactory::new(flattened_class.class_hash(), account.clone());
     let deploy_v1 = contract_factory.deploy_v1(vec![], felt!("1122"), false);
     let deployed_address = deploy_v1.deployed_address();
 
-    // env::set_var("STARKNET_CAIRO_CORE_CONTRACT_ADDRESS", deployed_address.to_hex_string());
-    starknet_settlement_params.starknet_cairo_core_contract_address = deployed_address.to_hex_string();
-
     let InvokeTransactionResult { transaction_hash: deploy_tx_hash } =
         deploy_v1.send().await.expect("Unable to deploy contract");
 
-    let is_success = wait_for_tx(&account, deploy_tx_hash, Duration::from_secs(2)).await;
+    let is_success = wait_for_tx(account, deploy_tx_hash, Duration::from_secs(2)).await;
     assert!(is_success, "Deploy trasaction failed");
 
+    deployed_address
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_settle(#[future] setup: (LocalWalletSignerMiddleware, MadaraCmd)) {
+    dotenvy::from_filename_override(".env.test").expect("Failed to load the .env file");
+
+    let (account, madara_process) = setup.await;
+
+    let mut starknet_settlement_params: StarknetSettlementValidatedArgs = StarknetSettlementValidatedArgs {
+        starknet_rpc_url: madara_process.rpc_url.clone(),
+        starknet_private_key: get_env_var_or_panic("MADARA_ORCHESTRATOR_STARKNET_PRIVATE_KEY"),
+        starknet_account_address: get_env_var_or_panic("MADARA_ORCHESTRATOR_STARKNET_ACCOUNT_ADDRESS"),
+        starknet_cairo_core_contract_address: get_env_var_or_panic(
+            "MADARA_ORCHESTRATOR_STARKNET_CAIRO_CORE_CONTRACT_ADDRESS",
+        ),
+        starknet_finality_retry_wait_in_secs: get_env_var_or_panic(
+            "MADARA_ORCHESTRATOR_STARKNET_FINALITY_RETRY_WAIT_IN_SECS",
+        )
+        .parse::<u64>()
+        .unwrap(),
+        starknet_fact_registry_address: None,
+    };
+
+    let deployed_address = declare_and_deploy(&account, "Piltover").await;
+    starknet_settlement_params.starknet_cairo_core_contract_address = deployed_address.to_hex_string();
+
     let settlement_client = StarknetSettlementClient::new_with_args(&starknet_settlement_params).await;
     let onchain_data_hash = [1; 32];
     let mut program_output = Vec::with_capacity(32);
@@ -213,3 +222,67 @@ async fn test_get_nonce_works(#[future] setup: (LocalWalletSignerMiddleware, Mad
     }
     assert!(nonce.is_ok(), "Failed to get nonce");
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_register_proof(#[future] setup: (LocalWalletSignerMiddleware, MadaraCmd)) {
+    let (account, madara_process) = setup.await;
+
+    let fact_registry_address = declare_and_deploy(&account, "FactRegistry").await;
+    let starknet_settlement_params = StarknetSettlementValidatedArgs {
+        starknet_rpc_url: madara_process.rpc_url.clone(),
+        starknet_private_key: get_env_var_or_panic("MADARA_ORCHESTRATOR_STARKNET_PRIVATE_KEY"),
+        starknet_account_address: get_env_var_or_panic("MADARA_ORCHESTRATOR_STARKNET_ACCOUNT_ADDRESS"),
+        starknet_cairo_core_contract_address: fact_registry_address.to_hex_string(),
+        starknet_finality_retry_wait_in_secs: 0,
+        starknet_fact_registry_address: Some(fact_registry_address.to_hex_string()),
+    };
+    let settlement_client = StarknetSettlementClient::new_with_args(&starknet_settlement_params).await;
+
+    // The mock registry registers the first element of the proof as the fact
+    let fact = felt!("0x1234");
+    let proof = serde_json::to_vec(&vec![felt!("3"), fact, felt!("0x1"), felt!("0x2")]).unwrap();
+    let register_proof_tx_hash = settlement_client.register_proof(proof).await.expect("Registering proof");
+
+    let is_success = wait_for_tx(
+        &account,
+        Felt::from_hex(&register_proof_tx_hash).expect("Incorrect transaction hash"),
+        Duration::from_secs(2),
+    )
+    .await;
+    assert!(is_success, "Register proof transaction failed/reverted");
+
+    let call_result = account
+        .provider()
+        .call(
+            FunctionCall {
+                contract_address: fact_registry_address,
+                entry_point_selector: selector!("is_fact_registered"),
+                calldata: vec![fact],
+            },
+            BlockId::Tag(BlockTag::Latest),
+        )
+        .await
+        .expect("failed to call the contract");
+    assert!(call_result[0] == true.into(), "Fact should be registered");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_update_state_with_blobs_fails(#[future] setup: (LocalWalletSignerMiddleware, MadaraCmd)) {
+    let (_account, madara_process) = setup.await;
+
+    let starknet_settlement_params = StarknetSettlementValidatedArgs {
+        starknet_rpc_url: madara_process.rpc_url.clone(),
+        starknet_private_key: get_env_var_or_panic("MADARA_ORCHESTRATOR_STARKNET_PRIVATE_KEY"),
+        starknet_account_address: get_env_var_or_panic("MADARA_ORCHESTRATOR_STARKNET_ACCOUNT_ADDRESS"),
+        starknet_cairo_core_contract_address: "0x1".to_string(),
+        starknet_finality_retry_wait_in_secs: 0,
+        starknet_fact_registry_address: None,
+    };
+    let settlement_client = StarknetSettlementClient::new_with_args(&starknet_settlement_params).await;
+
+    assert!(settlement_client.update_state_with_blobs(vec![[1; 32]], vec![vec![1; 32]], 0).await.is_err());
+    // Without a fact registry, proofs can't be registered
+    assert!(settlement_client.register_proof(serde_json::to_vec(&vec![felt!("0x1")]).unwrap()).await.is_err());
+}
@@ -1,4 +1,4 @@
-use opentelemetry::metrics::{Counter, Gauge, Meter};
+use opentelemetry::metrics::{Counter, Gauge, Histogram, Meter};
 
 pub trait Metrics {
     fn register() -> Self;
@@ -28,3 +28,12 @@ pub fn register_counter_metric_instrument(
 ) -> Counter<f64> {
     crate_meter.f64_counter(instrument_name).with_description(desc).with_unit(unit).init()
 }
+
+pub fn register_histogram_metric_instrument(
+    crate_meter: &Meter,
+    instrument_name: String,
+    desc: String,
+    unit: String,
+) -> Histogram<f64> {
+    crate_meter.f64_histogram(instrument_name).with_description(desc).with_unit(unit).init()
+}
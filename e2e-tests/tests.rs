@@ -12,7 +12,6 @@ use e2e_tests::starknet_client::StarknetClient;
 use e2e_tests::utils::{get_mongo_db_client, read_state_update_from_file, vec_u8_to_hex_string};
 use e2e_tests::{MongoDbServer, Orchestrator};
 use mongodb::bson::doc;
-use orchestrator::cli::database::DatabaseValidatedArgs;
 use orchestrator::constants::{
     BLOB_DATA_FILE_NAME, CAIRO_PIE_FILE_NAME, PROGRAM_OUTPUT_FILE_NAME, SNOS_OUTPUT_FILE_NAME,
 };
@@ -55,13 +54,11 @@ struct Setup {
 
 impl Setup {
     pub async fn new(l2_block_number: String) -> Self {
-        let db_params = DatabaseValidatedArgs::MongoDB(MongoDBValidatedArgs {
+        let mongodb_params = MongoDBValidatedArgs {
             connection_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_MONGODB_CONNECTION_URL"))
                 .expect("Invalid MongoDB connection URL"),
             database_name: get_env_var_or_panic("MADARA_ORCHESTRATOR_DATABASE_NAME"),
-        });
-
-        let DatabaseValidatedArgs::MongoDB(mongodb_params) = db_params;
+        };
 
         let mongo_db_instance = MongoDbServer::run(mongodb_params);
         println!("✅ Mongo DB setup completed");
@@ -319,7 +316,7 @@ pub async fn put_job_data_in_db_snos(mongo_db: &MongoDbServer, l2_block_number:
 /// Adding SNOS job in JOB_PROCESSING_QUEUE so that the job is triggered
 /// as soon as it is picked up by orchestrator
 pub async fn put_snos_job_in_processing_queue(id: Uuid, queue_params: AWSSQSValidatedArgs) -> color_eyre::Result<()> {
-    let message = JobQueueMessage { id };
+    let message = JobQueueMessage { id, trace_context: HashMap::new(), transient_retries: 0 };
     put_message_in_queue(
         message,
         format!(